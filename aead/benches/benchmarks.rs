@@ -16,6 +16,7 @@
 #![feature(test)]
 extern crate test;
 use test::Bencher;
+use tink_core::Aead;
 
 const MSG: &[u8] = b"this data needs to be encrypted";
 const AAD: &[u8] = b"this data needs to be authenticated, but not encrypted";
@@ -79,6 +80,26 @@ fn bench_aes256_gcm_decrypt_fail(b: &mut Bencher) {
     b.iter(|| a.decrypt(&ct, AAD).unwrap_err());
 }
 
+#[bench]
+fn bench_aes128_gcm_subtle_encrypt(b: &mut Bencher) {
+    let key = tink_core::subtle::random::get_random_bytes(16);
+    let a = tink_aead::subtle::AesGcm::new(&key).unwrap();
+    b.iter(|| a.encrypt(MSG, AAD).unwrap());
+}
+
+/// Comparison point for `bench_aes128_gcm_subtle_encrypt`, showing the per-message cost of
+/// expanding the key schedule if the primitive were not reused.
+#[bench]
+fn bench_aes128_gcm_subtle_new_encrypt(b: &mut Bencher) {
+    let key = tink_core::subtle::random::get_random_bytes(16);
+    b.iter(|| {
+        tink_aead::subtle::AesGcm::new(&key)
+            .unwrap()
+            .encrypt(MSG, AAD)
+            .unwrap()
+    });
+}
+
 #[bench]
 fn bench_aes128_gcm_siv_encrypt(b: &mut Bencher) {
     let (a, _ct) = setup(tink_aead::aes128_gcm_siv_key_template());
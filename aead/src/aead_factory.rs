@@ -16,11 +16,46 @@
 
 //! Provides an implementation of AEAD using a set of underlying implementations.
 
-use tink_core::{utils::wrap_err, TinkError};
+use std::sync::Arc;
+use tink_core::{monitoring, utils::wrap_err, TinkError};
 
 /// Returns a [`tink_core::Aead`] primitive from the given keyset handle.
 pub fn new(h: &tink_core::keyset::Handle) -> Result<Box<dyn tink_core::Aead>, TinkError> {
-    new_with_key_manager(h, None)
+    new_with_key_manager(h, None, AeadOptions::default())
+}
+
+/// Options for the [`tink_core::Aead`] primitive created by [`new_with_options`].  The default
+/// options give the same primitive as [`new`].
+#[derive(Clone, Debug, Default)]
+pub struct AeadOptions {
+    /// Maximum number of candidate keys to try when decrypting, if limited.
+    ///
+    /// This bounds the worst-case cost of decryption for keysets with many keys (in particular,
+    /// many RAW keys, all of which are candidates for every ciphertext).  Decryption fails if no
+    /// match has been found once the limit is reached.
+    pub max_decrypt_attempts: Option<usize>,
+    /// Counters to record each operation in, if any.
+    pub stats: Option<Arc<monitoring::Stats>>,
+    /// Emit RAW ciphertext (with no output prefix) even when the primary key is TINK- or
+    /// LEGACY-prefixed.
+    ///
+    /// This is intended for migrations where some consumer of the ciphertext cannot yet handle
+    /// output prefixes.  Ciphertext produced by the primitive can still be decrypted, as can
+    /// prefixed ciphertext.
+    ///
+    /// Security caveat: without the prefix, the key ID is lost, so decryption of such ciphertext
+    /// cannot be routed to the right key.  Every candidate key in the keyset may have to be tried
+    /// (which is also the case for RAW keys), so decryption cost grows with keyset size.
+    pub raw_output: bool,
+}
+
+/// Returns a [`tink_core::Aead`] primitive from the given keyset handle, configured by
+/// `options`.
+pub fn new_with_options(
+    h: &tink_core::keyset::Handle,
+    options: AeadOptions,
+) -> Result<Box<dyn tink_core::Aead>, TinkError> {
+    new_with_key_manager(h, None, options)
 }
 
 /// Return a [`tink_core::Aead`] primitive from the given keyset handle and custom key
@@ -28,24 +63,67 @@ pub fn new(h: &tink_core::keyset::Handle) -> Result<Box<dyn tink_core::Aead>, Ti
 fn new_with_key_manager(
     h: &tink_core::keyset::Handle,
     km: Option<std::sync::Arc<dyn tink_core::registry::KeyManager>>,
+    options: AeadOptions,
 ) -> Result<Box<dyn tink_core::Aead>, TinkError> {
+    if options.max_decrypt_attempts == Some(0) {
+        return Err("aead::factory: max_decrypt_attempts must be positive".into());
+    }
     let ps = h
-        .primitives_with_key_manager(km)
+        .primitives_with_key_manager(km.clone())
         .map_err(|e| wrap_err("aead::factory: cannot obtain primitive set", e))?;
 
-    let ret = WrappedAead::new(ps)?;
+    let mut ret = WrappedAead::new(ps, h.keyset_info(), options.max_decrypt_attempts)?;
+    ret.stats = options.stats;
+    ret.raw_output = options.raw_output;
+    #[cfg(feature = "rayon")]
+    {
+        ret.source = Some(Arc::new(h.primitive_source(km)?));
+    }
     Ok(Box::new(ret))
 }
 
+/// [`PrimitiveWrapper`](tink_core::registry::PrimitiveWrapper) that combines AEAD primitives,
+/// registered by [`init`](crate::init).
+pub struct AeadWrapper;
+
+impl tink_core::registry::PrimitiveWrapper for AeadWrapper {
+    fn primitive_name(&self) -> &'static str {
+        "aead"
+    }
+
+    fn wrap(
+        &self,
+        ps: tink_core::primitiveset::PrimitiveSet,
+        keyset_info: &tink_proto::KeysetInfo,
+    ) -> Result<tink_core::Primitive, TinkError> {
+        let aead = WrappedAead::new(ps, keyset_info.clone(), None)?;
+        Ok(tink_core::Primitive::Aead(Box::new(aead)))
+    }
+}
+
 /// `WrappedAead` is an AEAD implementation that uses the underlying primitive set for encryption
-/// and decryption.
+/// and decryption.  Decryptions are reported to the registered monitoring client.
 #[derive(Clone)]
 struct WrappedAead {
     ps: tink_core::primitiveset::TypedPrimitiveSet<Box<dyn tink_core::Aead>>,
+    decrypt_logger: Arc<dyn monitoring::Logger>,
+    /// Maximum number of candidate keys to try when decrypting, if limited.
+    max_decrypt_attempts: Option<usize>,
+    /// Operation counters, if requested.
+    stats: Option<Arc<monitoring::Stats>>,
+    /// Whether to omit the primary key's output prefix when encrypting.
+    raw_output: bool,
+    /// Source for the per-thread primitive sets used by `encrypt_batch`, if available.
+    #[cfg(feature = "rayon")]
+    source: Option<Arc<tink_core::keyset::PrimitiveSource>>,
 }
 
 impl WrappedAead {
-    fn new(ps: tink_core::primitiveset::PrimitiveSet) -> Result<WrappedAead, TinkError> {
+    fn new(
+        ps: tink_core::primitiveset::PrimitiveSet,
+        keyset_info: tink_proto::KeysetInfo,
+        max_decrypt_attempts: Option<usize>,
+    ) -> Result<WrappedAead, TinkError> {
         let entry = match &ps.primary {
             None => return Err("aead::factory: no primary primitive".into()),
             Some(p) => p,
@@ -62,29 +140,132 @@ impl WrappedAead {
                 };
             }
         }
+        let decrypt_logger = tink_core::registry::monitoring_client()
+            .new_logger(&monitoring::Context {
+                primitive: "aead",
+                api_function: "decrypt",
+                keyset_info,
+            })
+            .map_err(|e| wrap_err("aead::factory: cannot create monitoring logger", e))?;
         // The `.into()` call is only safe because we've just checked that all entries have
         // the right type of primitive
-        Ok(WrappedAead { ps: ps.into() })
+        Ok(WrappedAead {
+            ps: ps.into(),
+            decrypt_logger: decrypt_logger.into(),
+            max_decrypt_attempts,
+            stats: None,
+            raw_output: false,
+            #[cfg(feature = "rayon")]
+            source: None,
+        })
+    }
+
+    /// Record an attempt to decrypt with a further candidate key, failing if the limit on the
+    /// number of candidate keys has already been reached.
+    fn attempt(&self, attempts: &mut usize) -> Result<(), TinkError> {
+        if let Some(max) = self.max_decrypt_attempts {
+            if *attempts >= max {
+                self.decrypt_logger.log_failure();
+                return Err(format!(
+                    "aead::decrypt: decryption failed within limit of {max} candidate keys"
+                )
+                .into());
+            }
+        }
+        *attempts += 1;
+        Ok(())
+    }
+}
+
+/// Encrypt with the given primary entry, prepending its output prefix unless `raw_output` is
+/// set.
+fn encrypt_with(
+    primary: &tink_core::primitiveset::TypedEntry<Box<dyn tink_core::Aead>>,
+    raw_output: bool,
+    pt: &[u8],
+    aad: &[u8],
+) -> Result<Vec<u8>, TinkError> {
+    let ct = primary.primitive.encrypt(pt, aad)?;
+    if raw_output {
+        return Ok(ct);
     }
+
+    let mut ret = Vec::with_capacity(primary.prefix.len() + ct.len());
+    ret.extend_from_slice(&primary.prefix);
+    ret.extend_from_slice(&ct);
+    Ok(ret)
 }
 
 impl tink_core::Aead for WrappedAead {
     fn encrypt(&self, pt: &[u8], aad: &[u8]) -> Result<Vec<u8>, TinkError> {
-        let primary = self
+        let result = self
             .ps
             .primary
             .as_ref()
-            .ok_or_else(|| TinkError::new("no primary"))?;
-
-        let ct = primary.primitive.encrypt(pt, aad)?;
+            .ok_or_else(|| TinkError::new("no primary"))
+            .and_then(|primary| encrypt_with(primary, self.raw_output, pt, aad));
+        if let Some(stats) = &self.stats {
+            stats.record_encryption(&result);
+        }
+        result
+    }
 
-        let mut ret = Vec::with_capacity(primary.prefix.len() + ct.len());
-        ret.extend_from_slice(&primary.prefix);
-        ret.extend_from_slice(&ct);
-        Ok(ret)
+    /// Encrypt the batch in parallel across the rayon thread pool, with each worker thread
+    /// using its own primitive set.
+    #[cfg(feature = "rayon")]
+    fn encrypt_batch(&self, items: &[(&[u8], &[u8])]) -> Vec<Result<Vec<u8>, TinkError>> {
+        use rayon::prelude::*;
+        let source = match &self.source {
+            Some(source) => source,
+            None => {
+                return items
+                    .iter()
+                    .map(|(pt, aad)| self.encrypt(pt, aad))
+                    .collect()
+            }
+        };
+        let raw_output = self.raw_output;
+        let stats = self.stats.as_deref();
+        items
+            .par_iter()
+            .map_init(
+                || -> Result<tink_core::primitiveset::TypedEntry<Box<dyn tink_core::Aead>>, String> {
+                    let ps = source.primitives().map_err(|e| {
+                        format!("aead::factory: cannot obtain primitive set: {e}")
+                    })?;
+                    match ps.primary {
+                        Some(primary) => Ok(primary.into()),
+                        None => Err("no primary".to_string()),
+                    }
+                },
+                |primary, (pt, aad)| {
+                    let result = match primary {
+                        Ok(primary) => encrypt_with(primary, raw_output, pt, aad),
+                        Err(msg) => Err(msg.as_str().into()),
+                    };
+                    if let Some(stats) = stats {
+                        stats.record_encryption(&result);
+                    }
+                    result
+                },
+            )
+            .collect()
     }
 
     fn decrypt(&self, ct: &[u8], aad: &[u8]) -> Result<Vec<u8>, TinkError> {
+        let result = self.try_decrypt(ct, aad);
+        if let Some(stats) = &self.stats {
+            stats.record_decryption(&result);
+        }
+        result
+    }
+}
+
+impl WrappedAead {
+    /// Decrypt with each candidate key in turn, returning the first successful result.
+    fn try_decrypt(&self, ct: &[u8], aad: &[u8]) -> Result<Vec<u8>, TinkError> {
+        let mut attempts = 0;
+
         // try non-raw keys
         let prefix_size = tink_core::cryptofmt::NON_RAW_PREFIX_SIZE;
         if ct.len() > prefix_size {
@@ -92,7 +273,9 @@ impl tink_core::Aead for WrappedAead {
             let ct_no_prefix = &ct[prefix_size..];
             if let Some(entries) = self.ps.entries_for_prefix(prefix) {
                 for entry in entries {
+                    self.attempt(&mut attempts)?;
                     if let Ok(pt) = entry.primitive.decrypt(ct_no_prefix, aad) {
+                        self.decrypt_logger.log(entry.key_id, ct.len());
                         return Ok(pt);
                     }
                 }
@@ -102,13 +285,40 @@ impl tink_core::Aead for WrappedAead {
         // try raw keys
         if let Some(entries) = self.ps.raw_entries() {
             for entry in entries {
+                self.attempt(&mut attempts)?;
+                if let Ok(pt) = entry.primitive.decrypt(ct, aad) {
+                    self.decrypt_logger.log(entry.key_id, ct.len());
+                    return Ok(pt);
+                }
+            }
+        }
+
+        // try prefixed keys on unprefixed ciphertext, as emitted in raw output mode, starting
+        // with the primary
+        if self.raw_output {
+            let primary = self.ps.primary.iter();
+            let primary_id = self.ps.primary.as_ref().map(|p| p.key_id);
+            let others = self
+                .ps
+                .entries
+                .iter()
+                .filter(|(prefix, _)| !prefix.is_empty())
+                .flat_map(|(_, entries)| entries.iter())
+                .filter(|entry| Some(entry.key_id) != primary_id);
+            for entry in primary.chain(others) {
+                if entry.prefix.is_empty() {
+                    continue; // already tried
+                }
+                self.attempt(&mut attempts)?;
                 if let Ok(pt) = entry.primitive.decrypt(ct, aad) {
+                    self.decrypt_logger.log(entry.key_id, ct.len());
                     return Ok(pt);
                 }
             }
         }
 
         // nothing worked
+        self.decrypt_logger.log_failure();
         Err("aead::decrypt: decryption failed".into())
     }
 }
@@ -126,7 +126,9 @@ fn validate_aes_key(
         .params
         .as_ref()
         .ok_or_else(|| TinkError::new("AesCtrHmacAeadKeyManager: no AES key params"))?;
-    if (params.iv_size as usize) < subtle::AES_CTR_MIN_IV_SIZE || params.iv_size > 16 {
+    if (params.iv_size as usize) < subtle::AES_CTR_MIN_IV_SIZE
+        || params.iv_size as usize > subtle::AES_BLOCK_SIZE_IN_BYTES
+    {
         return Err(
             "AesCtrHmacAeadKeyManager: invalid AesCtrHmacAeadKey: IV size out of range".into(),
         );
@@ -176,7 +178,9 @@ fn validate_key_format(
         .params
         .as_ref()
         .ok_or_else(|| TinkError::new("AesCtrHmacAeadKeyManager: no AES key params"))?;
-    if (aes_params.iv_size as usize) < subtle::AES_CTR_MIN_IV_SIZE || aes_params.iv_size > 16 {
+    if (aes_params.iv_size as usize) < subtle::AES_CTR_MIN_IV_SIZE
+        || aes_params.iv_size as usize > subtle::AES_BLOCK_SIZE_IN_BYTES
+    {
         return Err(
             "AesCtrHmacAeadKeyManager: invalid AesCtrHmacAeadKeyFormat: IV size out of range"
                 .into(),
@@ -0,0 +1,63 @@
+// Copyright 2020 The Tink-Rust Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! Provide an AEAD combinator that falls back through a list of AEADs on decryption.
+
+use tink_core::TinkError;
+
+/// `FallbackAead` wraps an ordered list of [`tink_core::Aead`] instances.
+///
+/// Encryption always uses the first AEAD; decryption tries each AEAD in order and returns the
+/// first successful result.  Unlike [`VersionedAead`](crate::VersionedAead), ciphertexts carry
+/// no marker of which AEAD produced them, so existing ciphertexts can be read unchanged while a
+/// service migrates to a new cipher suite.
+pub struct FallbackAead {
+    aeads: Vec<Box<dyn tink_core::Aead>>,
+}
+
+/// Manual implementation of [`Clone`] relying on the trait bounds for
+/// primitives to provide `.box_clone()` methods.
+impl Clone for FallbackAead {
+    fn clone(&self) -> Self {
+        Self {
+            aeads: self.aeads.iter().map(|a| a.box_clone()).collect(),
+        }
+    }
+}
+
+impl FallbackAead {
+    /// Create a new `FallbackAead` that encrypts with the first entry of `aeads` and decrypts
+    /// with whichever entry first succeeds.
+    pub fn new(aeads: Vec<Box<dyn tink_core::Aead>>) -> Result<FallbackAead, TinkError> {
+        if aeads.is_empty() {
+            return Err("FallbackAead: no AEADs provided".into());
+        }
+        Ok(FallbackAead { aeads })
+    }
+}
+
+impl tink_core::Aead for FallbackAead {
+    fn encrypt(&self, pt: &[u8], aad: &[u8]) -> Result<Vec<u8>, TinkError> {
+        self.aeads[0].encrypt(pt, aad) // safe: checked in constructor
+    }
+
+    fn decrypt(&self, ct: &[u8], aad: &[u8]) -> Result<Vec<u8>, TinkError> {
+        self.aeads
+            .iter()
+            .find_map(|a| a.decrypt(ct, aad).ok())
+            .ok_or_else(|| "FallbackAead: decryption failed".into())
+    }
+}
@@ -16,24 +16,122 @@
 
 //! Provide an implementation of AEAD using a KMS.
 
-use std::convert::TryInto;
+use std::{cell::RefCell, collections::HashMap, convert::TryInto, rc::Rc};
 use tink_core::{utils::wrap_err, TinkError};
 
 const LEN_DEK: usize = 4;
 
 /// `KmsEnvelopeAead` represents an instance of Envelope AEAD.
+///
+/// The DEK template may be for either an AEAD or a deterministic AEAD (such as AES-SIV); in the
+/// latter case the payload encryption is deterministic for a given DEK, although each
+/// encryption still uses a fresh DEK.
+///
+/// Optionally (see [`KmsEnvelopeAead::with_dek_cache`]), the AEAD primitives for decrypted DEKs
+/// can be cached, keyed by encrypted DEK, so that repeated decryptions of ciphertexts that share a
+/// DEK do not need to call the remote KMS.  Clones of a `KmsEnvelopeAead` share a single cache.
 pub struct KmsEnvelopeAead {
     dek_template: tink_proto::KeyTemplate,
     remote: Box<dyn tink_core::Aead>,
+    dek_cache: Option<Rc<RefCell<DekCache>>>,
 }
 
-/// Manual implementation of [`Clone`] relying on the trait bounds for
-/// primitives to provide `.box_clone()` methods.
+/// Bounded least-recently-used cache of DEK primitives, keyed by encrypted DEK.  Only the
+/// primitives derived from the DEKs are held, never the DEK bytes themselves.
+struct DekCache {
+    capacity: usize,
+    /// Logical clock used to track recency of use.
+    tick: u64,
+    entries: HashMap<Vec<u8>, (u64, DekPrimitive)>,
+}
+
+/// Primitive for a DEK, which may be either an AEAD or a deterministic AEAD.
+enum DekPrimitive {
+    Aead(Box<dyn tink_core::Aead>),
+    DeterministicAead(Box<dyn tink_core::DeterministicAead>),
+}
+
+/// Manual implementation of [`Clone`] that clones the wrapped DEK primitive via its
+/// `.box_clone()` method.
+impl Clone for DekPrimitive {
+    fn clone(&self) -> Self {
+        match self {
+            DekPrimitive::Aead(p) => DekPrimitive::Aead(p.box_clone()),
+            DekPrimitive::DeterministicAead(p) => DekPrimitive::DeterministicAead(p.box_clone()),
+        }
+    }
+}
+
+impl DekPrimitive {
+    /// Convert a primitive created from a DEK.
+    fn new(p: tink_core::Primitive) -> Result<Self, TinkError> {
+        match p {
+            tink_core::Primitive::Aead(p) => Ok(DekPrimitive::Aead(p)),
+            tink_core::Primitive::DeterministicAead(p) => Ok(DekPrimitive::DeterministicAead(p)),
+            _ => Err("KmsEnvelopeAead: failed to convert AEAD primitive".into()),
+        }
+    }
+
+    fn encrypt(&self, pt: &[u8], aad: &[u8]) -> Result<Vec<u8>, TinkError> {
+        match self {
+            DekPrimitive::Aead(p) => p.encrypt(pt, aad),
+            DekPrimitive::DeterministicAead(p) => p.encrypt_deterministically(pt, aad),
+        }
+    }
+
+    fn decrypt(&self, ct: &[u8], aad: &[u8]) -> Result<Vec<u8>, TinkError> {
+        match self {
+            DekPrimitive::Aead(p) => p.decrypt(ct, aad),
+            DekPrimitive::DeterministicAead(p) => p.decrypt_deterministically(ct, aad),
+        }
+    }
+}
+
+impl DekCache {
+    fn new(capacity: usize) -> Self {
+        DekCache {
+            capacity,
+            tick: 0,
+            entries: HashMap::with_capacity(capacity),
+        }
+    }
+
+    fn get(&mut self, encrypted_dek: &[u8]) -> Option<DekPrimitive> {
+        self.tick += 1;
+        let tick = self.tick;
+        self.entries.get_mut(encrypted_dek).map(|(last_used, p)| {
+            *last_used = tick;
+            p.clone()
+        })
+    }
+
+    fn insert(&mut self, encrypted_dek: &[u8], primitive: DekPrimitive) {
+        if self.entries.len() >= self.capacity && !self.entries.contains_key(encrypted_dek) {
+            // Evict the least recently used entry.
+            if let Some(lru) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (last_used, _))| *last_used)
+                .map(|(k, _)| k.clone())
+            {
+                self.entries.remove(&lru);
+            }
+        }
+        self.tick += 1;
+        self.entries
+            .insert(encrypted_dek.to_vec(), (self.tick, primitive));
+    }
+}
+
+/// Manual implementation of [`Clone`] that clones the remote AEAD via its `.box_clone()` method.
+/// Any DEK cache is shared (not copied) between the original and its clones, so a DEK decrypted
+/// by one is available to all of them.
 impl Clone for KmsEnvelopeAead {
     fn clone(&self) -> Self {
         Self {
             dek_template: self.dek_template.clone(),
             remote: self.remote.box_clone(),
+            dek_cache: self.dek_cache.clone(),
         }
     }
 }
@@ -43,8 +141,43 @@ impl KmsEnvelopeAead {
         KmsEnvelopeAead {
             dek_template: kt,
             remote,
+            dek_cache: None,
         }
     }
+
+    /// Return this instance with a cache holding the DEK primitives for up to `capacity`
+    /// decrypted DEKs.  A `capacity` of zero disables caching.  Clones share the same cache.
+    pub fn with_dek_cache(mut self, capacity: usize) -> Self {
+        self.dek_cache = if capacity == 0 {
+            None
+        } else {
+            Some(Rc::new(RefCell::new(DekCache::new(capacity))))
+        };
+        self
+    }
+
+    /// Get the primitive corresponding to the encrypted DEK, using the remote KMS to decrypt the
+    /// DEK if it is not cached.
+    fn dek_primitive(&self, encrypted_dek: &[u8]) -> Result<DekPrimitive, TinkError> {
+        if let Some(cache) = &self.dek_cache {
+            if let Some(p) = cache.borrow_mut().get(encrypted_dek) {
+                return Ok(p);
+            }
+        }
+
+        // Decrypt the DEK.
+        let dek = self.remote.decrypt(encrypted_dek, &[])?;
+
+        // Get the primitive corresponding to the DEK.
+        let p = tink_core::registry::primitive(&self.dek_template.type_url, &dek)
+            .map_err(|e| wrap_err("KmsEnvelopeAead", e))?;
+        let primitive = DekPrimitive::new(p)?;
+
+        if let Some(cache) = &self.dek_cache {
+            cache.borrow_mut().insert(encrypted_dek, primitive.clone());
+        }
+        Ok(primitive)
+    }
 }
 
 impl tink_core::Aead for KmsEnvelopeAead {
@@ -53,10 +186,10 @@ impl tink_core::Aead for KmsEnvelopeAead {
         let dek = tink_core::registry::new_key(&self.dek_template)?;
         let encrypted_dek = self.remote.encrypt(&dek, &[])?;
 
-        let primitive = match tink_core::registry::primitive(&self.dek_template.type_url, &dek)? {
-            tink_core::Primitive::Aead(p) => p,
-            _ => return Err("KmsEnvelopeAead: failed to convert AEAD primitive".into()),
-        };
+        let primitive = DekPrimitive::new(tink_core::registry::primitive(
+            &self.dek_template.type_url,
+            &dek,
+        )?)?;
         let payload = primitive.encrypt(pt, aad)?;
         build_cipher_text(&encrypted_dek, &payload)
     }
@@ -80,16 +213,7 @@ impl tink_core::Aead for KmsEnvelopeAead {
         let encrypted_dek = &ct[..ed];
         let payload = &ct[ed..];
 
-        // Decrypt the DEK.
-        let dek = self.remote.decrypt(encrypted_dek, &[])?;
-
-        // Get an AEAD primitive corresponding to the DEK.
-        let p = tink_core::registry::primitive(&self.dek_template.type_url, &dek)
-            .map_err(|e| wrap_err("KmsEnvelopeAead", e))?;
-        let primitive = match p {
-            tink_core::Primitive::Aead(p) => p,
-            _ => return Err("KmsEnvelopeAead: failed to convert AEAD primitive".into()),
-        };
+        let primitive = self.dek_primitive(encrypted_dek)?;
 
         // Decrypt the payload.
         primitive.decrypt(payload, aad)
@@ -36,10 +36,16 @@ mod aes_gcm_siv_key_manager;
 pub use aes_gcm_siv_key_manager::*;
 mod chacha20poly1305_key_manager;
 pub use chacha20poly1305_key_manager::*;
+mod fallback_aead;
+pub use fallback_aead::*;
 mod kms_envelope_aead;
 pub use kms_envelope_aead::*;
 mod kms_envelope_aead_key_manager;
 pub use kms_envelope_aead_key_manager::*;
+mod rate_limited_aead;
+pub use rate_limited_aead::*;
+mod versioned_aead;
+pub use versioned_aead::*;
 mod xchacha20poly1305_key_manager;
 pub use xchacha20poly1305_key_manager::*;
 
@@ -67,40 +73,52 @@ pub fn init() {
             .expect("tink_aead::init() failed"); // safe: init
         register_key_manager(std::sync::Arc::new(KmsEnvelopeAeadKeyManager::default()))
             .expect("tink_aead::init() failed"); // safe:init
+        tink_core::registry::register_primitive_wrapper(std::sync::Arc::new(AeadWrapper))
+            .expect("tink_aead::init() failed"); // safe: init
 
-        tink_core::registry::register_template_generator("AES128_GCM", aes128_gcm_key_template);
-        tink_core::registry::register_template_generator("AES256_GCM", aes256_gcm_key_template);
+        tink_core::registry::register_template_generator("AES128_GCM", aes128_gcm_key_template)
+            .expect("tink_aead::init() failed"); // safe: init
+        tink_core::registry::register_template_generator("AES256_GCM", aes256_gcm_key_template)
+            .expect("tink_aead::init() failed"); // safe: init
         tink_core::registry::register_template_generator(
             "AES256_GCM_NO_PREFIX",
             aes256_gcm_no_prefix_key_template,
-        );
+        )
+        .expect("tink_aead::init() failed"); // safe: init
         tink_core::registry::register_template_generator(
             "AES128_GCM_SIV",
             aes128_gcm_siv_key_template,
-        );
+        )
+        .expect("tink_aead::init() failed"); // safe: init
         tink_core::registry::register_template_generator(
             "AES256_GCM_SIV",
             aes256_gcm_siv_key_template,
-        );
+        )
+        .expect("tink_aead::init() failed"); // safe: init
         tink_core::registry::register_template_generator(
             "AES256_GCM_SIV_NO_PREFIX",
             aes256_gcm_siv_no_prefix_key_template,
-        );
+        )
+        .expect("tink_aead::init() failed"); // safe: init
         tink_core::registry::register_template_generator(
             "AES128_CTR_HMAC_SHA256",
             aes128_ctr_hmac_sha256_key_template,
-        );
+        )
+        .expect("tink_aead::init() failed"); // safe: init
         tink_core::registry::register_template_generator(
             "AES256_CTR_HMAC_SHA256",
             aes256_ctr_hmac_sha256_key_template,
-        );
+        )
+        .expect("tink_aead::init() failed"); // safe: init
         tink_core::registry::register_template_generator(
             "CHACHA20_POLY1305",
             cha_cha20_poly1305_key_template,
-        );
+        )
+        .expect("tink_aead::init() failed"); // safe: init
         tink_core::registry::register_template_generator(
             "XCHACHA20_POLY1305",
             x_cha_cha20_poly1305_key_template,
-        );
+        )
+        .expect("tink_aead::init() failed"); // safe: init
     });
 }
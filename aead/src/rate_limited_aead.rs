@@ -0,0 +1,123 @@
+// Copyright 2020 The Tink-Rust Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! Provide an AEAD combinator that trips after repeated decryption failures.
+
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tink_core::TinkError;
+
+/// Error message returned by [`RateLimitedAead`] once its failure threshold has been reached.
+pub const RATE_LIMITED_AEAD_TRIPPED: &str = "RateLimitedAead: too many decryption failures";
+
+#[derive(Default)]
+struct FailureState {
+    /// Number of consecutive decryption failures in the current window.
+    failures: usize,
+    /// Time of the first failure in the current window.
+    window_start: Option<Instant>,
+}
+
+/// `RateLimitedAead` wraps an [`tink_core::Aead`] and acts as a circuit breaker for
+/// decryption.
+///
+/// Once `threshold` consecutive decryption failures have been observed within `window`,
+/// subsequent calls to `decrypt` fail with [`RATE_LIMITED_AEAD_TRIPPED`] without being passed
+/// to the wrapped primitive, until the window expires.  A successful decryption resets the
+/// failure count.  Encryption is always delegated.
+///
+/// Clones share the same failure state.
+pub struct RateLimitedAead {
+    inner: Box<dyn tink_core::Aead>,
+    threshold: usize,
+    window: Duration,
+    state: Arc<Mutex<FailureState>>,
+}
+
+/// Manual implementation of [`Clone`] relying on the trait bounds for
+/// primitives to provide `.box_clone()` methods.
+impl Clone for RateLimitedAead {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.box_clone(),
+            threshold: self.threshold,
+            window: self.window,
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl RateLimitedAead {
+    /// Create a new `RateLimitedAead` that trips after `threshold` consecutive decryption
+    /// failures within `window`.
+    pub fn new(
+        inner: Box<dyn tink_core::Aead>,
+        threshold: usize,
+        window: Duration,
+    ) -> Result<RateLimitedAead, TinkError> {
+        if threshold == 0 {
+            return Err("RateLimitedAead: threshold must be positive".into());
+        }
+        Ok(RateLimitedAead {
+            inner,
+            threshold,
+            window,
+            state: Arc::new(Mutex::new(FailureState::default())),
+        })
+    }
+
+    /// Indicate whether the breaker is currently tripped.
+    pub fn is_tripped(&self) -> bool {
+        let mut state = self.state.lock().expect("internal lock corrupted"); // safe: lock
+        self.expire(&mut state);
+        state.failures >= self.threshold
+    }
+
+    /// Forget failures that fall outside the current window.
+    fn expire(&self, state: &mut FailureState) {
+        if let Some(start) = state.window_start {
+            if start.elapsed() > self.window {
+                *state = FailureState::default();
+            }
+        }
+    }
+}
+
+impl tink_core::Aead for RateLimitedAead {
+    fn encrypt(&self, pt: &[u8], aad: &[u8]) -> Result<Vec<u8>, TinkError> {
+        self.inner.encrypt(pt, aad)
+    }
+
+    fn decrypt(&self, ct: &[u8], aad: &[u8]) -> Result<Vec<u8>, TinkError> {
+        if self.is_tripped() {
+            return Err(RATE_LIMITED_AEAD_TRIPPED.into());
+        }
+        let result = self.inner.decrypt(ct, aad);
+        let mut state = self.state.lock().expect("internal lock corrupted"); // safe: lock
+        match result {
+            Ok(_) => *state = FailureState::default(),
+            Err(_) => {
+                if state.window_start.is_none() {
+                    state.window_start = Some(Instant::now());
+                }
+                state.failures += 1;
+            }
+        }
+        result
+    }
+}
@@ -17,69 +17,8 @@
 //! AES-CTR implementation of [`IndCpaCipher`](super::IndCpaCipher).
 
 use super::IndCpaCipher;
-use aes::cipher::{consts::U16, generic_array::GenericArray, KeyIvInit, StreamCipher};
-use std::convert::TryInto;
-use tink_core::{utils::wrap_err, TinkError};
-
-/// The minimum IV size that this implementation supports.
-pub const AES_CTR_MIN_IV_SIZE: usize = 12;
-
-pub const AES_BLOCK_SIZE_IN_BYTES: usize = 16;
-
-type Aes128Ctr = ::ctr::Ctr64BE<aes::Aes128>;
-type Aes256Ctr = ::ctr::Ctr64BE<aes::Aes256>;
-
-#[allow(clippy::large_enum_variant)]
-#[derive(Clone)]
-enum AesCtrVariant {
-    Aes128([u8; 16]),
-    Aes256([u8; 32]),
-}
-
-/// `AesCtr` is an implementation of AEAD interface.
-#[derive(Clone)]
-pub struct AesCtr {
-    key: AesCtrVariant,
-    pub iv_size: usize,
-}
-
-impl AesCtr {
-    /// Return an `AesCtr` instance.  The key argument should be the AES key, either 16 or 32 bytes
-    /// to select AES-128 or AES-256.  `iv_size` specifies the size of the IV in bytes.
-    pub fn new(key: &[u8], iv_size: usize) -> Result<AesCtr, TinkError> {
-        let key_size = key.len();
-        super::validate_aes_key_size(key_size).map_err(|e| wrap_err("AesCtr", e))?;
-        if !(AES_CTR_MIN_IV_SIZE..=AES_BLOCK_SIZE_IN_BYTES).contains(&iv_size) {
-            return Err(format!("AesCtr: invalid IV size: {iv_size}").into());
-        }
-        let key = match key.len() {
-            16 => {
-                AesCtrVariant::Aes128(key.to_vec().try_into().unwrap(/* safe: len checked */))
-            }
-            32 => {
-                AesCtrVariant::Aes256(key.to_vec().try_into().unwrap(/* safe: len checked */))
-            }
-            l => return Err(format!("AesCtr: invalid AES key size {l} (want 16, 32)").into()),
-        };
-        Ok(AesCtr { key, iv_size })
-    }
-
-    /// Return the length of the key.
-    pub fn key_len(&self) -> usize {
-        match &self.key {
-            AesCtrVariant::Aes128(_) => 16,
-            AesCtrVariant::Aes256(_) => 32,
-        }
-    }
-
-    /// Create a new IV for encryption.
-    fn new_iv(&self) -> GenericArray<u8, U16> {
-        let mut padded_iv = [0; AES_BLOCK_SIZE_IN_BYTES];
-        let iv = tink_core::subtle::random::get_random_bytes(self.iv_size);
-        padded_iv[..iv.len()].copy_from_slice(&iv);
-        padded_iv.into()
-    }
-}
+pub use tink_core::subtle::{AesCtr, AES_BLOCK_SIZE_IN_BYTES, AES_CTR_MIN_IV_SIZE};
+use tink_core::TinkError;
 
 impl IndCpaCipher for AesCtr {
     /// Encrypt plaintext using AES in CTR mode.
@@ -89,23 +28,11 @@ impl IndCpaCipher for AesCtr {
         if plaintext.len() > ((isize::MAX as usize) - self.iv_size) {
             return Err("AesCtr: plaintext too long".into());
         }
-        let iv = self.new_iv();
         let mut ciphertext = Vec::with_capacity(self.iv_size + plaintext.len());
-        ciphertext.extend_from_slice(&iv[..self.iv_size]);
+        ciphertext.extend_from_slice(&tink_core::subtle::random::get_random_bytes(self.iv_size));
         ciphertext.extend_from_slice(plaintext);
-        match &self.key {
-            AesCtrVariant::Aes128(key) => {
-                let mut stream =
-                    Aes128Ctr::new_from_slices(key, &iv).unwrap(/* safe: len checked */);
-                stream.apply_keystream(&mut ciphertext[self.iv_size..]);
-            }
-            AesCtrVariant::Aes256(key) => {
-                let mut stream =
-                    Aes256Ctr::new_from_slices(key, &iv).unwrap(/* safe: len checked */);
-                stream.apply_keystream(&mut ciphertext[self.iv_size..]);
-            }
-        }
-
+        let (iv, data) = ciphertext.split_at_mut(self.iv_size);
+        self.apply_keystream(iv, data)?;
         Ok(ciphertext)
     }
 
@@ -115,24 +42,9 @@ impl IndCpaCipher for AesCtr {
             return Err("AesCtr: ciphertext too short".into());
         }
 
-        let mut padded_iv = [0; AES_BLOCK_SIZE_IN_BYTES];
-        padded_iv[..self.iv_size].copy_from_slice(&ciphertext[..self.iv_size]);
-
-        let mut plaintext = Vec::with_capacity(ciphertext.len() - self.iv_size);
-        plaintext.extend_from_slice(&ciphertext[self.iv_size..]);
-
-        match &self.key {
-            AesCtrVariant::Aes128(key) => {
-                let mut stream =
-                    Aes128Ctr::new_from_slices(key, &padded_iv).unwrap(/* safe: len checked */);
-                stream.apply_keystream(&mut plaintext);
-            }
-            AesCtrVariant::Aes256(key) => {
-                let mut stream =
-                    Aes256Ctr::new_from_slices(key, &padded_iv).unwrap(/* safe: len checked */);
-                stream.apply_keystream(&mut plaintext);
-            }
-        }
+        let (iv, data) = ciphertext.split_at(self.iv_size);
+        let mut plaintext = data.to_vec();
+        self.apply_keystream(iv, &mut plaintext)?;
         Ok(plaintext)
     }
 }
@@ -17,7 +17,11 @@
 //! AES-GCM based implementation of the [`tink_core::Aead`] trait.
 
 use aes_gcm::{
-    aead::{consts::U12, generic_array::GenericArray, Aead, Payload},
+    aead::{
+        consts::U12,
+        generic_array::{typenum::Unsigned, GenericArray},
+        Aead, AeadCore, AeadInPlace, Payload,
+    },
     KeyInit,
 };
 use tink_core::{utils::wrap_err, TinkError};
@@ -28,6 +32,11 @@ pub const AES_GCM_IV_SIZE: usize = 12;
 pub const AES_GCM_TAG_SIZE: usize = 16;
 /// The maximum supported plaintext size.
 const MAX_AES_GCM_PLAINTEXT_SIZE: u64 = (1 << 36) - 32;
+/// The default maximum additional authenticated data size.
+pub const DEFAULT_MAX_AES_GCM_AAD_SIZE: usize = 1 << 31;
+
+/// Output of [`AesGcm::encrypt_detached`], as `(ciphertext, iv, tag)`.
+pub type DetachedCiphertext = (Vec<u8>, Vec<u8>, Vec<u8>);
 
 #[derive(Clone)]
 enum AesGcmVariant {
@@ -36,9 +45,16 @@ enum AesGcmVariant {
 }
 
 /// `AesGcm` is an implementation of the [`tink_core::Aead`] trait.
+///
+/// The AES key schedule is expanded once, in [`AesGcm::new`], and reused for every subsequent
+/// operation (clones copy the expanded schedule), so a single instance should be kept for
+/// encrypting many messages under the same key.
 #[derive(Clone)]
 pub struct AesGcm {
     key: AesGcmVariant,
+    max_aad_size: usize,
+    #[cfg(all(feature = "nonce-reuse-detection", debug_assertions))]
+    nonce_detector: super::NonceReuseDetector,
 }
 
 impl AesGcm {
@@ -47,15 +63,138 @@ impl AesGcm {
     /// AES-128 or AES-256.
     pub fn new(key: &[u8]) -> Result<AesGcm, TinkError> {
         let key = match key.len() {
-            16 => AesGcmVariant::Aes128(Box::new(aes_gcm::Aes128Gcm::new(
-                GenericArray::from_slice(key),
-            ))),
-            32 => AesGcmVariant::Aes256(Box::new(aes_gcm::Aes256Gcm::new(
-                GenericArray::from_slice(key),
-            ))),
+            16 => {
+                check_sizes::<aes_gcm::Aes128Gcm>()?;
+                AesGcmVariant::Aes128(Box::new(aes_gcm::Aes128Gcm::new(GenericArray::from_slice(
+                    key,
+                ))))
+            }
+            32 => {
+                check_sizes::<aes_gcm::Aes256Gcm>()?;
+                AesGcmVariant::Aes256(Box::new(aes_gcm::Aes256Gcm::new(GenericArray::from_slice(
+                    key,
+                ))))
+            }
             l => return Err(format!("AesGcm: invalid AES key size {l} (want 16, 32)").into()),
         };
-        Ok(AesGcm { key })
+        Ok(AesGcm {
+            key,
+            max_aad_size: DEFAULT_MAX_AES_GCM_AAD_SIZE,
+            #[cfg(all(feature = "nonce-reuse-detection", debug_assertions))]
+            nonce_detector: super::NonceReuseDetector::new(),
+        })
+    }
+
+    /// Return this instance with the maximum permitted additional authenticated data size set
+    /// to `max_aad_size` bytes (default [`DEFAULT_MAX_AES_GCM_AAD_SIZE`]).  Longer AAD is
+    /// rejected by both `encrypt` and `decrypt`.
+    pub fn with_max_aad_size(mut self, max_aad_size: usize) -> Self {
+        self.max_aad_size = max_aad_size;
+        self
+    }
+
+    /// Return the detector that checks the nonces used by this instance (and its clones) for
+    /// reuse.  Only available in debug builds with the `nonce-reuse-detection` feature.
+    #[cfg(all(feature = "nonce-reuse-detection", debug_assertions))]
+    #[cfg_attr(docsrs, doc(cfg(feature = "nonce-reuse-detection")))]
+    pub fn nonce_reuse_detector(&self) -> &super::NonceReuseDetector {
+        &self.nonce_detector
+    }
+
+    /// Generate a fresh IV, checking it for reuse if nonce-reuse detection is enabled.
+    fn next_iv(&self) -> GenericArray<u8, U12> {
+        let iv = new_iv();
+        #[cfg(all(feature = "nonce-reuse-detection", debug_assertions))]
+        self.nonce_detector.observe(&iv);
+        iv
+    }
+
+    /// Check that `aad` does not exceed the configured maximum size.
+    fn check_aad_size(&self, aad: &[u8]) -> Result<(), TinkError> {
+        if aad.len() > self.max_aad_size {
+            Err("AesGcm: additional data too long".into())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Return the size of the nonce (IV) that prefixes each ciphertext.
+    pub fn nonce_size(&self) -> usize {
+        AES_GCM_IV_SIZE
+    }
+
+    /// Return the size of the authentication tag that suffixes each ciphertext.
+    pub fn tag_size(&self) -> usize {
+        AES_GCM_TAG_SIZE
+    }
+
+    /// Encrypt `pt` with `aad` as additional authenticated data, returning the ciphertext, the
+    /// IV and the authentication tag separately.
+    ///
+    /// The combined output of [`encrypt`](tink_core::Aead::encrypt) is the concatenation
+    /// `iv || ciphertext || tag`.
+    pub fn encrypt_detached(&self, pt: &[u8], aad: &[u8]) -> Result<DetachedCiphertext, TinkError> {
+        if pt.len() as u64 > max_pt_size() {
+            return Err("AesGcm: plaintext too long".into());
+        }
+        self.check_aad_size(aad)?;
+        let iv = self.next_iv();
+        let mut ct = pt.to_vec();
+        let tag = match &self.key {
+            AesGcmVariant::Aes128(key) => key.encrypt_in_place_detached(&iv, aad, &mut ct),
+            AesGcmVariant::Aes256(key) => key.encrypt_in_place_detached(&iv, aad, &mut ct),
+        }
+        .map_err(|e| wrap_err("AesGcm", e))?;
+        Ok((ct, iv.to_vec(), tag.to_vec()))
+    }
+
+    /// Decrypt `ct` using the given `iv` and authentication `tag`, with `aad` as the additional
+    /// authenticated data, as produced by [`AesGcm::encrypt_detached`].
+    pub fn decrypt_detached(
+        &self,
+        ct: &[u8],
+        iv: &[u8],
+        tag: &[u8],
+        aad: &[u8],
+    ) -> Result<Vec<u8>, TinkError> {
+        if iv.len() != AES_GCM_IV_SIZE {
+            return Err("AesGcm: invalid IV size".into());
+        }
+        if tag.len() != AES_GCM_TAG_SIZE {
+            return Err("AesGcm: invalid tag size".into());
+        }
+        self.check_aad_size(aad)?;
+        let iv = GenericArray::from_slice(iv);
+        let tag = GenericArray::from_slice(tag);
+        let mut pt = ct.to_vec();
+        match &self.key {
+            AesGcmVariant::Aes128(key) => key.decrypt_in_place_detached(iv, aad, &mut pt, tag),
+            AesGcmVariant::Aes256(key) => key.decrypt_in_place_detached(iv, aad, &mut pt, tag),
+        }
+        .map_err(|e| wrap_err("AesGcm", e))?;
+        Ok(pt)
+    }
+}
+
+/// Check that the nonce and tag sizes of the underlying cipher implementation match the sizes
+/// that this implementation assumes.
+fn check_sizes<C: AeadCore>() -> Result<(), TinkError> {
+    if C::NonceSize::USIZE != AES_GCM_IV_SIZE {
+        Err(format!(
+            "AesGcm: unexpected nonce size {} (want {})",
+            C::NonceSize::USIZE,
+            AES_GCM_IV_SIZE
+        )
+        .into())
+    } else if C::TagSize::USIZE != AES_GCM_TAG_SIZE {
+        Err(format!(
+            "AesGcm: unexpected tag size {} (want {})",
+            C::TagSize::USIZE,
+            AES_GCM_TAG_SIZE
+        )
+        .into())
+    } else {
+        Ok(())
     }
 }
 
@@ -68,7 +207,8 @@ impl tink_core::Aead for AesGcm {
         if pt.len() as u64 > max_pt_size() {
             return Err("AesGcm: plaintext too long".into());
         }
-        let iv = new_iv();
+        self.check_aad_size(aad)?;
+        let iv = self.next_iv();
         let payload = Payload { msg: pt, aad };
         let ct = match &self.key {
             AesGcmVariant::Aes128(key) => key.encrypt(&iv, payload),
@@ -86,6 +226,7 @@ impl tink_core::Aead for AesGcm {
         if ct.len() < AES_GCM_IV_SIZE + AES_GCM_TAG_SIZE {
             return Err("AesGcm: ciphertext too short".into());
         }
+        self.check_aad_size(aad)?;
         let iv = GenericArray::from_slice(&ct[..AES_GCM_IV_SIZE]);
         let payload = Payload {
             msg: &ct[AES_GCM_IV_SIZE..],
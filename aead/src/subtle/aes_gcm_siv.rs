@@ -55,33 +55,52 @@ impl AesGcmSiv {
         };
         Ok(AesGcmSiv { key })
     }
-}
 
-impl tink_core::Aead for AesGcmSiv {
-    /// Encrypt `pt` with `aad` as additional authenticated data.
+    /// Encrypt `pt` with `aad` as additional authenticated data, using the given `nonce` rather
+    /// than a random one.  The output has the same format as [`encrypt`](tink_core::Aead::encrypt).
     ///
-    /// The resulting ciphertext consists of two parts: (1) the IV used for encryption and (2) the
-    /// actual ciphertext (which itself is built of two parts, the inner ciphertext followed by
-    /// an authentication tag).
-    fn encrypt(&self, pt: &[u8], aad: &[u8]) -> Result<Vec<u8>, TinkError> {
+    /// Normally [`encrypt`](tink_core::Aead::encrypt) should be used instead.  AES-GCM-SIV is
+    /// resistant to nonce misuse: encrypting with a repeated nonce reveals only whether the
+    /// (plaintext, additional data) pairs are identical, as identical pairs produce identical
+    /// ciphertexts.
+    pub fn encrypt_with_nonce(
+        &self,
+        nonce: &[u8],
+        pt: &[u8],
+        aad: &[u8],
+    ) -> Result<Vec<u8>, TinkError> {
+        if nonce.len() != AES_GCM_SIV_NONCE_SIZE {
+            return Err("AesGcmSiv: invalid nonce size".into());
+        }
         if pt.len() > ((isize::MAX as usize) - AES_GCM_SIV_NONCE_SIZE - AES_GCM_SIV_TAG_SIZE) {
             return Err("AesGcmSiv: plaintext too long".into());
         }
         if aad.len() > (isize::MAX as usize) {
             return Err("AesGcmSiv: additional-data too long".into());
         }
-        let iv = new_iv();
+        let iv = GenericArray::<u8, U12>::from_slice(nonce);
         let payload = Payload { msg: pt, aad };
         let ct = match &self.key {
-            AesGcmSivVariant::Aes128(key) => key.encrypt(&iv, payload),
-            AesGcmSivVariant::Aes256(key) => key.encrypt(&iv, payload),
+            AesGcmSivVariant::Aes128(key) => key.encrypt(iv, payload),
+            AesGcmSivVariant::Aes256(key) => key.encrypt(iv, payload),
         }
         .map_err(|e| wrap_err("AesGcmSiv", e))?;
         let mut ret = Vec::with_capacity(iv.len() + ct.len());
-        ret.extend_from_slice(&iv);
+        ret.extend_from_slice(iv);
         ret.extend_from_slice(&ct);
         Ok(ret)
     }
+}
+
+impl tink_core::Aead for AesGcmSiv {
+    /// Encrypt `pt` with `aad` as additional authenticated data.
+    ///
+    /// The resulting ciphertext consists of two parts: (1) the IV used for encryption and (2) the
+    /// actual ciphertext (which itself is built of two parts, the inner ciphertext followed by
+    /// an authentication tag).
+    fn encrypt(&self, pt: &[u8], aad: &[u8]) -> Result<Vec<u8>, TinkError> {
+        self.encrypt_with_nonce(&new_iv(), pt, aad)
+    }
 
     /// Decrypt `ct` with `aad` as the additional authenticated data.
     fn decrypt(&self, ct: &[u8], aad: &[u8]) -> Result<Vec<u8>, TinkError> {
@@ -30,5 +30,10 @@ mod encrypt_then_authenticate;
 pub use encrypt_then_authenticate::*;
 mod ind_cpa;
 pub use ind_cpa::*;
+#[cfg(all(feature = "nonce-reuse-detection", debug_assertions))]
+#[cfg_attr(docsrs, doc(cfg(feature = "nonce-reuse-detection")))]
+mod nonce_reuse;
+#[cfg(all(feature = "nonce-reuse-detection", debug_assertions))]
+pub use nonce_reuse::*;
 mod xchacha20poly1305;
 pub use self::xchacha20poly1305::*;
@@ -0,0 +1,85 @@
+// Copyright 2020 The Tink-Rust Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! Development aid for detecting nonce reuse.
+
+use std::{
+    collections::{HashSet, VecDeque},
+    sync::{Arc, Mutex},
+};
+
+/// Number of recent nonces remembered by a [`NonceReuseDetector`].
+pub const NONCE_REUSE_DETECTOR_WINDOW: usize = 4096;
+
+#[derive(Default)]
+struct RecentNonces {
+    order: VecDeque<Vec<u8>>,
+    seen: HashSet<Vec<u8>>,
+}
+
+/// `NonceReuseDetector` remembers the most recent [`NONCE_REUSE_DETECTOR_WINDOW`] nonces used
+/// with a key, and panics if one of them is used again.  This is intended to catch a broken
+/// nonce source during development, and is only available in debug builds with the
+/// `nonce-reuse-detection` feature enabled.
+///
+/// Clones share the same history.
+#[derive(Clone, Default)]
+pub struct NonceReuseDetector {
+    recent: Arc<Mutex<RecentNonces>>,
+}
+
+impl NonceReuseDetector {
+    /// Create a new detector with an empty history.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that `nonce` is about to be used.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `nonce` is among the most recently recorded nonces.
+    pub fn observe(&self, nonce: &[u8]) {
+        let reused = {
+            let mut recent = self.recent.lock().expect("internal lock corrupted"); // safe: lock
+            recent.record(nonce)
+        };
+        // Panic after releasing the lock, so the detector stays usable.
+        if reused {
+            panic!("nonce reuse detected: {}", hex_encode(nonce));
+        }
+    }
+}
+
+impl RecentNonces {
+    /// Record `nonce`, returning `true` if it was already present.
+    fn record(&mut self, nonce: &[u8]) -> bool {
+        if !self.seen.insert(nonce.to_vec()) {
+            return true;
+        }
+        self.order.push_back(nonce.to_vec());
+        if self.order.len() > NONCE_REUSE_DETECTOR_WINDOW {
+            if let Some(oldest) = self.order.pop_front() {
+                self.seen.remove(&oldest);
+            }
+        }
+        false
+    }
+}
+
+fn hex_encode(data: &[u8]) -> String {
+    data.iter().map(|b| format!("{b:02x}")).collect()
+}
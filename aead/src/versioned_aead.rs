@@ -0,0 +1,84 @@
+// Copyright 2020 The Tink-Rust Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! Provide an AEAD combinator that tags ciphertexts with a version byte.
+
+use std::collections::HashMap;
+use tink_core::{utils::wrap_err, TinkError};
+
+/// `VersionedAead` prefixes each ciphertext with a 1-byte version tag identifying the inner
+/// [`tink_core::Aead`] that produced it.
+///
+/// Encryption always uses the AEAD for the current version; decryption dispatches on the version
+/// tag, so ciphertexts produced under older versions remain readable while a service migrates to
+/// a new cipher suite.
+pub struct VersionedAead {
+    current: u8,
+    aeads: HashMap<u8, Box<dyn tink_core::Aead>>,
+}
+
+/// Manual implementation of [`Clone`] relying on the trait bounds for
+/// primitives to provide `.box_clone()` methods.
+impl Clone for VersionedAead {
+    fn clone(&self) -> Self {
+        Self {
+            current: self.current,
+            aeads: self
+                .aeads
+                .iter()
+                .map(|(v, a)| (*v, a.box_clone()))
+                .collect(),
+        }
+    }
+}
+
+impl VersionedAead {
+    /// Create a new `VersionedAead` that encrypts with the AEAD registered for `current` and
+    /// decrypts with whichever AEAD in `aeads` matches the ciphertext's version tag.
+    pub fn new(
+        current: u8,
+        aeads: HashMap<u8, Box<dyn tink_core::Aead>>,
+    ) -> Result<VersionedAead, TinkError> {
+        if !aeads.contains_key(&current) {
+            return Err(format!("VersionedAead: no AEAD for current version {current}").into());
+        }
+        Ok(VersionedAead { current, aeads })
+    }
+}
+
+impl tink_core::Aead for VersionedAead {
+    fn encrypt(&self, pt: &[u8], aad: &[u8]) -> Result<Vec<u8>, TinkError> {
+        let aead = &self.aeads[&self.current]; // safe: checked in constructor
+        let ct = aead.encrypt(pt, aad)?;
+        let mut ret = Vec::with_capacity(1 + ct.len());
+        ret.push(self.current);
+        ret.extend_from_slice(&ct);
+        Ok(ret)
+    }
+
+    fn decrypt(&self, ct: &[u8], aad: &[u8]) -> Result<Vec<u8>, TinkError> {
+        if ct.is_empty() {
+            return Err("VersionedAead: ciphertext too short".into());
+        }
+        let version = ct[0];
+        let aead = self
+            .aeads
+            .get(&version)
+            .ok_or_else(|| TinkError::new(&format!("VersionedAead: unknown version {version}")))?;
+        aead.decrypt(&ct[1..], aad)
+            .map_err(|e| wrap_err("VersionedAead: decryption failed", e))
+    }
+}
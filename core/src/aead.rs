@@ -40,6 +40,15 @@ pub trait Aead: AeadBoxClone {
         ciphertext: &[u8],
         additional_data: &[u8],
     ) -> Result<Vec<u8>, crate::TinkError>;
+
+    /// Encrypt a batch of `(plaintext, additional_data)` pairs, returning one result per pair in
+    /// the same order.  A failure for one item does not affect the others.
+    fn encrypt_batch(&self, items: &[(&[u8], &[u8])]) -> Vec<Result<Vec<u8>, crate::TinkError>> {
+        items
+            .iter()
+            .map(|(plaintext, additional_data)| self.encrypt(plaintext, additional_data))
+            .collect()
+    }
 }
 
 /// Trait bound to indicate that primitive trait objects should support cloning
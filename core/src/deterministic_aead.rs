@@ -20,7 +20,7 @@
 /// data.
 ///
 /// ## Warning
-////
+///
 /// Unlike AEAD, implementations of this trait are not semantically secure, because
 /// encrypting the same plaintext always yields the same ciphertext.
 ///
@@ -55,6 +55,43 @@ pub trait DeterministicAead: DeterministicAeadBoxClone {
         ciphertext: &[u8],
         additional_data: &[u8],
     ) -> Result<Vec<u8>, crate::TinkError>;
+
+    /// Deterministically encrypt plaintext with a vector of additional data components, each of
+    /// which is authenticated separately (as for the S2V construction in
+    /// [RFC 5297](https://tools.ietf.org/html/rfc5297)). Encrypting with a single component is
+    /// equivalent to [`encrypt_deterministically`](Self::encrypt_deterministically).
+    ///
+    /// The default implementation only supports a single component.
+    fn encrypt_deterministically_multi(
+        &self,
+        plaintext: &[u8],
+        additional_data: &[&[u8]],
+    ) -> Result<Vec<u8>, crate::TinkError> {
+        match additional_data {
+            [ad] => self.encrypt_deterministically(plaintext, ad),
+            _ => Err(multi_unsupported()),
+        }
+    }
+
+    /// Deterministically decrypt ciphertext with a vector of additional data components, as
+    /// produced by [`encrypt_deterministically_multi`](Self::encrypt_deterministically_multi).
+    ///
+    /// The default implementation only supports a single component.
+    fn decrypt_deterministically_multi(
+        &self,
+        ciphertext: &[u8],
+        additional_data: &[&[u8]],
+    ) -> Result<Vec<u8>, crate::TinkError> {
+        match additional_data {
+            [ad] => self.decrypt_deterministically(ciphertext, ad),
+            _ => Err(multi_unsupported()),
+        }
+    }
+}
+
+fn multi_unsupported() -> crate::TinkError {
+    crate::TinkError::new("multiple additional data components not supported")
+        .with_kind(crate::ErrorKind::UnsupportedType)
 }
 
 /// Trait bound to indicate that primitive trait objects should support cloning
@@ -16,19 +16,30 @@
 
 //! Binary I/O for keysets.
 
+use super::ReadLimits;
 use crate::{utils::wrap_err, TinkError};
-use std::io::{Read, Write};
-use tink_proto::prost;
+use std::io::{BufRead, Read, Write};
+use tink_proto::prost::{self, Message};
 
 /// `BinaryReader` deserializes a keyset from binary proto format.
 pub struct BinaryReader<T: Read> {
     r: T,
+    limits: ReadLimits,
 }
 
 impl<T: Read> BinaryReader<T> {
-    /// Return a new [`BinaryReader`] that will read from `r`.
+    /// Return a new [`BinaryReader`] that will read from `r`, with default [`ReadLimits`].
     pub fn new(r: T) -> Self {
-        BinaryReader { r }
+        BinaryReader {
+            r,
+            limits: ReadLimits::default(),
+        }
+    }
+
+    /// Return this reader with the given limits on the keysets it accepts.
+    pub fn with_limits(mut self, limits: ReadLimits) -> Self {
+        self.limits = limits;
+        self
     }
 }
 
@@ -36,29 +47,211 @@ impl<T: Read> super::Reader for BinaryReader<T> {
     /// Return a (cleartext) [`Keyset`](tink_proto::Keyset) object from the underlying
     /// [`std::io::Read`].
     fn read(&mut self) -> Result<tink_proto::Keyset, TinkError> {
-        read::<tink_proto::Keyset>(&mut self.r)
+        read::<tink_proto::Keyset>(&mut self.r, self.limits.max_serialized_size)
     }
 
     /// Return an [`EncryptedKeyset`](tink_proto::EncryptedKeyset) object from the underlying
     /// [`std::io::Read`].
     fn read_encrypted(&mut self) -> Result<tink_proto::EncryptedKeyset, TinkError> {
-        read::<tink_proto::EncryptedKeyset>(&mut self.r)
+        read::<tink_proto::EncryptedKeyset>(&mut self.r, self.limits.max_serialized_size)
+    }
+
+    fn limits(&self) -> ReadLimits {
+        self.limits
     }
 }
 
-fn read<T>(r: &mut dyn Read) -> Result<T, TinkError>
+/// Read and decode a message of at most `max_size` bytes from `r`.
+fn read<T>(r: &mut dyn Read, max_size: usize) -> Result<T, TinkError>
 where
     T: prost::Message + std::default::Default,
 {
     let mut data = vec![];
-    r.read_to_end(&mut data)
+    r.take(max_size as u64 + 1)
+        .read_to_end(&mut data)
         .map_err(|e| wrap_err("read failed", e))?;
+    if data.len() > max_size {
+        return Err(format!("serialized keyset exceeds maximum size of {max_size}").into());
+    }
     match T::decode(data.as_ref()) {
         Ok(msg) => Ok(msg),
         Err(e) => Err(wrap_err("decode failed", e)),
     }
 }
 
+/// `StreamingBinaryReader` deserializes a keyset from binary proto format, decoding it one key at a
+/// time rather than reading the whole serialized keyset into memory first.  This keeps the peak
+/// memory use close to the size of the decoded keyset for keysets with many keys.
+///
+/// An [`EncryptedKeyset`](tink_proto::EncryptedKeyset) is a single opaque blob, so
+/// [`read_encrypted`](super::Reader::read_encrypted) reads it in one go, as [`BinaryReader`] does.
+/// There is no streaming equivalent for the JSON format; use `JsonReader` for that.
+///
+/// The [`ReadLimits`] are enforced as the keyset is decoded, so an oversized key is rejected
+/// before it is read in.
+pub struct StreamingBinaryReader<T: BufRead> {
+    r: T,
+    limits: ReadLimits,
+}
+
+impl<T: BufRead> StreamingBinaryReader<T> {
+    /// Return a new [`StreamingBinaryReader`] that will read from `r`, with default
+    /// [`ReadLimits`].
+    pub fn new(r: T) -> Self {
+        StreamingBinaryReader {
+            r,
+            limits: ReadLimits::default(),
+        }
+    }
+
+    /// Return this reader with the given limits on the keysets it accepts.
+    pub fn with_limits(mut self, limits: ReadLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+}
+
+impl<T: BufRead> super::Reader for StreamingBinaryReader<T> {
+    /// Return a (cleartext) [`Keyset`](tink_proto::Keyset) object from the underlying
+    /// [`std::io::BufRead`].
+    fn read(&mut self) -> Result<tink_proto::Keyset, TinkError> {
+        let max_size = self.limits.max_serialized_size;
+        let mut r = (&mut self.r).take(max_size as u64 + 1);
+        let result = read_keyset_incrementally(&mut r, &self.limits);
+        if r.limit() == 0 {
+            return Err(format!("serialized keyset exceeds maximum size of {max_size}").into());
+        }
+        result
+    }
+
+    /// Return an [`EncryptedKeyset`](tink_proto::EncryptedKeyset) object from the underlying
+    /// [`std::io::BufRead`].
+    fn read_encrypted(&mut self) -> Result<tink_proto::EncryptedKeyset, TinkError> {
+        read::<tink_proto::EncryptedKeyset>(&mut self.r, self.limits.max_serialized_size)
+    }
+
+    fn limits(&self) -> ReadLimits {
+        self.limits
+    }
+}
+
+/// Protobuf wire types, as used in the low 3 bits of a field tag.
+const WIRE_TYPE_VARINT: u64 = 0;
+const WIRE_TYPE_FIXED64: u64 = 1;
+const WIRE_TYPE_LENGTH_DELIMITED: u64 = 2;
+const WIRE_TYPE_FIXED32: u64 = 5;
+
+/// Field numbers in the `Keyset` message.
+const KEYSET_PRIMARY_KEY_ID_FIELD: u64 = 1;
+const KEYSET_KEY_FIELD: u64 = 2;
+
+/// Allowance for the parts of an encoded `Keyset.Key` other than its key data `value` (type URL,
+/// status, key ID etc.), used to reject oversized keys before reading them.
+const KEY_ENCODING_OVERHEAD: u64 = 1024;
+
+/// Decode a [`Keyset`](tink_proto::Keyset) from `r` field by field, holding at most one encoded
+/// key in memory at a time, and failing as soon as `limits` are exceeded.
+fn read_keyset_incrementally(
+    r: &mut dyn BufRead,
+    limits: &ReadLimits,
+) -> Result<tink_proto::Keyset, TinkError> {
+    let max_key_len = limits.max_key_data_size as u64 + KEY_ENCODING_OVERHEAD;
+    let mut keyset = tink_proto::Keyset::default();
+    let mut buf = Vec::new();
+    while let Some(tag) = read_varint(r)? {
+        match (tag >> 3, tag & 0x07) {
+            (KEYSET_PRIMARY_KEY_ID_FIELD, WIRE_TYPE_VARINT) => {
+                keyset.primary_key_id = expect_varint(r)? as u32;
+            }
+            (KEYSET_KEY_FIELD, WIRE_TYPE_LENGTH_DELIMITED) => {
+                if keyset.key.len() >= limits.max_keys {
+                    return Err(format!(
+                        "keyset has more than {} keys, exceeding maximum of {}",
+                        keyset.key.len(),
+                        limits.max_keys
+                    )
+                    .into());
+                }
+                let len = expect_varint(r)?;
+                if len > max_key_len {
+                    return Err(format!(
+                        "key of encoded size {len} has key data exceeding maximum of {}",
+                        limits.max_key_data_size
+                    )
+                    .into());
+                }
+                read_len(r, len, &mut buf)?;
+                let key = tink_proto::keyset::Key::decode(buf.as_ref())
+                    .map_err(|e| wrap_err("decode failed", e))?;
+                keyset.key.push(key);
+            }
+            (KEYSET_PRIMARY_KEY_ID_FIELD, _) | (KEYSET_KEY_FIELD, _) | (0, _) => {
+                return Err("decode failed: invalid field tag".into());
+            }
+            // Skip unknown fields, as the protobuf decoder would.
+            (_, WIRE_TYPE_VARINT) => {
+                expect_varint(r)?;
+            }
+            (_, WIRE_TYPE_FIXED64) => read_len(r, 8, &mut buf)?,
+            (_, WIRE_TYPE_LENGTH_DELIMITED) => {
+                let len = expect_varint(r)?;
+                skip_len(r, len)?;
+            }
+            (_, WIRE_TYPE_FIXED32) => read_len(r, 4, &mut buf)?,
+            (_, _) => return Err("decode failed: unsupported wire type".into()),
+        }
+    }
+    limits.validate(&keyset)?;
+    Ok(keyset)
+}
+
+/// Read a base-128 varint from `r`, returning `None` if `r` is already at end of input.
+fn read_varint(r: &mut dyn BufRead) -> Result<Option<u64>, TinkError> {
+    let mut value = 0u64;
+    for i in 0..10 {
+        let byte = match r.fill_buf().map_err(|e| wrap_err("read failed", e))? {
+            [] if i == 0 => return Ok(None),
+            [] => return Err("decode failed: truncated varint".into()),
+            [b, ..] => *b,
+        };
+        r.consume(1);
+        value |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Ok(Some(value));
+        }
+    }
+    Err("decode failed: invalid varint".into())
+}
+
+/// Read a base-128 varint from `r`, failing at end of input.
+fn expect_varint(r: &mut dyn BufRead) -> Result<u64, TinkError> {
+    read_varint(r)?.ok_or_else(|| "decode failed: truncated input".into())
+}
+
+/// Read exactly `len` bytes from `r` into `buf` (replacing its contents).  The buffer only grows
+/// as data actually arrives, so a corrupt length cannot trigger a huge allocation.
+fn read_len(r: &mut dyn BufRead, len: u64, buf: &mut Vec<u8>) -> Result<(), TinkError> {
+    buf.clear();
+    let n = r
+        .take(len)
+        .read_to_end(buf)
+        .map_err(|e| wrap_err("read failed", e))?;
+    if n as u64 != len {
+        return Err("decode failed: truncated input".into());
+    }
+    Ok(())
+}
+
+/// Skip over exactly `len` bytes of `r`, without buffering them.
+fn skip_len(r: &mut dyn BufRead, len: u64) -> Result<(), TinkError> {
+    let n = std::io::copy(&mut r.take(len), &mut std::io::sink())
+        .map_err(|e| wrap_err("read failed", e))?;
+    if n != len {
+        return Err("decode failed: truncated input".into());
+    }
+    Ok(())
+}
+
 /// `BinaryWriter` serializes a keyset into binary proto format.
 pub struct BinaryWriter<T: Write> {
     w: T,
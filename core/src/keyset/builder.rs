@@ -0,0 +1,97 @@
+// Copyright 2020 The Tink-Rust Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! Builder for assembling keysets.
+
+use crate::{monitoring, utils::wrap_err, KeyId, TinkError};
+
+/// `HandleBuilder` assembles a keyset from a mixture of freshly generated and existing keys,
+/// and produces a validated [`Handle`](super::Handle).
+/// Keys are added via an internal [`Manager`](super::Manager).  If more than one key is added
+/// with `is_primary` set, the most recently added one becomes the primary key, and a warning is
+/// reported to the registered [monitoring client](crate::registry::monitoring_client).
+#[derive(Default)]
+pub struct HandleBuilder {
+    ksm: super::Manager,
+}
+
+impl HandleBuilder {
+    /// Create a new builder for an empty keyset.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Generate a fresh key according to the given key template and add it to the keyset,
+    /// optionally as the primary key. Returns the key ID of the new key.
+    pub fn add_generated_entry(
+        &mut self,
+        kt: &tink_proto::KeyTemplate,
+        is_primary: bool,
+    ) -> Result<KeyId, TinkError> {
+        let previous_primary = self.ksm.keyset().primary_key_id;
+        let key_id = self
+            .ksm
+            .add(kt, is_primary)
+            .map_err(|e| wrap_err("keyset::HandleBuilder: cannot generate key", e))?;
+        if is_primary {
+            self.warn_if_primary_replaced(previous_primary, key_id);
+        }
+        Ok(key_id)
+    }
+
+    /// Add an existing key to the keyset, optionally as the primary key. Returns the key ID of
+    /// the key.
+    pub fn add_entry(
+        &mut self,
+        key: tink_proto::keyset::Key,
+        is_primary: bool,
+    ) -> Result<KeyId, TinkError> {
+        let previous_primary = self.ksm.keyset().primary_key_id;
+        let key_id = self
+            .ksm
+            .add_key(key, is_primary)
+            .map_err(|e| wrap_err("keyset::HandleBuilder: cannot add key", e))?;
+        if is_primary {
+            self.warn_if_primary_replaced(previous_primary, key_id);
+        }
+        Ok(key_id)
+    }
+
+    /// Report a warning to the monitoring client if `key_id` has replaced an earlier primary key.
+    fn warn_if_primary_replaced(&self, previous_primary: KeyId, key_id: KeyId) {
+        if previous_primary == 0 || previous_primary == key_id {
+            return;
+        }
+        let context = monitoring::Context {
+            primitive: "keyset",
+            api_function: "build",
+            keyset_info: super::handle::get_keyset_info(self.ksm.keyset()),
+        };
+        // Failure to obtain a logger only loses the warning.
+        if let Ok(logger) = crate::registry::monitoring_client().new_logger(&context) {
+            logger.log_warning(&format!(
+                "keyset::HandleBuilder: primary key {previous_primary} replaced by {key_id}"
+            ));
+        }
+    }
+
+    /// Validate the assembled keyset and return a [`Handle`](super::Handle) for it.
+    pub fn build(&self) -> Result<super::Handle, TinkError> {
+        super::validate(self.ksm.keyset())
+            .map_err(|e| wrap_err("keyset::HandleBuilder: invalid keyset", e))?;
+        self.ksm.handle()
+    }
+}
@@ -0,0 +1,84 @@
+// Copyright 2020 The Tink-Rust Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! Comparison of keyset metadata, for audit logging.
+
+use crate::KeyId;
+use std::collections::HashMap;
+use tink_proto::{KeyStatusType, KeysetInfo};
+
+/// Differences between two [`KeysetInfo`] snapshots of a keyset, as produced by [`diff`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct KeysetDiff {
+    /// IDs of keys present only in the new keyset, in keyset order.
+    pub added: Vec<KeyId>,
+    /// IDs of keys present only in the old keyset, in keyset order.
+    pub removed: Vec<KeyId>,
+    /// Keys present in both keysets whose status changed, as `(key_id, old, new)`.
+    pub status_changes: Vec<(KeyId, KeyStatusType, KeyStatusType)>,
+    /// The old and new primary key IDs, if the primary key changed.
+    pub primary_change: Option<(KeyId, KeyId)>,
+}
+
+impl KeysetDiff {
+    /// Indicate whether the two snapshots have identical key IDs, statuses and primary key.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.status_changes.is_empty()
+            && self.primary_change.is_none()
+    }
+}
+
+/// Compare two [`KeysetInfo`] snapshots (as returned by
+/// [`Handle::keyset_info`](crate::keyset::Handle::keyset_info)), which hold no secret key
+/// material.
+pub fn diff(old_info: &KeysetInfo, new_info: &KeysetInfo) -> KeysetDiff {
+    let status = |s: i32| KeyStatusType::from_i32(s).unwrap_or(KeyStatusType::UnknownStatus);
+    let old_status: HashMap<KeyId, i32> = old_info
+        .key_info
+        .iter()
+        .map(|k| (k.key_id, k.status))
+        .collect();
+    let new_status: HashMap<KeyId, i32> = new_info
+        .key_info
+        .iter()
+        .map(|k| (k.key_id, k.status))
+        .collect();
+
+    let mut result = KeysetDiff::default();
+    for key in &new_info.key_info {
+        match old_status.get(&key.key_id) {
+            None => result.added.push(key.key_id),
+            Some(old) if *old != key.status => {
+                result
+                    .status_changes
+                    .push((key.key_id, status(*old), status(key.status)))
+            }
+            Some(_) => {}
+        }
+    }
+    result.removed = old_info
+        .key_info
+        .iter()
+        .map(|k| k.key_id)
+        .filter(|id| !new_status.contains_key(id))
+        .collect();
+    if old_info.primary_key_id != new_info.primary_key_id {
+        result.primary_change = Some((old_info.primary_key_id, new_info.primary_key_id));
+    }
+    result
+}
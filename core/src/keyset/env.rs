@@ -0,0 +1,46 @@
+// Copyright 2020 The Tink-Rust Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! Loading of keysets from environment variables.
+
+use super::{BinaryReader, Handle};
+use crate::{utils::wrap_err, TinkError};
+use base64::Engine;
+
+/// Create a [`Handle`] from the environment variable `var_name`, which should hold a
+/// standard base64 encoding of a binary [`EncryptedKeyset`](tink_proto::EncryptedKeyset).
+/// The keyset is decrypted with `master_key`.
+pub fn read_from_env(
+    var_name: &str,
+    master_key: Box<dyn crate::Aead>,
+) -> Result<Handle, TinkError> {
+    let encoded = std::env::var(var_name).map_err(|e| {
+        wrap_err(
+            &format!("keyset::read_from_env: cannot read environment variable {var_name}"),
+            e,
+        )
+    })?;
+    let data = base64::engine::general_purpose::STANDARD
+        .decode(encoded.trim())
+        .map_err(|e| {
+            wrap_err(
+                &format!("keyset::read_from_env: {var_name} is not valid base64"),
+                e,
+            )
+        })?;
+    Handle::read(&mut BinaryReader::new(&data[..]), master_key)
+        .map_err(|e| wrap_err("keyset::read_from_env: cannot read keyset", e))
+}
@@ -72,6 +72,7 @@ impl Handle {
     {
         let encrypted_keyset = reader.read_encrypted()?;
         let ks = decrypt(&encrypted_keyset, master_key, associated_data)?;
+        reader.limits().validate(&ks)?;
         Ok(Handle {
             ks: validate_keyset(ks)?,
         })
@@ -84,6 +85,7 @@ impl Handle {
         T: crate::keyset::Reader,
     {
         let ks = reader.read()?;
+        reader.limits().validate(&ks)?;
         Handle::new_with_no_secrets(ks)
     }
 
@@ -113,6 +115,10 @@ impl Handle {
     }
 
     /// Encrypts and writes the enclosed [`Keyset`].
+    ///
+    /// The `master_key` can be any [`Aead`](crate::Aead) trait object, such as one obtained
+    /// from [`KmsClient::get_aead`](crate::registry::KmsClient::get_aead); a shared
+    /// `Arc<dyn Aead>` can be passed via its `box_clone()` method.
     pub fn write<T>(
         &self,
         writer: &mut T,
@@ -151,6 +157,19 @@ impl Handle {
         }
     }
 
+    /// Write the keyset in JSON format to `w`, for publishing public (verification or
+    /// encryption) keys.  As for [`write_with_no_secrets`](Handle::write_with_no_secrets), this
+    /// fails if the keyset contains secret key material; use [`public`](Handle::public) first to
+    /// extract the public keys from a private keyset.
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub fn write_no_secret_json<W>(&self, w: W) -> Result<(), TinkError>
+    where
+        W: std::io::Write,
+    {
+        self.write_with_no_secrets(&mut super::JsonWriter::new(w))
+    }
+
     /// Create a set of primitives corresponding to the keys with status=ENABLED in the keyset of
     /// the given keyset [`Handle`], assuming all the corresponding key managers are present (keys
     /// with status!=ENABLED are skipped).
@@ -177,42 +196,43 @@ impl Handle {
         &self,
         km: Option<Arc<dyn crate::registry::KeyManager>>,
     ) -> Result<crate::primitiveset::PrimitiveSet, TinkError> {
-        super::validate(&self.ks)
+        self.validate()
             .map_err(|e| wrap_err("primitives_with_key_manager: invalid keyset", e))?;
-        let mut primitive_set = crate::primitiveset::PrimitiveSet::new();
-        for key in &self.ks.key {
-            if key.status != tink_proto::KeyStatusType::Enabled as i32 {
-                continue;
-            }
-            let key_data = key
-                .key_data
-                .as_ref()
-                .ok_or_else(|| TinkError::new("primitives_with_key_manager: no key_data"))?;
-            let primitive = match &km {
-                Some(km) if km.does_support(&key_data.type_url) => km.primitive(&key_data.value),
-                Some(_) | None => crate::registry::primitive_from_key_data(key_data),
-            }
-            .map_err(|e| {
-                wrap_err(
-                    "primitives_with_key_manager: cannot get primitive from key",
-                    e,
-                )
-            })?;
-
-            let entry = primitive_set
-                .add(primitive, key)
-                .map_err(|e| wrap_err("primitives_with_key_manager: cannot add primitive", e))?;
-            if key.key_id == self.ks.primary_key_id {
-                primitive_set.primary = Some(entry.clone());
-            }
-        }
-        Ok(primitive_set)
+        primitives_from_keyset(&self.ks, km.as_ref())
+    }
+
+    /// Return a [`PrimitiveSource`] that can build the same primitive sets as
+    /// [`primitives_with_key_manager`](Handle::primitives_with_key_manager), and which can be
+    /// shared across threads (unlike the primitives themselves).
+    pub fn primitive_source(
+        &self,
+        km: Option<Arc<dyn crate::registry::KeyManager>>,
+    ) -> Result<PrimitiveSource, TinkError> {
+        self.validate()
+            .map_err(|e| wrap_err("primitive_source: invalid keyset", e))?;
+        Ok(PrimitiveSource {
+            ks: self.ks.clone(),
+            km,
+        })
+    }
+
+    /// Create a single primitive of type `P` from the keyset, for example
+    /// `handle.primitive::<dyn Aead>()`, by combining the primitives for its enabled keys with
+    /// the [`PrimitiveWrapper`](crate::registry::PrimitiveWrapper) registered for `P`.
+    pub fn primitive<P>(&self) -> Result<Box<P>, TinkError>
+    where
+        P: crate::PrimitiveType + ?Sized,
+    {
+        let wrapper = crate::registry::get_primitive_wrapper(P::NAME)
+            .map_err(|e| wrap_err("keyset::Handle: cannot wrap primitives", e))?;
+        let p = wrapper.wrap(self.primitives()?, &self.keyset_info())?;
+        P::from_primitive(p)
     }
 
     /// Check if the keyset handle contains any key material considered secret.  Both symmetric keys
     /// and the private key of an asymmetric crypto system are considered secret keys. Also
     /// returns true when encountering any errors.
-    fn has_secrets(&self) -> Result<bool, TinkError> {
+    pub(crate) fn has_secrets(&self) -> Result<bool, TinkError> {
         let mut result = false;
         for k in &self.ks.key {
             match &k.key_data {
@@ -236,6 +256,33 @@ impl Handle {
         get_keyset_info(&self.ks)
     }
 
+    /// Return the number of keys in the managed keyset, whatever their status.
+    pub fn key_count(&self) -> usize {
+        self.ks.key.len()
+    }
+
+    /// Return the number of enabled keys in the managed keyset, excluding disabled and destroyed
+    /// keys.
+    pub fn enabled_key_count(&self) -> usize {
+        self.ks
+            .key
+            .iter()
+            .filter(|k| k.status == tink_proto::KeyStatusType::Enabled as i32)
+            .count()
+    }
+
+    /// Check that the managed keyset is well-formed, as per [`validate`](super::validate); for
+    /// example, that it has an enabled primary key.
+    pub fn validate(&self) -> Result<(), TinkError> {
+        super::validate(&self.ks)
+    }
+
+    /// Return a reference to the enclosed [`Keyset`]; for internal use only.
+    #[cfg(feature = "serde")]
+    pub(crate) fn keyset(&self) -> &Keyset {
+        &self.ks
+    }
+
     /// Consume the `Handle` and return the enclosed [`Keyset`].
     pub(crate) fn into_inner(self) -> Keyset {
         self.ks
@@ -258,6 +305,92 @@ impl Handle {
     }
 }
 
+/// `PrimitiveSource` holds the keys of a [`Handle`] (and an optional custom key manager), for
+/// building a separate primitive set in each of several threads, as primitives are neither
+/// `Send` nor `Sync`.  It does not give access to the key material itself.
+pub struct PrimitiveSource {
+    ks: Keyset,
+    km: Option<Arc<dyn crate::registry::KeyManager>>,
+}
+
+impl PrimitiveSource {
+    /// Create a set of primitives corresponding to the keys with status=ENABLED, as for
+    /// [`Handle::primitives_with_key_manager`].
+    pub fn primitives(&self) -> Result<crate::primitiveset::PrimitiveSet, TinkError> {
+        primitives_from_keyset(&self.ks, self.km.as_ref())
+    }
+}
+
+impl std::fmt::Debug for PrimitiveSource {
+    /// Return a string representation of the keyset.
+    /// The result does not contain any sensitive key material.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", get_keyset_info(&self.ks))
+    }
+}
+
+/// Create a set of primitives corresponding to the keys with status=ENABLED in a (validated)
+/// keyset, using the given key manager for keys supported by it.
+fn primitives_from_keyset(
+    ks: &Keyset,
+    km: Option<&Arc<dyn crate::registry::KeyManager>>,
+) -> Result<crate::primitiveset::PrimitiveSet, TinkError> {
+    let mut primitive_set = crate::primitiveset::PrimitiveSet::new();
+    for key in &ks.key {
+        if key.status != tink_proto::KeyStatusType::Enabled as i32 {
+            continue;
+        }
+        let key_data = key
+            .key_data
+            .as_ref()
+            .ok_or_else(|| TinkError::new("primitives_with_key_manager: no key_data"))?;
+        let primitive = match km {
+            Some(km) if km.does_support(&key_data.type_url) => km.primitive(&key_data.value),
+            Some(_) | None => crate::registry::primitive_from_key_data(key_data),
+        }
+        .map_err(|e| {
+            wrap_err(
+                "primitives_with_key_manager: cannot get primitive from key",
+                e,
+            )
+        })?;
+
+        let entry = primitive_set
+            .add(primitive, key)
+            .map_err(|e| wrap_err("primitives_with_key_manager: cannot add primitive", e))?;
+        if key.key_id == ks.primary_key_id {
+            primitive_set.primary = Some(entry.clone());
+        }
+    }
+    Ok(primitive_set)
+}
+
+/// Create a keyset [`Handle`] holding just the given key, which is enabled and set as the primary
+/// key under a random key ID.  This is a convenient way of using a single key that has been
+/// imported from elsewhere.
+pub fn handle_from_single_key(
+    key_data: tink_proto::KeyData,
+    output_prefix_type: tink_proto::OutputPrefixType,
+) -> Result<Handle, TinkError> {
+    let key_id = loop {
+        let id = crate::subtle::random::get_random_uint32();
+        if id != 0 {
+            break id;
+        }
+    };
+    let ks = Keyset {
+        primary_key_id: key_id,
+        key: vec![tink_proto::keyset::Key {
+            key_data: Some(key_data),
+            status: tink_proto::KeyStatusType::Enabled as i32,
+            key_id,
+            output_prefix_type: output_prefix_type as i32,
+        }],
+    };
+    super::validate(&ks).map_err(|e| wrap_err("keyset::handle_from_single_key", e))?;
+    Handle::from_keyset(ks)
+}
+
 /// Check that a [`Keyset`] is valid.
 fn validate_keyset(ks: Keyset) -> Result<Keyset, TinkError> {
     for k in &ks.key {
@@ -301,7 +434,33 @@ fn decrypt(
     let decrypted = master_key
         .decrypt(&encrypted_keyset.encrypted_keyset, associated_data)
         .map_err(|e| wrap_err("keyset::Handle: decryption failed", e))?;
-    Keyset::decode(&decrypted[..]).map_err(|_| TinkError::new("keyset::Handle:: invalid keyset"))
+    let ks = Keyset::decode(&decrypted[..])
+        .map_err(|_| TinkError::new("keyset::Handle:: invalid keyset"))?;
+    if let Some(info) = &encrypted_keyset.keyset_info {
+        check_keyset_info(&ks, info)?;
+    }
+    Ok(ks)
+}
+
+/// Check that the cleartext [`KeysetInfo`] attached to an encrypted keyset is consistent with
+/// the decrypted [`Keyset`], to catch swapped or corrupted keyset blobs.
+fn check_keyset_info(keyset: &Keyset, info: &KeysetInfo) -> Result<(), TinkError> {
+    if info.primary_key_id != keyset.primary_key_id {
+        return Err(format!(
+            "keyset::Handle: keyset info primary key id {} does not match keyset primary key id {}",
+            info.primary_key_id, keyset.primary_key_id
+        )
+        .into());
+    }
+    if !info
+        .key_info
+        .iter()
+        .map(|ki| ki.key_id)
+        .eq(keyset.key.iter().map(|k| k.key_id))
+    {
+        return Err("keyset::Handle: keyset info key ids do not match keyset".into());
+    }
+    Ok(())
 }
 
 /// Encrypt a keyset with a master key.
@@ -324,7 +483,7 @@ fn encrypt(
 }
 
 /// Return a [`KeysetInfo`] from a [`Keyset`] protobuf.
-fn get_keyset_info(keyset: &Keyset) -> KeysetInfo {
+pub(crate) fn get_keyset_info(keyset: &Keyset) -> KeysetInfo {
     let n_key = keyset.key.len();
     let mut key_infos = Vec::with_capacity(n_key);
     for key in &keyset.key {
@@ -44,6 +44,7 @@ where
     T: super::Reader,
 {
     let ks = r.read()?;
+    r.limits().validate(&ks)?;
     if ks.key.is_empty() {
         Err("insecure: invalid keyset".into())
     } else {
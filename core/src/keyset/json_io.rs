@@ -16,6 +16,7 @@
 
 //! JSON I/O for keysets (requires activation of the `json` feature).
 
+use super::ReadLimits;
 use crate::{utils::wrap_err, TinkError};
 use serde::Deserialize;
 use std::io::{Read, Write};
@@ -24,13 +25,24 @@ use std::io::{Read, Write};
 #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
 pub struct JsonReader<T: Read> {
     r: T,
+    limits: ReadLimits,
 }
 
 impl<T: Read> JsonReader<T> {
-    /// Return a new [`JsonReader`] that will read from `r`.
+    /// Return a new [`JsonReader`] that will read from `r`, with default [`ReadLimits`].
     #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
     pub fn new(r: T) -> Self {
-        JsonReader { r }
+        JsonReader {
+            r,
+            limits: ReadLimits::default(),
+        }
+    }
+
+    /// Return this reader with the given limits on the keysets it accepts.
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub fn with_limits(mut self, limits: ReadLimits) -> Self {
+        self.limits = limits;
+        self
     }
 }
 
@@ -38,17 +50,34 @@ impl<T: Read> super::Reader for JsonReader<T> {
     /// Return a (cleartext) [`Keyset`](tink_proto::Keyset) object from the underlying
     /// [`std::io::Read`].
     fn read(&mut self) -> Result<tink_proto::Keyset, TinkError> {
-        let mut de = serde_json::Deserializer::from_reader(&mut self.r);
-        tink_proto::Keyset::deserialize(&mut de).map_err(|e| wrap_err("failed to parse", e))
+        read(&mut self.r, self.limits.max_serialized_size)
     }
 
     /// Return an [`EncryptedKeyset`](tink_proto::EncryptedKeyset) object from the underlying
     /// [`std::io::Read`].
     fn read_encrypted(&mut self) -> Result<tink_proto::EncryptedKeyset, TinkError> {
-        let mut de = serde_json::Deserializer::from_reader(&mut self.r);
-        tink_proto::EncryptedKeyset::deserialize(&mut de)
-            .map_err(|e| wrap_err("failed to parse", e))
+        read(&mut self.r, self.limits.max_serialized_size)
+    }
+
+    fn limits(&self) -> ReadLimits {
+        self.limits
+    }
+}
+
+/// Deserialize a message from at most `max_size` bytes of JSON from `r`.
+fn read<T>(r: &mut dyn Read, max_size: usize) -> Result<T, TinkError>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    let mut r = r.take(max_size as u64 + 1);
+    let result = {
+        let mut de = serde_json::Deserializer::from_reader(&mut r);
+        T::deserialize(&mut de).map_err(|e| wrap_err("failed to parse", e))
+    };
+    if r.limit() == 0 {
+        return Err(format!("serialized keyset exceeds maximum size of {max_size}").into());
     }
+    result
 }
 
 /// `JsonWriter` serializes a keyset into JSON format.
@@ -18,8 +18,12 @@
 
 use crate::{utils::wrap_err, KeyId, TinkError};
 use rand::Rng;
+use std::collections::HashSet;
 use tink_proto::{KeyStatusType, OutputPrefixType};
 
+/// Maximum number of random candidates tried when generating a fresh key ID.
+const MAX_KEY_ID_ATTEMPTS: usize = 1000;
+
 /// Manager manages a [`Keyset`](tink_proto::Keyset)-proto, with convenience methods that rotate,
 /// disable, enable or destroy keys. Note: It is not thread-safe.
 #[derive(Default)]
@@ -58,7 +62,7 @@ impl Manager {
     ) -> Result<KeyId, TinkError> {
         let key_data = crate::registry::new_key_data(kt)
             .map_err(|e| wrap_err("keyset::Manager: cannot create KeyData", e))?;
-        let key_id = self.new_key_id();
+        let key_id = self.new_key_id(&HashSet::new())?;
         let output_prefix_type = match OutputPrefixType::from_i32(kt.output_prefix_type) {
             None | Some(OutputPrefixType::UnknownPrefix) => {
                 return Err("keyset::Manager: unknown output prefix type".into())
@@ -72,6 +76,7 @@ impl Manager {
             output_prefix_type: output_prefix_type as i32,
         };
         self.ks.key.push(key);
+        self.check_unique_key_ids(self.ks.key.len() - 1)?;
         if as_primary {
             // Set the new key as the primary key
             self.ks.primary_key_id = key_id;
@@ -79,6 +84,60 @@ impl Manager {
         Ok(key_id)
     }
 
+    /// Add an existing key to the keyset, and optionally set it as the primary key. The key must
+    /// be valid and must not reuse the key ID of a key already in the keyset. Returns the key ID
+    /// of the added key.
+    pub fn add_key(
+        &mut self,
+        key: tink_proto::keyset::Key,
+        as_primary: bool,
+    ) -> Result<KeyId, TinkError> {
+        super::validate_key(&key).map_err(|e| wrap_err("keyset::Manager: invalid key", e))?;
+        let key_id = key.key_id;
+        if self.ks.key.iter().any(|k| k.key_id == key_id) {
+            return Err(format!("keyset::Manager: duplicate key id {key_id}").into());
+        }
+        self.ks.key.push(key);
+        if as_primary {
+            self.set_primary(key_id)?;
+        }
+        Ok(key_id)
+    }
+
+    /// Import all keys from the keyset in `other` into the managed keyset, leaving the primary key
+    /// unchanged.  Imported keys whose key ID collides with a key already in the keyset are given
+    /// a fresh key ID; note that this changes the output prefix for such keys, so existing
+    /// ciphertexts/tags with a non-raw prefix will no longer be matched to the key.  Fails without
+    /// modifying the keyset if any imported key has a type with no registered key manager.
+    /// Returns the key IDs of the imported keys, in order.
+    pub fn merge(&mut self, other: super::Handle) -> Result<Vec<KeyId>, TinkError> {
+        let incoming = other.into_inner();
+        for key in &incoming.key {
+            if let Some(key_data) = &key.key_data {
+                crate::registry::get_key_manager(&key_data.type_url)
+                    .map_err(|e| wrap_err("keyset::Manager: cannot merge key", e))?;
+            }
+        }
+        let incoming_ids: HashSet<KeyId> = incoming.key.iter().map(|k| k.key_id).collect();
+        let original_len = self.ks.key.len();
+        let mut key_ids = Vec::with_capacity(incoming.key.len());
+        for mut key in incoming.key {
+            if self.ks.key.iter().any(|k| k.key_id == key.key_id) {
+                key.key_id = match self.new_key_id(&incoming_ids) {
+                    Ok(id) => id,
+                    Err(e) => {
+                        self.ks.key.truncate(original_len);
+                        return Err(wrap_err("keyset::Manager: cannot merge key", e));
+                    }
+                };
+            }
+            key_ids.push(key.key_id);
+            self.ks.key.push(key);
+        }
+        self.check_unique_key_ids(original_len)?;
+        Ok(key_ids)
+    }
+
     /// Create a new [`Handle`](super::Handle) for the managed keyset.
     pub fn handle(&self) -> Result<super::Handle, TinkError> {
         super::Handle::from_keyset(self.ks.clone())
@@ -207,16 +266,40 @@ impl Manager {
         self.ks.key.len()
     }
 
-    /// Generate a key id that has not been used by any key in the [`Keyset`](tink_proto::Keyset).
-    fn new_key_id(&self) -> KeyId {
+    /// Return the managed [`Keyset`](tink_proto::Keyset); for internal use only.
+    pub(crate) fn keyset(&self) -> &tink_proto::Keyset {
+        &self.ks
+    }
+
+    /// Generate a key id that has not been used by any key in the [`Keyset`](tink_proto::Keyset),
+    /// and is not in `reserved`.  Fails if no such key ID is found within a bounded number of
+    /// attempts, which indicates that the key ID space is (nearly) exhausted.
+    fn new_key_id(&self, reserved: &HashSet<KeyId>) -> Result<KeyId, TinkError> {
         let mut rng = rand::thread_rng();
 
-        loop {
+        for _ in 0..MAX_KEY_ID_ATTEMPTS {
             let ret = rng.gen::<u32>();
-            if self.ks.key.iter().any(|x| x.key_id == ret) {
+            if reserved.contains(&ret) || self.ks.key.iter().any(|x| x.key_id == ret) {
                 continue;
             }
-            return ret;
+            return Ok(ret);
+        }
+        Err("keyset::Manager: cannot generate a fresh key id".into())
+    }
+
+    /// Check that the keys from index `first_new` onwards in the managed keyset have key IDs that
+    /// are distinct from each other and from all earlier keys.  On failure, the keys from
+    /// `first_new` onwards are removed again.
+    fn check_unique_key_ids(&mut self, first_new: usize) -> Result<(), TinkError> {
+        let mut seen: HashSet<KeyId> = self.ks.key[..first_new].iter().map(|k| k.key_id).collect();
+        if let Some(dup) = self.ks.key[first_new..]
+            .iter()
+            .find(|k| !seen.insert(k.key_id))
+        {
+            let key_id = dup.key_id;
+            self.ks.key.truncate(first_new);
+            return Err(format!("keyset::Manager: duplicate key id {key_id}").into());
         }
+        Ok(())
     }
 }
@@ -18,6 +18,12 @@
 
 mod binary_io;
 pub use binary_io::*;
+mod builder;
+pub use builder::*;
+mod diff;
+pub use diff::*;
+mod env;
+pub use env::*;
 mod handle;
 pub use handle::*;
 #[cfg(feature = "json")]
@@ -31,6 +37,11 @@ mod mem_io;
 pub use mem_io::*;
 mod reader;
 pub use reader::*;
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+mod serializable;
+#[cfg(feature = "serde")]
+pub use serializable::*;
 mod validation;
 pub use validation::*;
 mod writer;
@@ -26,4 +26,39 @@ pub trait Reader {
 
     /// Return an `EncryptedKeyset` object from the underlying source.
     fn read_encrypted(&mut self) -> Result<tink_proto::EncryptedKeyset, crate::TinkError>;
+
+    /// Return the limits that keysets obtained from this source must respect.
+    fn limits(&self) -> ReadLimits {
+        ReadLimits::default()
+    }
+}
+
+/// `ReadLimits` bounds the keysets that are accepted from a [`Reader`], to guard against
+/// resource exhaustion from crafted keysets.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReadLimits {
+    /// Maximum length of the serialized keyset (or encrypted keyset) read from the source.
+    pub max_serialized_size: usize,
+    /// Maximum length of a [`KeyData`](tink_proto::KeyData) `value`.
+    pub max_key_data_size: usize,
+    /// Maximum number of keys in the keyset.
+    pub max_keys: usize,
+}
+
+impl Default for ReadLimits {
+    fn default() -> Self {
+        ReadLimits {
+            max_serialized_size: super::DEFAULT_MAX_SERIALIZED_KEYSET_SIZE,
+            max_key_data_size: super::DEFAULT_MAX_KEY_DATA_SIZE,
+            max_keys: super::DEFAULT_MAX_KEYSET_KEYS,
+        }
+    }
+}
+
+impl ReadLimits {
+    /// Check that `keyset` respects these limits.
+    pub fn validate(&self, keyset: &tink_proto::Keyset) -> Result<(), crate::TinkError> {
+        super::validate_key_count(keyset, self.max_keys)?;
+        super::validate_key_data_size(keyset, self.max_key_data_size)
+    }
 }
@@ -0,0 +1,151 @@
+// Copyright 2020 The Tink-Rust Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! Serde-serializable keyset representation (requires activation of the `serde` feature).
+
+use crate::{utils::wrap_err, TinkError};
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use tink_proto::Keyset;
+
+/// `SerializableKeyset` is a cleartext keyset in a form that can be embedded in any
+/// serde-based format (for example, as part of a configuration file).  It mirrors the Tink
+/// JSON keyset schema, with enum values serialized by name and key data base64-encoded.
+///
+/// Conversion from a [`Handle`](super::Handle) fails if the keyset holds secret key material,
+/// and conversion back to a [`Handle`](super::Handle) fails for secret key material unless
+/// the `insecure` flag (only available with the `insecure` feature) has been set.
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SerializableKeyset {
+    pub primary_key_id: u32,
+    pub key: Vec<SerializableKey>,
+    /// Allow conversion to a [`Handle`](super::Handle) even if the keyset contains secret key
+    /// material.  Never serialized.
+    #[cfg(feature = "insecure")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "insecure")))]
+    #[serde(skip)]
+    pub insecure: bool,
+}
+
+/// A single key within a [`SerializableKeyset`].
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SerializableKey {
+    pub key_data: SerializableKeyData,
+    /// [`KeyStatusType`](tink_proto::KeyStatusType) value, serialized by name (e.g.
+    /// `"ENABLED"`).
+    #[serde(with = "tink_proto::json::key_status_type")]
+    pub status: i32,
+    pub key_id: u32,
+    /// [`OutputPrefixType`](tink_proto::OutputPrefixType) value, serialized by name (e.g.
+    /// `"TINK"`).
+    #[serde(with = "tink_proto::json::output_prefix_type")]
+    pub output_prefix_type: i32,
+}
+
+/// Key data within a [`SerializableKey`].
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SerializableKeyData {
+    pub type_url: String,
+    /// Serialized key proto, serialized as base64.
+    #[serde(with = "tink_proto::json::b64")]
+    pub value: Vec<u8>,
+    /// [`KeyMaterialType`](tink_proto::key_data::KeyMaterialType) value, serialized by name
+    /// (e.g. `"ASYMMETRIC_PUBLIC"`).
+    #[serde(with = "tink_proto::json::key_material_type")]
+    pub key_material_type: i32,
+}
+
+impl From<&Keyset> for SerializableKeyset {
+    fn from(ks: &Keyset) -> Self {
+        SerializableKeyset {
+            primary_key_id: ks.primary_key_id,
+            key: ks
+                .key
+                .iter()
+                .map(|k| {
+                    let key_data = k.key_data.clone().unwrap_or_default();
+                    SerializableKey {
+                        key_data: SerializableKeyData {
+                            type_url: key_data.type_url,
+                            value: key_data.value,
+                            key_material_type: key_data.key_material_type,
+                        },
+                        status: k.status,
+                        key_id: k.key_id,
+                        output_prefix_type: k.output_prefix_type,
+                    }
+                })
+                .collect(),
+            #[cfg(feature = "insecure")]
+            insecure: false,
+        }
+    }
+}
+
+impl From<&SerializableKeyset> for Keyset {
+    fn from(ks: &SerializableKeyset) -> Self {
+        Keyset {
+            primary_key_id: ks.primary_key_id,
+            key: ks
+                .key
+                .iter()
+                .map(|k| tink_proto::keyset::Key {
+                    key_data: Some(tink_proto::KeyData {
+                        type_url: k.key_data.type_url.clone(),
+                        value: k.key_data.value.clone(),
+                        key_material_type: k.key_data.key_material_type,
+                    }),
+                    status: k.status,
+                    key_id: k.key_id,
+                    output_prefix_type: k.output_prefix_type,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<&super::Handle> for SerializableKeyset {
+    type Error = TinkError;
+    /// Convert the keyset held by `h`, failing if it contains secret key material.
+    fn try_from(h: &super::Handle) -> Result<Self, TinkError> {
+        if h.has_secrets()? {
+            return Err("exporting unencrypted secret key material is forbidden".into());
+        }
+        Ok(SerializableKeyset::from(h.keyset()))
+    }
+}
+
+impl TryFrom<&SerializableKeyset> for super::Handle {
+    type Error = TinkError;
+    /// Convert to a [`Handle`](super::Handle), failing if the keyset is invalid (including keys
+    /// with unknown enum values), or if it contains secret key material and the `insecure` flag
+    /// is not set.
+    fn try_from(ks: &SerializableKeyset) -> Result<Self, TinkError> {
+        let keyset = Keyset::from(ks);
+        super::validate(&keyset).map_err(|e| wrap_err("SerializableKeyset: invalid keyset", e))?;
+        #[cfg(feature = "insecure")]
+        if ks.insecure {
+            return super::insecure::new_handle(keyset);
+        }
+        super::Handle::new_with_no_secrets(keyset)
+    }
+}
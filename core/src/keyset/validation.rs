@@ -16,16 +16,27 @@
 
 //! Keyset validation functions.
 
-use crate::TinkError;
+use crate::{ErrorKind, TinkError};
+
+/// Default maximum permitted length of a [`KeyData`](tink_proto::KeyData) `value` in a keyset
+/// that is read in (1 MiB).
+pub const DEFAULT_MAX_KEY_DATA_SIZE: usize = 1024 * 1024;
+
+/// Default maximum permitted length of a serialized keyset (or encrypted keyset) that is read in
+/// (16 MiB).
+pub const DEFAULT_MAX_SERIALIZED_KEYSET_SIZE: usize = 16 * 1024 * 1024;
+
+/// Default maximum permitted number of keys in a keyset that is read in.
+pub const DEFAULT_MAX_KEYSET_KEYS: usize = 10_000;
 
 /// Check whether the given version is valid. The version is valid
 /// only if it is the range [0..max_expected].
 pub fn validate_key_version(version: u32, max_expected: u32) -> Result<(), TinkError> {
     if version > max_expected {
-        Err(format!(
+        Err(TinkError::new(&format!(
             "key has version {version}; only keys with version in range [0..{max_expected}] are supported",
-        )
-        .into())
+        ))
+        .with_kind(ErrorKind::InvalidKey))
     } else {
         Ok(())
     }
@@ -61,7 +72,7 @@ pub fn validate(keyset: &tink_proto::Keyset) -> Result<(), TinkError> {
         num_enabled_keys += 1;
     }
     if num_enabled_keys == 0 {
-        Err("keyset must contain at least one ENABLED key".into())
+        Err("keyset has no enabled keys".into())
     } else if !has_primary_key && !contains_only_pub {
         Err("keyset does not contain a valid primary key".into())
     } else {
@@ -69,11 +80,47 @@ pub fn validate(keyset: &tink_proto::Keyset) -> Result<(), TinkError> {
     }
 }
 
-/// Validate the given key.
+/// Check that none of the [`KeyData`](tink_proto::KeyData) values in the given keyset exceed
+/// `max_size` bytes.
+pub fn validate_key_data_size(
+    keyset: &tink_proto::Keyset,
+    max_size: usize,
+) -> Result<(), TinkError> {
+    for key in &keyset.key {
+        if let Some(key_data) = &key.key_data {
+            if key_data.value.len() > max_size {
+                return Err(format!(
+                    "key {} has key data of size {}, exceeding maximum of {}",
+                    key.key_id,
+                    key_data.value.len(),
+                    max_size
+                )
+                .into());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Check that the given keyset holds no more than `max_keys` keys.
+pub fn validate_key_count(keyset: &tink_proto::Keyset, max_keys: usize) -> Result<(), TinkError> {
+    if keyset.key.len() > max_keys {
+        return Err(format!(
+            "keyset has {} keys, exceeding maximum of {}",
+            keyset.key.len(),
+            max_keys
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Validate the given key.  Key data may only be absent for a
+/// [`Destroyed`](tink_proto::KeyStatusType::Destroyed) key.
 pub fn validate_key(key: &tink_proto::keyset::Key) -> Result<(), TinkError> {
     if key.key_id == 0 {
         Err(format!("key has zero key id: {}", key.key_id).into())
-    } else if key.key_data.is_none() {
+    } else if key.key_data.is_none() && key.status != tink_proto::KeyStatusType::Destroyed as i32 {
         Err(format!("key {} has no key data", key.key_id).into())
     } else if key.output_prefix_type != tink_proto::OutputPrefixType::Tink as i32
         && key.output_prefix_type != tink_proto::OutputPrefixType::Legacy as i32
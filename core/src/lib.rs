@@ -21,11 +21,12 @@
 
 pub mod cryptofmt;
 pub mod keyset;
+pub mod monitoring;
 pub mod primitiveset;
 pub mod registry;
 pub mod subtle;
 pub mod utils;
-pub use utils::TinkError;
+pub use utils::{ErrorKind, ResultExt, TinkError};
 
 /// The [upstream Tink](https://github.com/google/tink) version that this Rust
 /// port is based on.
@@ -67,6 +68,23 @@ pub enum Primitive {
     Verifier(Box<dyn Verifier>),
 }
 
+impl Primitive {
+    /// Return the name of the type of primitive.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Primitive::Aead(_) => "aead",
+            Primitive::DeterministicAead(_) => "deterministic_aead",
+            Primitive::HybridDecrypt(_) => "hybrid_decrypt",
+            Primitive::HybridEncrypt(_) => "hybrid_encrypt",
+            Primitive::Mac(_) => "mac",
+            Primitive::Prf(_) => "prf",
+            Primitive::Signer(_) => "signer",
+            Primitive::StreamingAead(_) => "streaming_aead",
+            Primitive::Verifier(_) => "verifier",
+        }
+    }
+}
+
 /// Manual implementation of the [`Clone`] trait, which makes use of the trait bounds
 /// on the individual primitive types; specifically that they provide a `box_clone()`
 /// method.
@@ -169,3 +187,38 @@ impl From<Primitive> for Box<dyn Verifier> {
         }
     }
 }
+
+/// Primitive trait object types that can be obtained from a keyset via
+/// [`keyset::Handle::primitive`], for example `dyn Aead`.
+pub trait PrimitiveType {
+    /// Name of the primitive type, as given by [`Primitive::name`].
+    const NAME: &'static str;
+
+    /// Extract a primitive of this type, failing if the primitive is of a different type.
+    fn from_primitive(p: Primitive) -> Result<Box<Self>, TinkError>;
+}
+
+macro_rules! primitive_type {
+    ($trait:ident, $name:literal) => {
+        impl PrimitiveType for dyn $trait {
+            const NAME: &'static str = $name;
+
+            fn from_primitive(p: Primitive) -> Result<Box<Self>, TinkError> {
+                match p {
+                    Primitive::$trait(p) => Ok(p),
+                    p => Err(format!("expected {} primitive, got {}", $name, p.name()).into()),
+                }
+            }
+        }
+    };
+}
+
+primitive_type!(Aead, "aead");
+primitive_type!(DeterministicAead, "deterministic_aead");
+primitive_type!(HybridDecrypt, "hybrid_decrypt");
+primitive_type!(HybridEncrypt, "hybrid_encrypt");
+primitive_type!(Mac, "mac");
+primitive_type!(Prf, "prf");
+primitive_type!(Signer, "signer");
+primitive_type!(StreamingAead, "streaming_aead");
+primitive_type!(Verifier, "verifier");
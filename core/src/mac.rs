@@ -30,9 +30,17 @@ pub trait Mac: MacBoxClone {
         if crate::subtle::constant_time_compare(mac, &computed) {
             Ok(())
         } else {
-            Err("Invalid MAC".into())
+            Err(crate::TinkError::new("Invalid MAC").with_kind(crate::ErrorKind::CryptoFailure))
         }
     }
+
+    /// Verify a batch of `(mac, data)` pairs, returning one result per pair in the same order.
+    fn verify_mac_batch(&self, pairs: &[(&[u8], &[u8])]) -> Vec<Result<(), crate::TinkError>> {
+        pairs
+            .iter()
+            .map(|(mac, data)| self.verify_mac(mac, data))
+            .collect()
+    }
 }
 
 /// Trait bound to indicate that primitive trait objects should support cloning
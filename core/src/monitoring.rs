@@ -0,0 +1,112 @@
+// Copyright 2020 The Tink-Rust Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! Hooks for monitoring the use of primitives.
+//!
+//! A [`Client`] registered with
+//! [`register_monitoring_client`](crate::registry::register_monitoring_client) is asked for a
+//! [`Logger`] whenever a primitive wrapper is created, and the wrapper then reports each operation
+//! to that logger.  Keyset building also reports warnings (with a [`Context`] whose primitive is
+//! `"keyset"`).
+
+use crate::{KeyId, TinkError};
+use std::sync::atomic::{AtomicU64, Ordering};
+use tink_proto::KeysetInfo;
+
+/// Description of the primitive and API function that a [`Logger`] is used for.
+#[derive(Clone, Debug)]
+pub struct Context {
+    /// Name of the primitive, e.g. `"aead"`.
+    pub primitive: &'static str,
+    /// Name of the API function, e.g. `"decrypt"`.
+    pub api_function: &'static str,
+    /// Information about the keyset that the primitive was created from.
+    pub keyset_info: KeysetInfo,
+}
+
+/// Receives events for operations performed by a primitive.
+pub trait Logger: Send + Sync {
+    /// Record a successful operation using the key with ID `key_id` on `num_bytes` bytes of input.
+    fn log(&self, key_id: KeyId, num_bytes: usize);
+
+    /// Record a failed operation.
+    fn log_failure(&self);
+
+    /// Record a warning about a suspicious but permitted use of the API, such as a keyset being
+    /// built with more than one key marked as primary.  The default implementation ignores it.
+    fn log_warning(&self, _message: &str) {}
+}
+
+/// Creates [`Logger`]s for primitives.
+pub trait Client: Send + Sync {
+    /// Create a new [`Logger`] for the given context.
+    fn new_logger(&self, context: &Context) -> Result<Box<dyn Logger>, TinkError>;
+}
+
+/// A [`Logger`] that discards all events.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DoNothingLogger;
+
+impl Logger for DoNothingLogger {
+    fn log(&self, _key_id: KeyId, _num_bytes: usize) {}
+    fn log_failure(&self) {}
+}
+
+/// A [`Client`] that creates [`DoNothingLogger`]s; used when no client has been registered.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DoNothingClient;
+
+impl Client for DoNothingClient {
+    fn new_logger(&self, _context: &Context) -> Result<Box<dyn Logger>, TinkError> {
+        Ok(Box::new(DoNothingLogger))
+    }
+}
+
+/// Lightweight operation counters for a primitive wrapper, as an alternative to registering a
+/// full monitoring [`Client`].
+///
+/// Every call to the wrapper counts as an encryption or a decryption, whether or not it
+/// succeeds; calls that fail are additionally counted in `failures`.  For MACs, computing a
+/// tag counts as an encryption and verifying a tag counts as a decryption.
+#[derive(Debug, Default)]
+pub struct Stats {
+    /// Number of encryption (or MAC computation) operations.
+    pub encryptions: AtomicU64,
+    /// Number of decryption (or MAC verification) operations.
+    pub decryptions: AtomicU64,
+    /// Number of operations of either kind that failed.
+    pub failures: AtomicU64,
+}
+
+impl Stats {
+    /// Record an encryption operation with the given outcome.
+    pub fn record_encryption<T>(&self, result: &Result<T, TinkError>) {
+        self.encryptions.fetch_add(1, Ordering::Relaxed);
+        self.record_outcome(result);
+    }
+
+    /// Record a decryption operation with the given outcome.
+    pub fn record_decryption<T>(&self, result: &Result<T, TinkError>) {
+        self.decryptions.fetch_add(1, Ordering::Relaxed);
+        self.record_outcome(result);
+    }
+
+    fn record_outcome<T>(&self, result: &Result<T, TinkError>) {
+        if result.is_err() {
+            self.failures.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
@@ -24,6 +24,7 @@
 ///   * It is indistinguishable from a random function: Given the evaluation of n different inputs,
 ///     an attacker cannot distinguish between the PRF and random bytes on an input different from
 ///     the n that are known.
+///
 /// Use cases for PRF are deterministic redaction of PII, keyed hash functions,
 /// creating sub IDs that do not allow joining with the original dataset without
 /// knowing the key.
@@ -43,7 +44,30 @@ pub trait Prf: PrfBoxClone {
     /// a probability less than 1:2^32. When in doubt, request a security review.
     /// Returns a non ok status if the algorithm fails or if the output of
     /// algorithm is less than outputLength.
+    /// An `output_length` of zero is valid and always yields an empty vector.
     fn compute_prf(&self, input: &[u8], output_length: usize) -> Result<Vec<u8>, crate::TinkError>;
+
+    /// Start a PRF computation whose input is supplied in pieces via [`PrfContext::update`],
+    /// for example when the input is too large to hold in memory at once.  The output is the
+    /// same as for [`compute_prf`](Prf::compute_prf) on the concatenated input.
+    ///
+    /// Returns an error of kind [`UnsupportedType`](crate::ErrorKind::UnsupportedType) for PRFs
+    /// that do not support incremental computation.
+    fn start_incremental(&self) -> Result<Box<dyn PrfContext>, crate::TinkError> {
+        Err(
+            crate::TinkError::new("incremental PRF computation not supported")
+                .with_kind(crate::ErrorKind::UnsupportedType),
+        )
+    }
+}
+
+/// In-progress PRF computation, created by [`Prf::start_incremental`].
+pub trait PrfContext {
+    /// Feed more input into the computation.
+    fn update(&mut self, data: &[u8]);
+
+    /// Complete the computation, returning the first `output_length` bytes of output.
+    fn finalize(self: Box<Self>, output_length: usize) -> Result<Vec<u8>, crate::TinkError>;
 }
 
 /// Trait bound to indicate that primitive trait objects should support cloning
@@ -55,6 +55,11 @@ pub trait KeyManager: Send + Sync {
         })
     }
 
+    /// Return the range of key versions that this key manager accepts.
+    fn supported_versions(&self) -> std::ops::RangeInclusive<u32> {
+        0..=0
+    }
+
     /// Indicate whether this `KeyManager` understands private key types.
     fn supports_private_keys(&self) -> bool {
         false
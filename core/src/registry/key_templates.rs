@@ -16,36 +16,72 @@
 
 //! Provides a registry of generator functions that return [`tink_proto::KeyTemplate`] objects.
 
+use crate::TinkError;
 use lazy_static::lazy_static;
 use std::{collections::HashMap, sync::RwLock};
 
 pub type KeyTemplateGenerator = fn() -> tink_proto::KeyTemplate;
 
 lazy_static! {
-    /// Global registry of key template generator functions, indexed by template name.
-    static ref TEMPLATE_GENERATORS: RwLock<HashMap<String, KeyTemplateGenerator>> =
+    /// Global registry of key template generator functions, indexed by template name.  The
+    /// template produced by each generator is kept alongside it, so that conflicting
+    /// registrations can be detected without invoking generators while the lock is held.
+    static ref TEMPLATE_GENERATORS: RwLock<HashMap<String, (KeyTemplateGenerator, tink_proto::KeyTemplate)>> =
         RwLock::new(HashMap::new());
 }
 
 /// Register a key template generator function by name.
-pub fn register_template_generator(name: &str, generator: KeyTemplateGenerator) {
-    TEMPLATE_GENERATORS
-        .write()
-        .unwrap() // safe: lock
-        .insert(name.to_string(), generator);
+///
+/// Registering a generator under a name that is already in use is allowed only if both
+/// generators produce identical templates; different names may map to identical templates.
+pub fn register_template_generator(
+    name: &str,
+    generator: KeyTemplateGenerator,
+) -> Result<(), TinkError> {
+    let template = generator();
+    let mut generators = TEMPLATE_GENERATORS.write().unwrap(); // safe: lock
+    if let Some((_, existing)) = generators.get(name) {
+        if *existing != template {
+            return Err(format!(
+                "registry::register_template_generator: a different template is already registered as {name}"
+            )
+            .into());
+        }
+    }
+    generators.insert(name.to_string(), (generator, template));
+    Ok(())
 }
 
 /// Find a key template generator function by name.
 pub fn get_template_generator(name: &str) -> Option<KeyTemplateGenerator> {
-    TEMPLATE_GENERATORS.read().unwrap().get(name).copied() // safe: lock
+    TEMPLATE_GENERATORS
+        .read()
+        .unwrap() // safe: lock
+        .get(name)
+        .map(|(generator, _)| *generator)
 }
 
-/// Return all available key template generator names.
-pub fn template_names() -> Vec<String> {
-    TEMPLATE_GENERATORS
+/// Return all available key template generator names, in sorted order.
+pub fn get_template_names() -> Vec<String> {
+    let mut names: Vec<String> = TEMPLATE_GENERATORS
         .read()
         .unwrap() // safe: lock
         .keys()
         .cloned()
-        .collect()
+        .collect();
+    names.sort();
+    names
+}
+
+/// Return all available key template generator names, in sorted order.
+///
+/// Equivalent to [`get_template_names`].
+pub fn template_names() -> Vec<String> {
+    get_template_names()
+}
+
+/// Remove all registered key template generator functions.
+#[cfg(feature = "testing")]
+pub(crate) fn clear_template_generators() {
+    TEMPLATE_GENERATORS.write().unwrap().clear(); // safe: lock
 }
@@ -26,7 +26,7 @@
 //! factories, which in the background query the Registry for specific [`KeyManager`]s. Registry is
 //! public though, to enable configurations with custom primitives and [`KeyManager`]s.
 
-use crate::TinkError;
+use crate::{ErrorKind, TinkError};
 use lazy_static::lazy_static;
 use std::{
     collections::HashMap,
@@ -39,6 +39,8 @@ mod key_manager;
 pub use key_manager::*;
 mod key_templates;
 pub use key_templates::*;
+mod primitive_wrapper;
+pub use primitive_wrapper::*;
 
 lazy_static! {
     /// Global registry of key manager objects, indexed by type URL.
@@ -46,12 +48,17 @@ lazy_static! {
         RwLock::new(HashMap::new());
     /// Global list of KMS client objects.
     static ref KMS_CLIENTS: RwLock<Vec<Arc<dyn KmsClient>>> = RwLock::new(Vec::new());
+    /// Global monitoring client, if any.
+    static ref MONITORING_CLIENT: RwLock<Option<Arc<dyn crate::monitoring::Client>>> =
+        RwLock::new(None);
 }
 
 /// Error message for global key manager registry lock.
 const MERR: &str = "global KEY_MANAGERS lock poisoned";
 /// Error message for global KMS client list lock.
 const CERR: &str = "global KMS_CLIENTS lock poisoned";
+/// Error message for global monitoring client lock.
+const MONERR: &str = "global MONITORING_CLIENT lock poisoned";
 
 /// Register the given key manager. Does not allow overwrite of existing key managers.
 pub fn register_key_manager<T>(km: Arc<T>) -> Result<(), TinkError>
@@ -77,10 +84,19 @@ pub fn get_key_manager(type_url: &str) -> Result<Arc<dyn KeyManager>, TinkError>
         TinkError::new(&format!(
             "registry::get_key_manager: unsupported key type: {type_url}",
         ))
+        .with_kind(ErrorKind::UnsupportedType)
     })?;
     Ok(km.clone())
 }
 
+/// Return the type URLs of all registered key managers, in sorted order.
+pub fn key_manager_type_urls() -> Vec<String> {
+    let key_mgrs = KEY_MANAGERS.read().expect(MERR); // safe: lock
+    let mut type_urls: Vec<String> = key_mgrs.keys().map(|k| k.to_string()).collect();
+    type_urls.sort();
+    type_urls
+}
+
 /// Generate a new [`KeyData`](tink_proto::KeyData) for the given key template.
 pub fn new_key_data(kt: &tink_proto::KeyTemplate) -> Result<tink_proto::KeyData, TinkError> {
     get_key_manager(&kt.type_url)?.new_key_data(&kt.value)
@@ -98,9 +114,16 @@ pub fn primitive_from_key_data(kd: &tink_proto::KeyData) -> Result<crate::Primit
 
 /// Create a new primitive for the given serialized key using the [`KeyManager`]
 /// identified by the given `type_url`.
+///
+/// This operates on a single key, without the output prefix handling and key selection done by
+/// the primitive wrappers built from a [`keyset::Handle`](crate::keyset::Handle), so is mainly
+/// useful for custom wrapping.
 pub fn primitive(type_url: &str, sk: &[u8]) -> Result<crate::Primitive, TinkError> {
     if sk.is_empty() {
-        return Err("registry::primitive: invalid serialized key".into());
+        return Err(
+            TinkError::new("registry::primitive: invalid serialized key")
+                .with_kind(ErrorKind::InvalidKey),
+        );
     }
     get_key_manager(type_url)?.primitive(sk)
 }
@@ -120,6 +143,44 @@ pub fn clear_kms_clients() {
     kms_clients.clear();
 }
 
+/// Register the monitoring client used by primitive wrappers, replacing any previously
+/// registered client.  Only wrappers created after this call use the new client.
+pub fn register_monitoring_client<T>(client: T)
+where
+    T: 'static + crate::monitoring::Client,
+{
+    let mut mon_client = MONITORING_CLIENT.write().expect(MONERR); // safe: lock
+    *mon_client = Some(Arc::new(client));
+}
+
+/// Return the registered monitoring client, or a [`DoNothingClient`] if none has been
+/// registered.
+///
+/// [`DoNothingClient`]: crate::monitoring::DoNothingClient
+pub fn monitoring_client() -> Arc<dyn crate::monitoring::Client> {
+    let mon_client = MONITORING_CLIENT.read().expect(MONERR); // safe: lock
+    match mon_client.as_ref() {
+        Some(c) => c.clone(),
+        None => Arc::new(crate::monitoring::DoNothingClient),
+    }
+}
+
+/// Reset the global registry, removing all registered key managers, key template generators,
+/// primitive wrappers, KMS clients and the monitoring client.
+///
+/// This is intended for test isolation only.  Note that the `init()` functions of the primitive
+/// crates only register their key managers once per process, so they will not re-populate the
+/// registry after it has been cleared.
+#[cfg(feature = "testing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+pub fn clear() {
+    KEY_MANAGERS.write().expect(MERR).clear(); // safe: lock
+    clear_template_generators();
+    clear_primitive_wrappers();
+    clear_kms_clients();
+    *MONITORING_CLIENT.write().expect(MONERR) = None; // safe: lock
+}
+
 /// Fetches a [`KmsClient`] by a given URI.
 pub fn get_kms_client(key_uri: &str) -> Result<Arc<dyn KmsClient>, TinkError> {
     let kms_clients = KMS_CLIENTS.read().expect(CERR); // safe: lock
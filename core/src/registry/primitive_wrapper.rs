@@ -0,0 +1,84 @@
+// Copyright 2020 The Tink-Rust Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! Provides a registry of [`PrimitiveWrapper`] objects, which combine the primitives for the keys
+//! in a keyset into a single primitive.
+
+use crate::{primitiveset::PrimitiveSet, ErrorKind, Primitive, TinkError};
+use lazy_static::lazy_static;
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+use tink_proto::KeysetInfo;
+
+/// `PrimitiveWrapper` combines the primitives for all of the enabled keys in a keyset into a
+/// single primitive of the same type, which uses the primary key for producing output and
+/// selects the appropriate key (by output prefix) when consuming input.
+pub trait PrimitiveWrapper: Send + Sync {
+    /// Return the name of the primitive type handled by this wrapper, as given by
+    /// [`Primitive::name`] and [`PrimitiveType::NAME`](crate::PrimitiveType::NAME).
+    fn primitive_name(&self) -> &'static str;
+
+    /// Wrap the given primitive set into a single primitive.  The `keyset_info` describes the
+    /// keyset that the primitive set was built from, for use in monitoring.
+    fn wrap(&self, ps: PrimitiveSet, keyset_info: &KeysetInfo) -> Result<Primitive, TinkError>;
+}
+
+lazy_static! {
+    /// Global registry of primitive wrappers, indexed by primitive name.
+    static ref PRIMITIVE_WRAPPERS: RwLock<HashMap<&'static str, Arc<dyn PrimitiveWrapper>>> =
+        RwLock::new(HashMap::new());
+}
+
+/// Error message for global primitive wrapper lock.
+const WERR: &str = "global PRIMITIVE_WRAPPERS lock poisoned";
+
+/// Register the given primitive wrapper. Does not allow overwrite of an existing wrapper for the
+/// same primitive type.
+pub fn register_primitive_wrapper<T>(wrapper: Arc<T>) -> Result<(), TinkError>
+where
+    T: 'static + PrimitiveWrapper,
+{
+    let mut wrappers = PRIMITIVE_WRAPPERS.write().expect(WERR); // safe: lock
+    let name = wrapper.primitive_name();
+    if wrappers.contains_key(name) {
+        return Err(format!(
+            "registry::register_primitive_wrapper: wrapper for {name} already registered"
+        )
+        .into());
+    }
+    wrappers.insert(name, wrapper);
+    Ok(())
+}
+
+/// Return the primitive wrapper for the given primitive name if it exists.
+pub fn get_primitive_wrapper(name: &str) -> Result<Arc<dyn PrimitiveWrapper>, TinkError> {
+    let wrappers = PRIMITIVE_WRAPPERS.read().expect(WERR); // safe: lock
+    let wrapper = wrappers.get(name).ok_or_else(|| {
+        TinkError::new(&format!(
+            "registry::get_primitive_wrapper: no wrapper registered for {name}"
+        ))
+        .with_kind(ErrorKind::UnsupportedType)
+    })?;
+    Ok(wrapper.clone())
+}
+
+/// Remove all registered primitive wrappers.
+#[cfg(feature = "testing")]
+pub(crate) fn clear_primitive_wrappers() {
+    PRIMITIVE_WRAPPERS.write().expect(WERR).clear(); // safe: lock
+}
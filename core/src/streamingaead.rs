@@ -16,6 +16,9 @@
 
 //! Streaming authenticated encryption with associated data.
 
+use crate::utils::wrap_err;
+use std::{cell::RefCell, io::Read, rc::Rc};
+
 /// `StreamingAead` is an interface for streaming authenticated encryption with associated data.
 ///
 /// Streaming encryption is typically used for encrypting large plaintexts such as large files.
@@ -46,6 +49,80 @@ pub trait StreamingAead: StreamingAeadBoxClone {
         r: Box<dyn std::io::Read>,
         aad: &[u8],
     ) -> Result<Box<dyn std::io::Read>, crate::TinkError>;
+
+    /// Return an upper bound on the number of ciphertext bytes that a single `read` on a reader
+    /// from [`new_decrypting_reader`] may pull from the underlying reader, or `None` if there is
+    /// no known bound.  This allows callers that feed the underlying reader from a buffer (such
+    /// as asynchronous adapters) to know how much ciphertext needs to be available.
+    ///
+    /// [`new_decrypting_reader`]: StreamingAead::new_decrypting_reader
+    fn max_read_ahead(&self) -> Option<usize> {
+        None
+    }
+
+    /// Return the size of the ciphertext (header and segments) that encrypting `plaintext_len`
+    /// bytes of plaintext produces, or `None` if the size cannot be predicted.
+    fn ciphertext_size(&self, _plaintext_len: u64) -> Option<u64> {
+        None
+    }
+
+    /// Return the size of the plaintext held in a ciphertext of length `ciphertext_len`, for
+    /// example to find the end of the plaintext when seeking.  Fails if no ciphertext can have
+    /// this length, or if the size cannot be determined.
+    fn plaintext_size(&self, _ciphertext_len: u64) -> Result<u64, crate::TinkError> {
+        Err("StreamingAead: plaintext size not available".into())
+    }
+
+    /// Encrypt all of `plaintext` in one go, using `aad` as associated authenticated data.  The
+    /// output is identical in format to the data written via [`new_encrypting_writer`], so it
+    /// can be decrypted by either route.
+    ///
+    /// The default implementation goes via [`new_encrypting_writer`]; implementations may
+    /// override this to avoid the I/O plumbing for small inputs.
+    ///
+    /// [`new_encrypting_writer`]: StreamingAead::new_encrypting_writer
+    fn encrypt_all(&self, plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>, crate::TinkError> {
+        let buf = SharedVec::default();
+        let mut w = self.new_encrypting_writer(Box::new(buf.clone()), aad)?;
+        w.write_all(plaintext)
+            .map_err(|e| wrap_err("write failed", e))?;
+        w.close()?;
+        drop(w);
+        Ok(buf.0.take())
+    }
+
+    /// Decrypt all of `ciphertext` in one go, using `aad` as associated authenticated data.  This
+    /// accepts the output of either [`encrypt_all`] or [`new_encrypting_writer`].
+    ///
+    /// The default implementation goes via [`new_decrypting_reader`]; implementations may
+    /// override this to avoid the I/O plumbing for small inputs.
+    ///
+    /// [`encrypt_all`]: StreamingAead::encrypt_all
+    /// [`new_encrypting_writer`]: StreamingAead::new_encrypting_writer
+    /// [`new_decrypting_reader`]: StreamingAead::new_decrypting_reader
+    fn decrypt_all(&self, ciphertext: &[u8], aad: &[u8]) -> Result<Vec<u8>, crate::TinkError> {
+        let mut r =
+            self.new_decrypting_reader(Box::new(std::io::Cursor::new(ciphertext.to_vec())), aad)?;
+        let mut plaintext = Vec::new();
+        r.read_to_end(&mut plaintext)
+            .map_err(|e| wrap_err("read failed", e))?;
+        Ok(plaintext)
+    }
+}
+
+/// In-memory buffer that can be handed to an encrypting writer (which requires a `'static`
+/// [`std::io::Write`]) while still allowing the written data to be retrieved afterwards.
+#[derive(Clone, Default)]
+struct SharedVec(Rc<RefCell<Vec<u8>>>);
+
+impl std::io::Write for SharedVec {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
 }
 
 /// Trait for an object that writes encrypted data.  Users must call `close()` to finish.
@@ -0,0 +1,104 @@
+// Copyright 2020 The Tink-Rust Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! AES in counter mode, as a building block for CTR-based constructions.
+
+use crate::TinkError;
+use aes::cipher::{consts::U16, generic_array::GenericArray, KeyIvInit, StreamCipher};
+use std::convert::TryInto;
+
+/// The minimum IV size that this implementation supports.
+pub const AES_CTR_MIN_IV_SIZE: usize = 12;
+
+pub const AES_BLOCK_SIZE_IN_BYTES: usize = 16;
+
+type Aes128Ctr = ::ctr::Ctr64BE<aes::Aes128>;
+type Aes256Ctr = ::ctr::Ctr64BE<aes::Aes256>;
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone)]
+enum AesCtrVariant {
+    Aes128([u8; 16]),
+    Aes256([u8; 32]),
+}
+
+/// `AesCtr` applies the AES-CTR keystream for a caller-supplied IV.  It is shared by the
+/// AES-CTR-HMAC AEAD (where it also acts as an `IndCpaCipher`) and AES-CTR-HMAC streaming AEAD
+/// implementations.
+#[derive(Clone)]
+pub struct AesCtr {
+    key: AesCtrVariant,
+    pub iv_size: usize,
+}
+
+impl AesCtr {
+    /// Return an `AesCtr` instance.  The key argument should be the AES key, either 16 or 32 bytes
+    /// to select AES-128 or AES-256.  `iv_size` specifies the size of the IV in bytes.
+    pub fn new(key: &[u8], iv_size: usize) -> Result<AesCtr, TinkError> {
+        if !(AES_CTR_MIN_IV_SIZE..=AES_BLOCK_SIZE_IN_BYTES).contains(&iv_size) {
+            return Err(format!("AesCtr: invalid IV size: {iv_size}").into());
+        }
+        let key = match key.len() {
+            16 => {
+                AesCtrVariant::Aes128(key.try_into().unwrap(/* safe: len checked */))
+            }
+            32 => {
+                AesCtrVariant::Aes256(key.try_into().unwrap(/* safe: len checked */))
+            }
+            l => {
+                return Err(format!("AesCtr: invalid AES key size; want 16 or 32, got {l}").into())
+            }
+        };
+        Ok(AesCtr { key, iv_size })
+    }
+
+    /// Return the length of the key.
+    pub fn key_len(&self) -> usize {
+        match &self.key {
+            AesCtrVariant::Aes128(_) => 16,
+            AesCtrVariant::Aes256(_) => 32,
+        }
+    }
+
+    /// XOR `data` in place with the AES-CTR keystream that starts from the counter block
+    /// formed by `iv` (which must be `iv_size` bytes long) padded with zeros.  This is both
+    /// encryption and decryption, with no IV handling.
+    pub fn apply_keystream(&self, iv: &[u8], data: &mut [u8]) -> Result<(), TinkError> {
+        if iv.len() != self.iv_size {
+            return Err(format!(
+                "AesCtr: invalid IV size {} (want {})",
+                iv.len(),
+                self.iv_size
+            )
+            .into());
+        }
+        let mut padded_iv: GenericArray<u8, U16> = Default::default();
+        padded_iv[..iv.len()].copy_from_slice(iv);
+        match &self.key {
+            AesCtrVariant::Aes128(key) => {
+                let mut stream =
+                    Aes128Ctr::new_from_slices(key, &padded_iv).unwrap(/* safe: len checked */);
+                stream.apply_keystream(data);
+            }
+            AesCtrVariant::Aes256(key) => {
+                let mut stream =
+                    Aes256Ctr::new_from_slices(key, &padded_iv).unwrap(/* safe: len checked */);
+                stream.apply_keystream(data);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,292 @@
+// Copyright 2020 The Tink-Rust Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! Elliptic curve parameters and point encoding for the NIST curves used by ECDSA and ECIES.
+//!
+//! Point encoding and decoding require the `ec` feature.
+
+use crate::TinkError;
+use tink_proto::EllipticCurveType;
+#[cfg(feature = "ec")]
+use {
+    crate::utils::wrap_err,
+    p256::elliptic_curve::{
+        self,
+        sec1::{FromEncodedPoint, ModulusSize, ToEncodedPoint},
+    },
+    tink_proto::EcPointFormat,
+};
+
+// See SEC 1 section 2.3.3.
+/// Prefix byte indicating uncompressed format (x || y).
+#[cfg(feature = "ec")]
+const EC_FORMAT_PREFIX_UNCOMPRESSED: u8 = 4;
+/// Prefix byte indicating compressed format (x, with y having 1 final bit).
+#[cfg(feature = "ec")]
+const EC_FORMAT_PREFIX_COMPRESSED_ODD: u8 = 3;
+/// Prefix byte indicating compressed format (x, with y having 0 final bit).
+#[cfg(feature = "ec")]
+const EC_FORMAT_PREFIX_COMPRESSED_EVEN: u8 = 2;
+
+/// Domain parameters of a named elliptic curve.  Big-endian integers are left-padded with zeros
+/// to the field size in bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CurveParams {
+    /// Curve identifier.
+    pub curve: EllipticCurveType,
+    /// Size of the underlying prime field in bits.
+    pub field_size_in_bits: usize,
+    /// Order of the base point, as a big-endian integer.
+    pub order: &'static [u8],
+    /// Affine x-coordinate of the base point, as a big-endian integer.
+    pub generator_x: &'static [u8],
+    /// Affine y-coordinate of the base point, as a big-endian integer.
+    pub generator_y: &'static [u8],
+}
+
+impl CurveParams {
+    /// Return the size of the underlying prime field in bytes.
+    pub fn field_size_in_bytes(&self) -> usize {
+        self.field_size_in_bits.div_ceil(8)
+    }
+}
+
+// Parameters from FIPS 186-4 appendix D.1.2.
+const P256_ORDER: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xbc, 0xe6, 0xfa, 0xad, 0xa7, 0x17, 0x9e, 0x84, 0xf3, 0xb9, 0xca, 0xc2, 0xfc, 0x63, 0x25, 0x51,
+];
+const P256_GX: [u8; 32] = [
+    0x6b, 0x17, 0xd1, 0xf2, 0xe1, 0x2c, 0x42, 0x47, 0xf8, 0xbc, 0xe6, 0xe5, 0x63, 0xa4, 0x40, 0xf2,
+    0x77, 0x03, 0x7d, 0x81, 0x2d, 0xeb, 0x33, 0xa0, 0xf4, 0xa1, 0x39, 0x45, 0xd8, 0x98, 0xc2, 0x96,
+];
+const P256_GY: [u8; 32] = [
+    0x4f, 0xe3, 0x42, 0xe2, 0xfe, 0x1a, 0x7f, 0x9b, 0x8e, 0xe7, 0xeb, 0x4a, 0x7c, 0x0f, 0x9e, 0x16,
+    0x2b, 0xce, 0x33, 0x57, 0x6b, 0x31, 0x5e, 0xce, 0xcb, 0xb6, 0x40, 0x68, 0x37, 0xbf, 0x51, 0xf5,
+];
+const P384_ORDER: [u8; 48] = [
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xc7, 0x63, 0x4d, 0x81, 0xf4, 0x37, 0x2d, 0xdf,
+    0x58, 0x1a, 0x0d, 0xb2, 0x48, 0xb0, 0xa7, 0x7a, 0xec, 0xec, 0x19, 0x6a, 0xcc, 0xc5, 0x29, 0x73,
+];
+const P384_GX: [u8; 48] = [
+    0xaa, 0x87, 0xca, 0x22, 0xbe, 0x8b, 0x05, 0x37, 0x8e, 0xb1, 0xc7, 0x1e, 0xf3, 0x20, 0xad, 0x74,
+    0x6e, 0x1d, 0x3b, 0x62, 0x8b, 0xa7, 0x9b, 0x98, 0x59, 0xf7, 0x41, 0xe0, 0x82, 0x54, 0x2a, 0x38,
+    0x55, 0x02, 0xf2, 0x5d, 0xbf, 0x55, 0x29, 0x6c, 0x3a, 0x54, 0x5e, 0x38, 0x72, 0x76, 0x0a, 0xb7,
+];
+const P384_GY: [u8; 48] = [
+    0x36, 0x17, 0xde, 0x4a, 0x96, 0x26, 0x2c, 0x6f, 0x5d, 0x9e, 0x98, 0xbf, 0x92, 0x92, 0xdc, 0x29,
+    0xf8, 0xf4, 0x1d, 0xbd, 0x28, 0x9a, 0x14, 0x7c, 0xe9, 0xda, 0x31, 0x13, 0xb5, 0xf0, 0xb8, 0xc0,
+    0x0a, 0x60, 0xb1, 0xce, 0x1d, 0x7e, 0x81, 0x9d, 0x7a, 0x43, 0x1d, 0x7c, 0x90, 0xea, 0x0e, 0x5f,
+];
+const P521_ORDER: [u8; 66] = [
+    0x01, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xff, 0xfa, 0x51, 0x86, 0x87, 0x83, 0xbf, 0x2f, 0x96, 0x6b, 0x7f, 0xcc, 0x01, 0x48, 0xf7, 0x09,
+    0xa5, 0xd0, 0x3b, 0xb5, 0xc9, 0xb8, 0x89, 0x9c, 0x47, 0xae, 0xbb, 0x6f, 0xb7, 0x1e, 0x91, 0x38,
+    0x64, 0x09,
+];
+const P521_GX: [u8; 66] = [
+    0x00, 0xc6, 0x85, 0x8e, 0x06, 0xb7, 0x04, 0x04, 0xe9, 0xcd, 0x9e, 0x3e, 0xcb, 0x66, 0x23, 0x95,
+    0xb4, 0x42, 0x9c, 0x64, 0x81, 0x39, 0x05, 0x3f, 0xb5, 0x21, 0xf8, 0x28, 0xaf, 0x60, 0x6b, 0x4d,
+    0x3d, 0xba, 0xa1, 0x4b, 0x5e, 0x77, 0xef, 0xe7, 0x59, 0x28, 0xfe, 0x1d, 0xc1, 0x27, 0xa2, 0xff,
+    0xa8, 0xde, 0x33, 0x48, 0xb3, 0xc1, 0x85, 0x6a, 0x42, 0x9b, 0xf9, 0x7e, 0x7e, 0x31, 0xc2, 0xe5,
+    0xbd, 0x66,
+];
+const P521_GY: [u8; 66] = [
+    0x01, 0x18, 0x39, 0x29, 0x6a, 0x78, 0x9a, 0x3b, 0xc0, 0x04, 0x5c, 0x8a, 0x5f, 0xb4, 0x2c, 0x7d,
+    0x1b, 0xd9, 0x98, 0xf5, 0x44, 0x49, 0x57, 0x9b, 0x44, 0x68, 0x17, 0xaf, 0xbd, 0x17, 0x27, 0x3e,
+    0x66, 0x2c, 0x97, 0xee, 0x72, 0x99, 0x5e, 0xf4, 0x26, 0x40, 0xc5, 0x50, 0xb9, 0x01, 0x3f, 0xad,
+    0x07, 0x61, 0x35, 0x3c, 0x70, 0x86, 0xa2, 0x72, 0xc2, 0x40, 0x88, 0xbe, 0x94, 0x76, 0x9f, 0xd1,
+    0x66, 0x50,
+];
+
+/// Return the parameters of the curve with the given name, which should be one of `NIST_P256`,
+/// `NIST_P384` or `NIST_P521`.
+pub fn get_curve(name: &str) -> Result<CurveParams, TinkError> {
+    match name {
+        "NIST_P256" => curve_params(EllipticCurveType::NistP256),
+        "NIST_P384" => curve_params(EllipticCurveType::NistP384),
+        "NIST_P521" => curve_params(EllipticCurveType::NistP521),
+        _ => Err(format!("unsupported curve name {name}").into()),
+    }
+}
+
+/// Return the parameters of the given curve.
+pub fn curve_params(curve: EllipticCurveType) -> Result<CurveParams, TinkError> {
+    match curve {
+        EllipticCurveType::NistP256 => Ok(CurveParams {
+            curve,
+            field_size_in_bits: 256,
+            order: &P256_ORDER,
+            generator_x: &P256_GX,
+            generator_y: &P256_GY,
+        }),
+        EllipticCurveType::NistP384 => Ok(CurveParams {
+            curve,
+            field_size_in_bits: 384,
+            order: &P384_ORDER,
+            generator_x: &P384_GX,
+            generator_y: &P384_GY,
+        }),
+        EllipticCurveType::NistP521 => Ok(CurveParams {
+            curve,
+            field_size_in_bits: 521,
+            order: &P521_ORDER,
+            generator_x: &P521_GX,
+            generator_y: &P521_GY,
+        }),
+        _ => Err(format!("unsupported curve {curve:?}").into()),
+    }
+}
+
+/// Return the size in bytes of the field underlying the given curve.
+pub fn field_size_in_bytes(curve: EllipticCurveType) -> Result<usize, TinkError> {
+    Ok(curve_params(curve)?.field_size_in_bytes())
+}
+
+/// Check that `scalar`, interpreted as a big-endian integer, is a valid private key for the given
+/// curve, i.e. that it lies in the range `[1, n-1]` where `n` is the order of the curve.
+pub fn validate_private_scalar(curve: EllipticCurveType, scalar: &[u8]) -> Result<(), TinkError> {
+    let order = curve_params(curve)?.order;
+    let leading_zeros = scalar.iter().take_while(|b| **b == 0).count();
+    let scalar = &scalar[leading_zeros..];
+    if scalar.is_empty() {
+        return Err("private scalar is zero".into());
+    }
+    // Both values are now minimal big-endian encodings, so compare lengths before contents.
+    if (scalar.len(), scalar) >= (order.len(), order) {
+        return Err("private scalar out of range for curve".into());
+    }
+    Ok(())
+}
+
+/// Encode the point with affine coordinates `x` and `y` (big-endian) on the given curve in the
+/// specified format.  Fails if the point is not on the curve.
+#[cfg(feature = "ec")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ec")))]
+pub fn point_encode(
+    curve: EllipticCurveType,
+    format: EcPointFormat,
+    x: &[u8],
+    y: &[u8],
+) -> Result<Vec<u8>, TinkError> {
+    let c_size = field_size_in_bytes(curve)?;
+    let mut uncompressed = Vec::with_capacity(1 + 2 * c_size);
+    uncompressed.push(EC_FORMAT_PREFIX_UNCOMPRESSED);
+    uncompressed.extend_from_slice(&pad_coordinate(x, c_size)?);
+    uncompressed.extend_from_slice(&pad_coordinate(y, c_size)?);
+    // Check that the point is on the curve.
+    sec1_decode(curve, &uncompressed)?;
+    match format {
+        EcPointFormat::Uncompressed => Ok(uncompressed),
+        EcPointFormat::DoNotUseCrunchyUncompressed => Ok(uncompressed[1..].to_vec()),
+        EcPointFormat::Compressed => {
+            let mut encoded = Vec::with_capacity(1 + c_size);
+            encoded.push(if uncompressed[2 * c_size] & 0x01 == 1 {
+                EC_FORMAT_PREFIX_COMPRESSED_ODD
+            } else {
+                EC_FORMAT_PREFIX_COMPRESSED_EVEN
+            });
+            encoded.extend_from_slice(&uncompressed[1..1 + c_size]);
+            Ok(encoded)
+        }
+        _ => Err(format!("invalid point format {format:?}").into()),
+    }
+}
+
+/// Decode a point on the given curve from the specified format, returning its affine `(x, y)`
+/// coordinates as big-endian integers padded to the field size.  Fails if the encoding is
+/// malformed or the point is not on the curve.
+#[cfg(feature = "ec")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ec")))]
+pub fn point_decode(
+    curve: EllipticCurveType,
+    format: EcPointFormat,
+    e: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>), TinkError> {
+    let c_size = field_size_in_bytes(curve)?;
+    match format {
+        EcPointFormat::Uncompressed => {
+            if e.len() != 2 * c_size + 1 {
+                return Err("invalid point size".into());
+            }
+            if e[0] != EC_FORMAT_PREFIX_UNCOMPRESSED {
+                return Err("invalid point format".into());
+            }
+            sec1_decode(curve, e)
+        }
+        EcPointFormat::DoNotUseCrunchyUncompressed => {
+            if e.len() != 2 * c_size {
+                return Err("invalid point size".into());
+            }
+            let mut e_prefixed = Vec::with_capacity(1 + e.len());
+            e_prefixed.push(EC_FORMAT_PREFIX_UNCOMPRESSED);
+            e_prefixed.extend_from_slice(e);
+            sec1_decode(curve, &e_prefixed)
+        }
+        EcPointFormat::Compressed => {
+            if e.len() != c_size + 1 {
+                return Err("compressed point has wrong length".into());
+            }
+            if e[0] != EC_FORMAT_PREFIX_COMPRESSED_EVEN && e[0] != EC_FORMAT_PREFIX_COMPRESSED_ODD {
+                return Err("invalid point format".into());
+            }
+            sec1_decode(curve, e)
+        }
+        _ => Err(format!("invalid point format {format:?}").into()),
+    }
+}
+
+/// Left-pad a big-endian coordinate with zeros to `size` bytes.
+#[cfg(feature = "ec")]
+fn pad_coordinate(v: &[u8], size: usize) -> Result<Vec<u8>, TinkError> {
+    let leading_zeros = v.iter().take_while(|b| **b == 0).count();
+    let v = &v[leading_zeros..];
+    if v.len() > size {
+        return Err("coordinate too large for curve".into());
+    }
+    let mut padded = vec![0; size - v.len()];
+    padded.extend_from_slice(v);
+    Ok(padded)
+}
+
+/// Decode a SEC 1 encoded point on the given curve, returning its affine coordinates.
+#[cfg(feature = "ec")]
+fn sec1_decode(curve: EllipticCurveType, e: &[u8]) -> Result<(Vec<u8>, Vec<u8>), TinkError> {
+    match curve {
+        EllipticCurveType::NistP256 => sec1_decode_for::<p256::NistP256>(e),
+        EllipticCurveType::NistP384 => sec1_decode_for::<p384::NistP384>(e),
+        EllipticCurveType::NistP521 => sec1_decode_for::<p521::NistP521>(e),
+        _ => Err(format!("unsupported curve {curve:?}").into()),
+    }
+}
+
+#[cfg(feature = "ec")]
+fn sec1_decode_for<C>(e: &[u8]) -> Result<(Vec<u8>, Vec<u8>), TinkError>
+where
+    C: elliptic_curve::CurveArithmetic,
+    elliptic_curve::AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    elliptic_curve::FieldBytesSize<C>: ModulusSize,
+{
+    let pub_key = elliptic_curve::PublicKey::<C>::from_sec1_bytes(e)
+        .map_err(|e| wrap_err("invalid point", e))?;
+    let point = pub_key.to_encoded_point(/* compress= */ false);
+    match (point.x(), point.y()) {
+        (Some(x), Some(y)) => Ok((x.to_vec(), y.to_vec())),
+        _ => Err("invalid point".into()),
+    }
+}
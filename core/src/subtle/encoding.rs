@@ -0,0 +1,45 @@
+// Copyright 2020 The Tink-Rust Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! URL-safe base64 encoding helpers.
+
+use crate::{utils::wrap_err, TinkError};
+use base64::{
+    alphabet,
+    engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig},
+    Engine,
+};
+
+/// URL-safe base64 engine that omits padding on encode and accepts input with or without
+/// padding on decode.
+const URL_SAFE_NO_PAD: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::URL_SAFE,
+    GeneralPurposeConfig::new()
+        .with_encode_padding(false)
+        .with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+/// Encode `data` as URL-safe base64 without padding.
+pub fn base64_encode(data: &[u8]) -> String {
+    URL_SAFE_NO_PAD.encode(data)
+}
+
+/// Decode URL-safe base64 `data`, which may or may not include trailing padding.
+pub fn base64_decode(data: &str) -> Result<Vec<u8>, TinkError> {
+    URL_SAFE_NO_PAD
+        .decode(data)
+        .map_err(|e| wrap_err("invalid base64 data", e))
+}
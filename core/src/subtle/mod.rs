@@ -21,6 +21,10 @@ use digest::Digest;
 use subtle::ConstantTimeEq;
 use tink_proto::HashType;
 
+mod aes_ctr;
+pub use self::aes_ctr::*;
+pub mod ec_util;
+pub mod encoding;
 mod hkdf;
 pub use self::hkdf::*;
 pub mod random;
@@ -69,6 +73,15 @@ pub fn compute_hash(hash_fn: &mut HashFunc, data: &[u8]) -> Result<Vec<u8>, Tink
     })
 }
 
+/// Calculate a one-shot digest of the given data using the specified hash algorithm.
+///
+/// This is equivalent to [`compute_hash`] with a fresh hash function from [`get_hash_func`].
+pub fn compute_digest(hash: HashType, data: &[u8]) -> Result<Vec<u8>, TinkError> {
+    let mut hash_fn =
+        get_hash_func(hash).ok_or_else(|| TinkError::new("invalid hash algorithm"))?;
+    compute_hash(&mut hash_fn, data)
+}
+
 /// Calculate a hash of the given data with the given hash function.
 fn compute_hash_with<T>(hash_func: &mut T, data: &[u8]) -> Vec<u8>
 where
@@ -16,12 +16,22 @@
 
 //! Utilities for random data.
 
+use crate::TinkError;
 use rand::{thread_rng, Rng};
 
+/// Maximum number of random bytes generated in a single request to the RNG.
+const RANDOM_CHUNK_SIZE: usize = 64 * 1024;
+
 /// Return a vector of the given `size` filled with random bytes.
+///
+/// Large requests are filled in chunks of at most `RANDOM_CHUNK_SIZE` bytes, so that a single
+/// call never holds on to the RNG (or asks the OS entropy source) for an unbounded amount of
+/// output.
 pub fn get_random_bytes(size: usize) -> Vec<u8> {
     let mut data = vec![0u8; size];
-    thread_rng().fill(&mut data[..]);
+    for chunk in data.chunks_mut(RANDOM_CHUNK_SIZE) {
+        thread_rng().fill(chunk);
+    }
     data
 }
 
@@ -29,3 +39,29 @@ pub fn get_random_bytes(size: usize) -> Vec<u8> {
 pub fn get_random_uint32() -> u32 {
     thread_rng().gen()
 }
+
+/// Return a uniformly random scalar in the range `[1, n-1]`, where `n` is the group order given
+/// as a big-endian integer.  The result is big-endian and has the same length as `order`.
+///
+/// Candidates are drawn with the same bit length as `n` and rejected if they fall outside the
+/// range, which avoids the bias that reducing a random value modulo `n` would introduce.
+pub fn get_random_key_material(order: &[u8]) -> Result<Vec<u8>, TinkError> {
+    let start = match order.iter().position(|b| *b != 0) {
+        Some(i) => i,
+        None => return Err("invalid group order".into()),
+    };
+    if start == order.len() - 1 && order[start] == 1 {
+        return Err("invalid group order".into());
+    }
+    // Mask for the most significant non-zero byte, covering the bit length of `n`.
+    let top_mask = 0xffu8 >> order[start].leading_zeros();
+    let mut rng = thread_rng();
+    let mut candidate = vec![0u8; order.len()];
+    loop {
+        rng.fill(&mut candidate[start..]);
+        candidate[start] &= top_mask;
+        if candidate.iter().any(|b| *b != 0) && candidate.as_slice() < order {
+            return Ok(candidate);
+        }
+    }
+}
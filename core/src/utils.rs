@@ -21,11 +21,29 @@
 
 use std::error::Error;
 
+/// Broad category of a [`TinkError`], allowing callers to branch on the kind of failure
+/// without inspecting error messages.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum ErrorKind {
+    /// Failure not assigned to any more specific category.
+    Other,
+    /// Serialized data (e.g. a key or key format protobuf) could not be decoded.
+    DecodeError,
+    /// Key material or parameters are invalid or have an unsupported version.
+    InvalidKey,
+    /// The requested key type or primitive is not supported.
+    UnsupportedType,
+    /// A cryptographic operation failed.
+    CryptoFailure,
+}
+
 /// `Error` type for errors emitted by Tink. Note that errors from cryptographic
 /// operations are necessarily uninformative, to avoid information leakage.
 #[derive(Debug)]
 pub struct TinkError {
     msg: String,
+    kind: ErrorKind,
     src: Option<Box<dyn Error + Send>>,
 }
 
@@ -33,6 +51,39 @@ impl TinkError {
     pub fn new(msg: &str) -> Self {
         msg.into()
     }
+
+    /// Return the category of this error.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Return this error with its category set to `kind`.
+    pub fn with_kind(mut self, kind: ErrorKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Return a new error that annotates this one with `msg`, equivalent to
+    /// `wrap_err(msg, self)`.  The new error keeps this error's [`ErrorKind`].
+    pub fn context(self, msg: &str) -> Self {
+        wrap_err(msg, self)
+    }
+}
+
+/// Extension trait for annotating the error of a [`Result`] with an additional message, as a
+/// fluent alternative to `.map_err(|e| wrap_err(msg, e))`.
+pub trait ResultExt<T> {
+    /// Wrap any error in a [`TinkError`] carrying `msg`, as for [`wrap_err`].
+    fn context(self, msg: &str) -> Result<T, TinkError>;
+}
+
+impl<T, E> ResultExt<T> for Result<T, E>
+where
+    E: Error + Send + 'static,
+{
+    fn context(self, msg: &str) -> Result<T, TinkError> {
+        self.map_err(|e| wrap_err(msg, e))
+    }
 }
 
 impl std::fmt::Display for TinkError {
@@ -47,10 +98,30 @@ impl std::fmt::Display for TinkError {
 
 impl Error for TinkError {}
 
+/// Serialize as `{"message": ..., "kind": ...}`, where the message is the [`Display`] form of the
+/// error (including any wrapped source errors).  Tink error messages never include key material.
+///
+/// [`Display`]: std::fmt::Display
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl serde::Serialize for TinkError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("TinkError", 2)?;
+        state.serialize_field("message", &self.to_string())?;
+        state.serialize_field("kind", &self.kind)?;
+        state.end()
+    }
+}
+
 impl std::convert::From<&str> for TinkError {
     fn from(msg: &str) -> Self {
         TinkError {
             msg: msg.to_string(),
+            kind: ErrorKind::Other,
             src: None,
         }
     }
@@ -58,7 +129,11 @@ impl std::convert::From<&str> for TinkError {
 
 impl std::convert::From<String> for TinkError {
     fn from(msg: String) -> Self {
-        TinkError { msg, src: None }
+        TinkError {
+            msg,
+            kind: ErrorKind::Other,
+            src: None,
+        }
     }
 }
 
@@ -71,12 +146,19 @@ impl std::convert::From<String> for TinkError {
 ///     return nil, fmt.Errorf("FunctionCall failed: %s", err)
 ///   }
 /// ```
+///
+/// If `src` is itself a [`TinkError`], the resulting error inherits its [`ErrorKind`].
 pub fn wrap_err<T>(msg: &str, src: T) -> TinkError
 where
     T: Error + Send + 'static,
 {
+    let src: Box<dyn Error + Send> = Box::new(src);
+    let kind = src
+        .downcast_ref::<TinkError>()
+        .map_or(ErrorKind::Other, TinkError::kind);
     TinkError {
         msg: msg.to_string(),
-        src: Some(Box::new(src)),
+        kind,
+        src: Some(src),
     }
 }
@@ -40,6 +40,26 @@ fn new_with_key_manager(
     Ok(Box::new(ret))
 }
 
+/// [`PrimitiveWrapper`](tink_core::registry::PrimitiveWrapper) that combines deterministic AEAD
+/// primitives, registered by [`init`](crate::init).
+pub struct DeterministicAeadWrapper;
+
+impl tink_core::registry::PrimitiveWrapper for DeterministicAeadWrapper {
+    fn primitive_name(&self) -> &'static str {
+        "deterministic_aead"
+    }
+
+    fn wrap(
+        &self,
+        ps: tink_core::primitiveset::PrimitiveSet,
+        _keyset_info: &tink_proto::KeysetInfo,
+    ) -> Result<tink_core::Primitive, TinkError> {
+        Ok(tink_core::Primitive::DeterministicAead(Box::new(
+            WrappedDeterministicAead::new(ps)?,
+        )))
+    }
+}
+
 /// A [`tink_core::DeterministicAead`] implementation that uses the underlying primitive set
 /// for deterministic encryption and decryption.
 #[derive(Clone)]
@@ -75,13 +95,25 @@ impl WrappedDeterministicAead {
 
 impl tink_core::DeterministicAead for WrappedDeterministicAead {
     fn encrypt_deterministically(&self, pt: &[u8], aad: &[u8]) -> Result<Vec<u8>, TinkError> {
+        self.encrypt_deterministically_multi(pt, &[aad])
+    }
+
+    fn decrypt_deterministically(&self, ct: &[u8], aad: &[u8]) -> Result<Vec<u8>, TinkError> {
+        self.decrypt_deterministically_multi(ct, &[aad])
+    }
+
+    fn encrypt_deterministically_multi(
+        &self,
+        pt: &[u8],
+        aad: &[&[u8]],
+    ) -> Result<Vec<u8>, TinkError> {
         let primary = self
             .ps
             .primary
             .as_ref()
             .ok_or_else(|| TinkError::new("no primary"))?;
 
-        let ct = primary.primitive.encrypt_deterministically(pt, aad)?;
+        let ct = primary.primitive.encrypt_deterministically_multi(pt, aad)?;
 
         let mut ret = Vec::with_capacity(primary.prefix.len() + ct.len());
         ret.extend_from_slice(&primary.prefix);
@@ -89,7 +121,11 @@ impl tink_core::DeterministicAead for WrappedDeterministicAead {
         Ok(ret)
     }
 
-    fn decrypt_deterministically(&self, ct: &[u8], aad: &[u8]) -> Result<Vec<u8>, TinkError> {
+    fn decrypt_deterministically_multi(
+        &self,
+        ct: &[u8],
+        aad: &[&[u8]],
+    ) -> Result<Vec<u8>, TinkError> {
         // try non-raw keys
         let prefix_size = tink_core::cryptofmt::NON_RAW_PREFIX_SIZE;
         if ct.len() > prefix_size {
@@ -97,7 +133,10 @@ impl tink_core::DeterministicAead for WrappedDeterministicAead {
             let ct_no_prefix = &ct[prefix_size..];
             if let Some(entries) = self.ps.entries_for_prefix(prefix) {
                 for entry in entries {
-                    if let Ok(pt) = entry.primitive.decrypt_deterministically(ct_no_prefix, aad) {
+                    if let Ok(pt) = entry
+                        .primitive
+                        .decrypt_deterministically_multi(ct_no_prefix, aad)
+                    {
                         return Ok(pt);
                     }
                 }
@@ -107,7 +146,7 @@ impl tink_core::DeterministicAead for WrappedDeterministicAead {
         // try raw keys
         if let Some(entries) = self.ps.raw_entries() {
             for entry in entries {
-                if let Ok(pt) = entry.primitive.decrypt_deterministically(ct, aad) {
+                if let Ok(pt) = entry.primitive.decrypt_deterministically_multi(ct, aad) {
                     return Ok(pt);
                 }
             }
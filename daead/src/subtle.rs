@@ -26,8 +26,13 @@ const AES_BLOCK_SIZE: usize = 16;
 /// [RFC 5297](https://tools.ietf.org/html/rfc5297).
 ///
 /// `AesSiv` implements a deterministic encryption with additional data (i.e. the
-/// `DeterministicAEAD` trait). Hence the implementation below is restricted
-/// to one AD component.
+/// `DeterministicAEAD` trait). Multiple AD components are available via
+/// [`encrypt_deterministically_multi`](tink_core::DeterministicAead::encrypt_deterministically_multi).
+///
+/// The 64-byte key is split into two halves: the first 32 bytes key the S2V
+/// (AES-CMAC) computation of the synthetic IV over the additional data and
+/// plaintext, and the second 32 bytes key the AES-CTR encryption that uses
+/// the synthetic IV as its initial counter block.
 ///
 /// # Security Note:
 ///
@@ -71,27 +76,43 @@ impl tink_core::DeterministicAead for AesSiv {
         &self,
         plaintext: &[u8],
         additional_data: &[u8],
+    ) -> Result<Vec<u8>, TinkError> {
+        self.encrypt_deterministically_multi(plaintext, &[additional_data])
+    }
+
+    fn decrypt_deterministically(
+        &self,
+        ciphertext: &[u8],
+        additional_data: &[u8],
+    ) -> Result<Vec<u8>, TinkError> {
+        self.decrypt_deterministically_multi(ciphertext, &[additional_data])
+    }
+
+    fn encrypt_deterministically_multi(
+        &self,
+        plaintext: &[u8],
+        additional_data: &[&[u8]],
     ) -> Result<Vec<u8>, TinkError> {
         if plaintext.len() > (isize::MAX as usize) - AES_BLOCK_SIZE {
             return Err("AesSiv: plaintext too long".into());
         }
         self.cipher
             .borrow_mut()
-            .encrypt([additional_data], plaintext)
+            .encrypt(additional_data, plaintext)
             .map_err(|e| wrap_err("AesSiv: encrypt failed", e))
     }
 
-    fn decrypt_deterministically(
+    fn decrypt_deterministically_multi(
         &self,
         ciphertext: &[u8],
-        additional_data: &[u8],
+        additional_data: &[&[u8]],
     ) -> Result<Vec<u8>, TinkError> {
         if ciphertext.len() < aes_siv::siv::IV_SIZE {
             return Err("AesSiv: ciphertext is too short".into());
         }
         self.cipher
             .borrow_mut()
-            .decrypt([additional_data], ciphertext)
+            .decrypt(additional_data, ciphertext)
             .map_err(|e| wrap_err("AesSiv: decrypt failed", e))
     }
 }
@@ -195,6 +195,7 @@ pub(crate) fn check_ecies_aead_hkdf_params(
         .as_ref()
         .ok_or_else(|| TinkError::new("no dem_params"))?;
 
+    crate::subtle::validate_hkdf_salt(&kem_params.hkdf_salt)?;
     let curve = EllipticCurveType::from_i32(kem_params.curve_type)
         .unwrap_or(EllipticCurveType::UnknownCurve);
 
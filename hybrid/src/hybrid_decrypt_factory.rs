@@ -40,6 +40,26 @@ fn new_decrypt_with_key_manager(
     Ok(Box::new(ret))
 }
 
+/// [`PrimitiveWrapper`](tink_core::registry::PrimitiveWrapper) that combines hybrid decryption
+/// primitives, registered by [`init`](crate::init).
+pub struct HybridDecryptWrapper;
+
+impl tink_core::registry::PrimitiveWrapper for HybridDecryptWrapper {
+    fn primitive_name(&self) -> &'static str {
+        "hybrid_decrypt"
+    }
+
+    fn wrap(
+        &self,
+        ps: tink_core::primitiveset::PrimitiveSet,
+        _keyset_info: &tink_proto::KeysetInfo,
+    ) -> Result<tink_core::Primitive, TinkError> {
+        Ok(tink_core::Primitive::HybridDecrypt(Box::new(
+            WrappedHybridDecrypt::new(ps)?,
+        )))
+    }
+}
+
 /// `WrappedHybridDecrypt` is a hybrid decrypt implementation that uses the underlying primitive set
 /// for decryption.
 #[derive(Clone)]
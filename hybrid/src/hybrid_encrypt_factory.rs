@@ -40,6 +40,26 @@ fn new_encrypt_with_key_manager(
     Ok(Box::new(ret))
 }
 
+/// [`PrimitiveWrapper`](tink_core::registry::PrimitiveWrapper) that combines hybrid encryption
+/// primitives, registered by [`init`](crate::init).
+pub struct HybridEncryptWrapper;
+
+impl tink_core::registry::PrimitiveWrapper for HybridEncryptWrapper {
+    fn primitive_name(&self) -> &'static str {
+        "hybrid_encrypt"
+    }
+
+    fn wrap(
+        &self,
+        ps: tink_core::primitiveset::PrimitiveSet,
+        _keyset_info: &tink_proto::KeysetInfo,
+    ) -> Result<tink_core::Primitive, TinkError> {
+        Ok(tink_core::Primitive::HybridEncrypt(Box::new(
+            WrappedHybridEncrypt::new(ps)?,
+        )))
+    }
+}
+
 /// `WrappedHybridEncrypt` is a hybrid encrypt implementation that uses the underlying primitive set
 /// for encryption.
 #[derive(Clone)]
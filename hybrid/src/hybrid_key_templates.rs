@@ -35,6 +35,23 @@ pub fn ecies_hkdf_aes128_gcm_key_template() -> KeyTemplate {
     )
 }
 
+/// Return a [`KeyTemplate`] that generates an ECDH P-256 and decapsulation key AES128-GCM key with
+/// the following parameters:
+///  - KEM: ECDH over NIST P-256, with compressed point encoding
+///  - DEM: AES128-GCM
+///  - KDF: HKDF-HMAC-SHA256 with an empty salt
+#[cfg(feature = "aead")]
+#[cfg_attr(docsrs, doc(cfg(feature = "aead")))]
+pub fn ecies_p256_compressed_hkdf_aes128_gcm_key_template() -> KeyTemplate {
+    create_ecies_aead_hkdf_key_template(
+        EllipticCurveType::NistP256,
+        HashType::Sha256,
+        EcPointFormat::Compressed,
+        tink_aead::aes128_gcm_key_template(),
+        &[],
+    )
+}
+
 /// Return a [`KeyTemplate`] that generates an ECDH P-256 and decapsulation key
 /// AES128-CTR-HMAC-SHA256 with the following parameters:
 ///  - KEM: ECDH over NIST P-256
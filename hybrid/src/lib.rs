@@ -31,7 +31,9 @@
 #![deny(broken_intra_doc_links)]
 
 use std::sync::Once;
-use tink_core::registry::{register_key_manager, register_template_generator};
+use tink_core::registry::{
+    register_key_manager, register_primitive_wrapper, register_template_generator,
+};
 
 mod ecies_aead_hkdf_dem_helper;
 pub use ecies_aead_hkdf_dem_helper::*;
@@ -70,14 +72,25 @@ pub fn init() {
             EciesAeadHkdfPublicKeyKeyManager::default(),
         ))
         .expect("tink_hybrid::init() failed"); // safe: init
+        register_primitive_wrapper(std::sync::Arc::new(HybridDecryptWrapper))
+            .expect("tink_hybrid::init() failed"); // safe: init
+        register_primitive_wrapper(std::sync::Arc::new(HybridEncryptWrapper))
+            .expect("tink_hybrid::init() failed"); // safe: init
 
         register_template_generator(
             "ECIES_P256_HKDF_HMAC_SHA256_AES128_GCM",
             ecies_hkdf_aes128_gcm_key_template,
-        );
+        )
+        .expect("tink_hybrid::init() failed"); // safe: init
+        register_template_generator(
+            "ECIES_P256_COMPRESSED_HKDF_HMAC_SHA256_AES128_GCM",
+            ecies_p256_compressed_hkdf_aes128_gcm_key_template,
+        )
+        .expect("tink_hybrid::init() failed"); // safe: init
         register_template_generator(
             "ECIES_P256_HKDF_HMAC_SHA256_AES128_CTR_HMAC_SHA256",
             ecies_hkdf_aes128_ctr_hmac_sha256_key_template,
-        );
+        )
+        .expect("tink_hybrid::init() failed"); // safe: init
     });
 }
@@ -34,6 +34,8 @@ pub struct EciesAeadHkdfHybridDecrypt {
 impl EciesAeadHkdfHybridDecrypt {
     /// Return an ECIES decryption construct with HKDF-KEM (key encapsulation mechanism)
     /// and AEAD-DEM (data encapsulation mechanism).
+    ///
+    /// As for encryption, an empty `hkdf_salt` is equivalent to HKDF's default all-zero salt.
     pub fn new(
         private_key: EcPrivateKey,
         hkdf_salt: &[u8],
@@ -41,6 +43,7 @@ impl EciesAeadHkdfHybridDecrypt {
         point_format: EcPointFormat,
         dem_helper: crate::EciesAeadHkdfDemHelper,
     ) -> Result<EciesAeadHkdfHybridDecrypt, TinkError> {
+        super::validate_hkdf_salt(hkdf_salt)?;
         Ok(EciesAeadHkdfHybridDecrypt {
             private_key,
             hkdf_salt: hkdf_salt.to_vec(),
@@ -18,6 +18,24 @@ use crate::subtle::{EcPublicKey, EciesAeadHkdfDemHelper, EciesHkdfSenderKem};
 use tink_core::TinkError;
 use tink_proto::{EcPointFormat, HashType};
 
+/// Maximum permitted size in bytes of the HKDF salt.  Tink's own templates use an empty salt,
+/// and longer salts give no additional security.
+pub const MAX_HKDF_SALT_SIZE: usize = 1024;
+
+/// Check that `hkdf_salt` does not exceed [`MAX_HKDF_SALT_SIZE`].
+pub(crate) fn validate_hkdf_salt(hkdf_salt: &[u8]) -> Result<(), TinkError> {
+    if hkdf_salt.len() > MAX_HKDF_SALT_SIZE {
+        Err(format!(
+            "HKDF salt too long: {} bytes (max {})",
+            hkdf_salt.len(),
+            MAX_HKDF_SALT_SIZE
+        )
+        .into())
+    } else {
+        Ok(())
+    }
+}
+
 /// Instance of ECIES encryption with HKDF-KEM (key encapsulation mechanism)
 /// and AEAD-DEM (data encapsulation mechanism).
 #[derive(Clone)]
@@ -32,6 +50,9 @@ pub struct EciesAeadHkdfHybridEncrypt {
 impl EciesAeadHkdfHybridEncrypt {
     /// Return an ECIES encryption construct with HKDF-KEM (key encapsulation mechanism)
     /// and AEAD-DEM (data encapsulation mechanism).
+    ///
+    /// As in other Tink implementations, an empty `hkdf_salt` is passed to HKDF as-is, which is
+    /// equivalent to the all-zero salt that HKDF uses when no salt is given.
     pub fn new(
         public_key: &EcPublicKey,
         hkdf_salt: &[u8],
@@ -39,6 +60,7 @@ impl EciesAeadHkdfHybridEncrypt {
         point_format: EcPointFormat,
         dem_helper: crate::EciesAeadHkdfDemHelper,
     ) -> Result<EciesAeadHkdfHybridEncrypt, TinkError> {
+        validate_hkdf_salt(hkdf_salt)?;
         Ok(EciesAeadHkdfHybridEncrypt {
             public_key: public_key.clone(),
             hkdf_salt: hkdf_salt.to_vec(),
@@ -51,6 +73,10 @@ impl EciesAeadHkdfHybridEncrypt {
 
 impl tink_core::HybridEncrypt for EciesAeadHkdfHybridEncrypt {
     /// Encrypt using ECIES with a HKDF-KEM and AEAD-DEM mechanisms.
+    ///
+    /// The `context_info` is bound to the ciphertext by using it as the HKDF info when deriving
+    /// the DEM key, so decryption with a different `context_info` fails.  (It is not also passed
+    /// as DEM associated data, which would break compatibility with other Tink implementations.)
     fn encrypt(&self, plaintext: &[u8], context_info: &[u8]) -> Result<Vec<u8>, TinkError> {
         let s_kem = EciesHkdfSenderKem::new(&self.public_key);
         let kem_key = s_kem.encapsulate(
@@ -23,16 +23,12 @@ use p256::{
         AffinePoint,
     },
 };
-use tink_core::{utils::wrap_err, TinkError};
+use tink_core::{subtle::ec_util, TinkError};
 use tink_proto::{EcPointFormat, EllipticCurveType};
 
 // See SEC 1 section 2.3.3.
 /// Prefix byte indicating uncompressed format (x || y)
 const EC_FORMAT_PREFIX_UNCOMPRESSED: u8 = 4;
-/// Prefix byte indicating compressed format (x, with y having 1 final bit).
-const EC_FORMAT_PREFIX_COMPRESSED_ODD: u8 = 3;
-/// Prefix byte indicating compressed format (x, with y having 0 final bit).
-const EC_FORMAT_PREFIX_COMPRESSED_EVEN: u8 = 2;
 
 /// An elliptic curve public key.
 #[derive(Debug, Clone)]
@@ -130,17 +126,8 @@ impl EcPrivateKey {
     }
 }
 
-fn field_size_in_bytes(c: EllipticCurveType) -> Result<usize, TinkError> {
-    match c {
-        EllipticCurveType::NistP256 => {
-            Ok(<p256::NistP256 as elliptic_curve::Curve>::FieldBytesSize::to_usize())
-        }
-        _ => Err(format!("unsupported curve {c:?}").into()),
-    }
-}
-
 pub fn encoding_size_in_bytes(c: EllipticCurveType, p: EcPointFormat) -> Result<usize, TinkError> {
-    let c_size = field_size_in_bytes(c)?;
+    let c_size = ec_util::field_size_in_bytes(c)?;
     match p {
         EcPointFormat::Uncompressed => Ok(2 * c_size + 1), // 04 || x || y
         EcPointFormat::DoNotUseCrunchyUncompressed => Ok(2 * c_size), // x || y
@@ -155,34 +142,8 @@ pub fn point_encode(
     p_format: EcPointFormat,
     pub_key: &EcPublicKey,
 ) -> Result<Vec<u8>, TinkError> {
-    let c_size = field_size_in_bytes(c)?;
     let (x, y) = pub_key.x_y_bytes()?;
-    match p_format {
-        EcPointFormat::Uncompressed => {
-            let mut encoded = vec![0; 2 * c_size + 1];
-            encoded[1 + 2 * c_size - y.len()..].copy_from_slice(&y);
-            encoded[1 + c_size - x.len()..1 + c_size].copy_from_slice(&x);
-            encoded[0] = EC_FORMAT_PREFIX_UNCOMPRESSED;
-            Ok(encoded)
-        }
-        EcPointFormat::DoNotUseCrunchyUncompressed => {
-            let mut encoded = vec![0; 2 * c_size];
-            encoded[2 * c_size - y.len()..].copy_from_slice(&y);
-            encoded[c_size - x.len()..c_size].copy_from_slice(&x);
-            Ok(encoded)
-        }
-        EcPointFormat::Compressed => {
-            let mut encoded = vec![0; c_size + 1];
-            encoded[0] = if y[y.len() - 1] & 0x01 == 1 {
-                EC_FORMAT_PREFIX_COMPRESSED_ODD
-            } else {
-                EC_FORMAT_PREFIX_COMPRESSED_EVEN
-            };
-            encoded[1 + c_size - x.len()..].copy_from_slice(&x);
-            Ok(encoded)
-        }
-        _ => Err("invalid point format".into()),
-    }
+    ec_util::point_encode(c, p_format, &x, &y)
 }
 
 // Decode an encoded point to return an [`EcPubKey`].
@@ -191,53 +152,8 @@ pub fn point_decode(
     p_format: EcPointFormat,
     e: &[u8],
 ) -> Result<EcPublicKey, TinkError> {
-    let c_size = field_size_in_bytes(c)?;
-    match p_format {
-        EcPointFormat::Uncompressed => {
-            if e.len() != (2 * c_size + 1) {
-                return Err("invalid point size".into());
-            }
-            if e[0] != EC_FORMAT_PREFIX_UNCOMPRESSED {
-                return Err("invalid point format".into());
-            }
-            match c {
-                EllipticCurveType::NistP256 => {
-                    let pub_key = p256::PublicKey::from_sec1_bytes(e)
-                        .map_err(|e| wrap_err("invalid point", e))?;
-                    Ok(EcPublicKey::NistP256(*pub_key.as_affine()))
-                }
-                _ => Err(format!("unsupported curve {c:?}").into()),
-            }
-        }
-        EcPointFormat::DoNotUseCrunchyUncompressed => {
-            if e.len() != 2 * c_size {
-                return Err("invalid point size".into());
-            }
-            let mut e_prefixed = Vec::with_capacity(1 + e.len());
-            e_prefixed.push(EC_FORMAT_PREFIX_UNCOMPRESSED);
-            e_prefixed.extend_from_slice(e);
-            point_decode(c, EcPointFormat::Uncompressed, &e_prefixed)
-        }
-        EcPointFormat::Compressed => {
-            if e.len() != c_size + 1 {
-                return Err("compressed point has wrong length".into());
-            }
-            let _lsb = match e[0] {
-                EC_FORMAT_PREFIX_COMPRESSED_EVEN => false,
-                EC_FORMAT_PREFIX_COMPRESSED_ODD => true,
-                _ => return Err("invalid format".into()),
-            };
-            match c {
-                EllipticCurveType::NistP256 => {
-                    let pub_key = p256::PublicKey::from_sec1_bytes(e)
-                        .map_err(|e| wrap_err("invalid point", e))?;
-                    Ok(EcPublicKey::NistP256(*pub_key.as_affine()))
-                }
-                _ => Err(format!("unsupported curve {c:?}").into()),
-            }
-        }
-        _ => Err(format!("invalid point format: {p_format:?}").into()),
-    }
+    let (x, y) = ec_util::point_decode(c, p_format, e)?;
+    EcPublicKey::new(c, &x, &y)
 }
 
 /// Compute a shared secret using given private key and peer public key.
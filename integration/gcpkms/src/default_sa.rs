@@ -80,8 +80,7 @@ async fn on_gce_test() -> bool {
             Ok(v) => v,
             Err(_) => return false,
         };
-        return rsp.headers().get("Metadata-Flavor")
-            == Some(&http::HeaderValue::from_static("Google"));
+        rsp.headers().get("Metadata-Flavor") == Some(&http::HeaderValue::from_static("Google"))
     };
     // Ensure the HTTP request times out in a reasonable period.
     let timed_http_result = async {
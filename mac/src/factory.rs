@@ -17,34 +17,65 @@
 //! Provides an implementation of MAC using a set of underlying implementations.
 
 use std::sync::Arc;
-use tink_core::{utils::wrap_err, TinkError};
+use tink_core::{monitoring, primitiveset::TypedEntry, utils::wrap_err, TinkError};
 use tink_proto::OutputPrefixType;
 
 const MAX_INT: usize = usize::MAX >> 1;
 
 /// Create a [`tink_core::Mac`] primitive from the given keyset handle.
 pub fn new(h: &tink_core::keyset::Handle) -> Result<Box<dyn tink_core::Mac>, TinkError> {
-    new_with_key_manager(h, None)
+    new_with_key_manager(h, None, None)
+}
+
+/// Create a [`tink_core::Mac`] primitive from the given keyset handle, which records each
+/// operation in `stats`.
+pub fn new_with_stats(
+    h: &tink_core::keyset::Handle,
+    stats: Arc<monitoring::Stats>,
+) -> Result<Box<dyn tink_core::Mac>, TinkError> {
+    new_with_key_manager(h, None, Some(stats))
 }
 
 /// Create a [`tink_core::Mac`] primitive from the given keyset handle and a custom key manager.
 fn new_with_key_manager(
     h: &tink_core::keyset::Handle,
     km: Option<Arc<dyn tink_core::registry::KeyManager>>,
+    stats: Option<Arc<monitoring::Stats>>,
 ) -> Result<Box<dyn tink_core::Mac>, TinkError> {
     let ps = h
         .primitives_with_key_manager(km)
         .map_err(|e| wrap_err("mac::factory: cannot obtain primitive set", e))?;
 
-    let ret = WrappedMac::new(ps)?;
+    let mut ret = WrappedMac::new(ps)?;
+    ret.stats = stats;
     Ok(Box::new(ret))
 }
 
+/// [`PrimitiveWrapper`](tink_core::registry::PrimitiveWrapper) that combines MAC primitives,
+/// registered by [`init`](crate::init).
+pub struct MacWrapper;
+
+impl tink_core::registry::PrimitiveWrapper for MacWrapper {
+    fn primitive_name(&self) -> &'static str {
+        "mac"
+    }
+
+    fn wrap(
+        &self,
+        ps: tink_core::primitiveset::PrimitiveSet,
+        _keyset_info: &tink_proto::KeysetInfo,
+    ) -> Result<tink_core::Primitive, TinkError> {
+        Ok(tink_core::Primitive::Mac(Box::new(WrappedMac::new(ps)?)))
+    }
+}
+
 /// A [`tink_core::Mac`] implementation that uses the underlying primitive set to compute and
 /// verify MACs.
 #[derive(Clone)]
 struct WrappedMac {
     ps: tink_core::primitiveset::TypedPrimitiveSet<Box<dyn tink_core::Mac>>,
+    /// Operation counters, if requested.
+    stats: Option<Arc<monitoring::Stats>>,
 }
 
 impl WrappedMac {
@@ -67,12 +98,84 @@ impl WrappedMac {
         }
         // The `.into()` call is only safe because we've just checked that all entries have
         // the right type of primitive
-        Ok(WrappedMac { ps: ps.into() })
+        Ok(WrappedMac {
+            ps: ps.into(),
+            stats: None,
+        })
     }
-}
 
-impl tink_core::Mac for WrappedMac {
-    fn compute_mac(&self, data: &[u8]) -> Result<Vec<u8>, TinkError> {
+    /// Verify `mac` against `data`, using the given (pre-fetched) raw entries of the primitive
+    /// set.
+    ///
+    /// Most MACs are produced by the primary key, so that is tried first (if its prefix matches);
+    /// the remaining candidates are then tried in keyset order, prefixed keys before raw keys.
+    ///
+    /// After any prefix is stripped, the remaining candidate tag is passed on as-is, rather than
+    /// being cut down to each key's tag size; a tag with trailing bytes is therefore rejected,
+    /// so that each valid MAC has a single encoding.
+    fn verify_with_raw_entries(
+        &self,
+        raw_entries: Option<&Vec<TypedEntry<Box<dyn tink_core::Mac>>>>,
+        mac: &[u8],
+        data: &[u8],
+    ) -> Result<(), TinkError> {
+        // This also rejects raw MAC with size of 4 bytes or fewer. Those MACs are
+        // clearly insecure, thus should be discouraged.
+        let prefix_size = tink_core::cryptofmt::NON_RAW_PREFIX_SIZE;
+        if mac.len() <= prefix_size {
+            return Err("mac::factory: invalid mac".into());
+        }
+
+        // try the primary key
+        let primary = self.ps.primary.as_ref();
+        if let Some(primary) = primary {
+            if mac.starts_with(&primary.prefix) && verify_entry(primary, mac, data)?.is_ok() {
+                return Ok(());
+            }
+        }
+        let is_primary = |entry: &TypedEntry<Box<dyn tink_core::Mac>>| {
+            primary.is_some_and(|p| p.key_id == entry.key_id)
+        };
+
+        // try non raw keys
+        let prefix = &mac[..prefix_size];
+        if let Some(entries) = self.ps.entries_for_prefix(prefix) {
+            for entry in entries.iter().filter(|e| !is_primary(e)) {
+                if verify_entry(entry, mac, data)?.is_ok() {
+                    return Ok(());
+                }
+            }
+        }
+
+        if let Some(entries) = raw_entries {
+            for entry in entries.iter().filter(|e| !is_primary(e)) {
+                if verify_entry(entry, mac, data)?.is_ok() {
+                    return Ok(());
+                }
+            }
+        }
+
+        // nothing worked
+        Err("mac::factory: decryption failed".into())
+    }
+
+    /// Verify `mac` against `data` as for `verify_with_raw_entries`, recording
+    /// the outcome in the operation counters.
+    fn verify_and_record(
+        &self,
+        raw_entries: Option<&Vec<TypedEntry<Box<dyn tink_core::Mac>>>>,
+        mac: &[u8],
+        data: &[u8],
+    ) -> Result<(), TinkError> {
+        let result = self.verify_with_raw_entries(raw_entries, mac, data);
+        if let Some(stats) = &self.stats {
+            stats.record_decryption(&result);
+        }
+        result
+    }
+
+    /// Compute a MAC over `data` with the primary entry.
+    fn compute_with_primary(&self, data: &[u8]) -> Result<Vec<u8>, TinkError> {
         let primary = match &self.ps.primary {
             Some(p) => p,
             None => return Err("mac::factory: no primary primitive".into()),
@@ -94,56 +197,52 @@ impl tink_core::Mac for WrappedMac {
         ret.extend_from_slice(&mac);
         Ok(ret)
     }
+}
 
-    fn verify_mac(&self, mac: &[u8], data: &[u8]) -> Result<(), TinkError> {
-        // This also rejects raw MAC with size of 4 bytes or fewer. Those MACs are
-        // clearly insecure, thus should be discouraged.
-        let prefix_size = tink_core::cryptofmt::NON_RAW_PREFIX_SIZE;
-        if mac.len() <= prefix_size {
-            return Err("mac::factory: invalid mac".into());
-        }
-
-        // try non raw keys
-        let prefix = &mac[..prefix_size];
-        let mac_no_prefix = &mac[prefix_size..];
-        if let Some(entries) = self.ps.entries_for_prefix(prefix) {
-            for entry in entries {
-                let result = if entry.prefix_type == OutputPrefixType::Legacy {
-                    if data.len() >= MAX_INT {
-                        return Err("mac::factory: data too long".into());
-                    }
-                    let mut local_data = Vec::with_capacity(data.len() + 1);
-                    local_data.extend_from_slice(data);
-                    local_data.push(0u8);
-                    entry.primitive.verify_mac(mac_no_prefix, &local_data)
-                } else {
-                    entry.primitive.verify_mac(mac_no_prefix, data)
-                };
-                if result.is_ok() {
-                    return Ok(());
-                }
+/// Verify `mac` (including any output prefix) against `data` with a single entry.  The outer
+/// `Result` reports errors that should abort verification altogether; the inner one reports
+/// whether this entry's MAC matched.
+fn verify_entry(
+    entry: &TypedEntry<Box<dyn tink_core::Mac>>,
+    mac: &[u8],
+    data: &[u8],
+) -> Result<Result<(), TinkError>, TinkError> {
+    let mac_no_prefix = &mac[entry.prefix.len()..];
+    let result = match entry.prefix_type {
+        OutputPrefixType::Legacy => {
+            if data.len() >= MAX_INT {
+                return Err("mac::factory: data too long".into());
             }
+            let mut local_data = Vec::with_capacity(data.len() + 1);
+            local_data.extend_from_slice(data);
+            local_data.push(tink_core::cryptofmt::LEGACY_START_BYTE);
+            entry.primitive.verify_mac(mac_no_prefix, &local_data)
         }
+        _ => entry.primitive.verify_mac(mac_no_prefix, data),
+    };
+    Ok(result)
+}
 
-        if let Some(entries) = self.ps.raw_entries() {
-            for entry in entries {
-                let result = if entry.prefix_type == OutputPrefixType::Legacy {
-                    // This diverges from the upstream Go code (as of v1.5.0), but matches the
-                    // behaviour of the upstream C++/Java/Python code.
-                    let mut local_data = Vec::with_capacity(data.len() + 1);
-                    local_data.extend_from_slice(data);
-                    local_data.push(tink_core::cryptofmt::LEGACY_START_BYTE);
-                    entry.primitive.verify_mac(mac, &local_data)
-                } else {
-                    entry.primitive.verify_mac(mac, data)
-                };
-                if result.is_ok() {
-                    return Ok(());
-                }
-            }
+impl tink_core::Mac for WrappedMac {
+    fn compute_mac(&self, data: &[u8]) -> Result<Vec<u8>, TinkError> {
+        let result = self.compute_with_primary(data);
+        if let Some(stats) = &self.stats {
+            stats.record_encryption(&result);
         }
+        result
+    }
 
-        // nothing worked
-        Err("mac::factory: decryption failed".into())
+    fn verify_mac(&self, mac: &[u8], data: &[u8]) -> Result<(), TinkError> {
+        self.verify_and_record(self.ps.raw_entries(), mac, data)
+    }
+
+    /// Verify a batch of `(mac, data)` pairs, looking up the raw entries of the primitive set
+    /// only once for the whole batch.
+    fn verify_mac_batch(&self, pairs: &[(&[u8], &[u8])]) -> Vec<Result<(), TinkError>> {
+        let raw_entries = self.ps.raw_entries();
+        pairs
+            .iter()
+            .map(|(mac, data)| self.verify_and_record(raw_entries, mac, data))
+            .collect()
     }
 }
@@ -16,7 +16,7 @@
 
 //! Key manager for AES-CMAC keys for HMAC.
 
-use tink_core::{utils::wrap_err, TinkError};
+use tink_core::{registry::KeyManager, utils::wrap_err, ErrorKind, TinkError};
 use tink_proto::{prost::Message, HashType};
 
 /// Maximal version of HMAC keys.
@@ -28,19 +28,25 @@ pub const HMAC_TYPE_URL: &str = "type.googleapis.com/google.crypto.tink.HmacKey"
 #[derive(Default)]
 pub(crate) struct HmacKeyManager;
 
-impl tink_core::registry::KeyManager for HmacKeyManager {
+impl KeyManager for HmacKeyManager {
     /// Create an HMAC instance for the given serialized [`HmacKey`](tink_proto::HmacKey) proto.
     fn primitive(&self, serialized_key: &[u8]) -> Result<tink_core::Primitive, TinkError> {
         if serialized_key.is_empty() {
-            return Err("HmacKeyManager: invalid key".into());
+            return Err(
+                TinkError::new("HmacKeyManager: invalid key").with_kind(ErrorKind::InvalidKey)
+            );
         }
 
-        let key = tink_proto::HmacKey::decode(serialized_key)
-            .map_err(|e| wrap_err("HmacKeyManager: decode failed", e))?;
+        let key = tink_proto::HmacKey::decode(serialized_key).map_err(|e| {
+            wrap_err("HmacKeyManager: decode failed", e).with_kind(ErrorKind::DecodeError)
+        })?;
         validate_key(&key)?;
 
         let params = match &key.params {
-            None => return Err("HmacKeyManager: no key params".into()),
+            None => {
+                return Err(TinkError::new("HmacKeyManager: no key params")
+                    .with_kind(ErrorKind::InvalidKey))
+            }
             Some(p) => p,
         };
         let hash = HashType::from_i32(params.hash).unwrap_or(HashType::UnknownHash);
@@ -53,23 +59,9 @@ impl tink_core::registry::KeyManager for HmacKeyManager {
     /// Generate a new serialized [`HmacKey`](tink_proto::HmacKey) according to specification in
     /// the given [`HmacKeyFormat`](tink_proto::HmacKeyFormat).
     fn new_key(&self, serialized_key_format: &[u8]) -> Result<Vec<u8>, TinkError> {
-        if serialized_key_format.is_empty() {
-            return Err("HmacKeyManager: invalid key format".into());
-        }
-        let key_format = tink_proto::HmacKeyFormat::decode(serialized_key_format)
-            .map_err(|_| "HmacKeyManager: invalid key format")?;
-        validate_key_format(&key_format)
-            .map_err(|e| wrap_err("HmacKeyManager: invalid key format", e))?;
-        let key_value = tink_core::subtle::random::get_random_bytes(key_format.key_size as usize);
-        let mut sk = Vec::new();
-        tink_proto::HmacKey {
-            version: HMAC_KEY_VERSION,
-            params: key_format.params,
-            key_value,
-        }
-        .encode(&mut sk)
-        .map_err(|e| wrap_err("HmacKeyManager: failed to encode new key", e))?;
-        Ok(sk)
+        new_key_with(serialized_key_format, |key_size| {
+            Ok(tink_core::subtle::random::get_random_bytes(key_size))
+        })
     }
 
     fn type_url(&self) -> &'static str {
@@ -79,19 +71,97 @@ impl tink_core::registry::KeyManager for HmacKeyManager {
     fn key_material_type(&self) -> tink_proto::key_data::KeyMaterialType {
         tink_proto::key_data::KeyMaterialType::Symmetric
     }
+
+    fn supported_versions(&self) -> std::ops::RangeInclusive<u32> {
+        0..=HMAC_KEY_VERSION
+    }
+}
+
+/// Build a serialized [`HmacKey`](tink_proto::HmacKey) from caller-provided key material, such
+/// as a key exported from an HSM.  The key material and `params` are validated in the same way
+/// as for keys generated by the HMAC key manager, so keys shorter than 16 bytes are rejected.
+pub fn import_hmac_key(
+    params: &tink_proto::HmacParams,
+    key_value: &[u8],
+) -> Result<Vec<u8>, TinkError> {
+    let key = tink_proto::HmacKey {
+        version: HMAC_KEY_VERSION,
+        params: Some(params.clone()),
+        key_value: key_value.to_vec(),
+    };
+    validate_key(&key).map_err(|e| wrap_err("HmacKeyManager: invalid imported key", e))?;
+    let mut sk = Vec::new();
+    key.encode(&mut sk)
+        .map_err(|e| wrap_err("HmacKeyManager: failed to encode imported key", e))?;
+    Ok(sk)
+}
+
+/// Generate a new serialized [`HmacKey`](tink_proto::HmacKey) according to the given
+/// [`HmacKeyFormat`](tink_proto::HmacKeyFormat), in the same way as the HMAC key manager's
+/// `new_key()`, but reading the `key_size` bytes of key material from `reader` rather than
+/// generating them randomly.
+///
+/// This is intended for tests that need known keys only.
+#[cfg(feature = "testing")]
+pub fn new_hmac_key_from_reader(
+    serialized_key_format: &[u8],
+    reader: &mut dyn std::io::Read,
+) -> Result<Vec<u8>, TinkError> {
+    new_key_with(serialized_key_format, |key_size| {
+        let mut key_value = vec![0; key_size];
+        reader
+            .read_exact(&mut key_value)
+            .map_err(|e| wrap_err("HmacKeyManager: cannot read key material", e))?;
+        Ok(key_value)
+    })
+}
+
+/// Generate a new serialized [`HmacKey`](tink_proto::HmacKey) according to the given
+/// [`HmacKeyFormat`](tink_proto::HmacKeyFormat), using `key_value_for` to obtain key material
+/// of the required size.
+fn new_key_with<F>(serialized_key_format: &[u8], key_value_for: F) -> Result<Vec<u8>, TinkError>
+where
+    F: FnOnce(usize) -> Result<Vec<u8>, TinkError>,
+{
+    if serialized_key_format.is_empty() {
+        return Err(
+            TinkError::new("HmacKeyManager: invalid key format").with_kind(ErrorKind::InvalidKey)
+        );
+    }
+    let key_format = tink_proto::HmacKeyFormat::decode(serialized_key_format).map_err(|e| {
+        wrap_err("HmacKeyManager: invalid key format", e).with_kind(ErrorKind::DecodeError)
+    })?;
+    validate_key_format(&key_format).map_err(|e| {
+        wrap_err("HmacKeyManager: invalid key format", e).with_kind(ErrorKind::InvalidKey)
+    })?;
+    let key_value = key_value_for(key_format.key_size as usize)?;
+    let mut sk = Vec::new();
+    tink_proto::HmacKey {
+        version: HMAC_KEY_VERSION,
+        params: key_format.params,
+        key_value,
+    }
+    .encode(&mut sk)
+    .map_err(|e| wrap_err("HmacKeyManager: failed to encode new key", e))?;
+    Ok(sk)
 }
 
 /// Validate the given [`HmacKey`](tink_proto::HmacKey). It only validates the version of the
 /// key because other parameters will be validated in primitive construction.
 fn validate_key(key: &tink_proto::HmacKey) -> Result<(), TinkError> {
-    tink_core::keyset::validate_key_version(key.version, HMAC_KEY_VERSION)
+    let versions = HmacKeyManager.supported_versions();
+    tink_core::keyset::validate_key_version(key.version, *versions.end())
         .map_err(|e| wrap_err("HmacKeyManager: invalid version", e))?;
     let key_size = key.key_value.len();
     match &key.params {
-        None => Err("HmacKeyManager: missing HMAC params".into()),
+        None => {
+            Err(TinkError::new("HmacKeyManager: missing HMAC params")
+                .with_kind(ErrorKind::InvalidKey))
+        }
         Some(params) => {
             let hash = HashType::from_i32(params.hash).unwrap_or(HashType::UnknownHash);
             crate::subtle::validate_hmac_params(hash, key_size, params.tag_size as usize)
+                .map_err(|e| e.with_kind(ErrorKind::InvalidKey))
         }
     }
 }
@@ -57,6 +57,26 @@ pub fn aes_cmac_tag128_key_template() -> KeyTemplate {
     create_cmac_key_template(32, 16)
 }
 
+/// Return a [`KeyTemplate`] that generates a HMAC key using the given `hash` function and
+/// `tag_size` (in bytes), with the TINK output prefix.  The key size is the digest size of
+/// `hash`.
+///
+/// The parameters are not validated here; an invalid combination is rejected when a key is
+/// generated from the template.
+pub fn hmac_key_template(hash: tink_proto::HashType, tag_size: u32) -> KeyTemplate {
+    let key_size = tink_core::subtle::get_hash_digest_size(hash).unwrap_or(32) as u32;
+    create_hmac_key_template(key_size, tag_size, hash)
+}
+
+/// Return a [`KeyTemplate`] that generates a 32-byte AES-CMAC key with the given `tag_size` (in
+/// bytes), with the TINK output prefix.
+///
+/// The tag size is not validated here; an invalid value is rejected when a key is generated
+/// from the template.
+pub fn aes_cmac_key_template(tag_size: u32) -> KeyTemplate {
+    create_cmac_key_template(32, tag_size)
+}
+
 /// Create a new [`KeyTemplate`] for HMAC using the given parameters.
 fn create_hmac_key_template(
     key_size: u32,
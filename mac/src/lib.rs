@@ -45,29 +45,34 @@ static INIT: Once = Once::new();
 /// Tink.
 pub fn init() {
     INIT.call_once(|| {
-        tink_core::registry::register_key_manager(std::sync::Arc::new(HmacKeyManager::default()))
+        tink_core::registry::register_key_manager(std::sync::Arc::new(HmacKeyManager))
+            .expect("tink_mac::init() failed"); // safe: init
+        tink_core::registry::register_key_manager(std::sync::Arc::new(AesCmacKeyManager))
+            .expect("tink_mac::init() failed"); // safe: init
+        tink_core::registry::register_primitive_wrapper(std::sync::Arc::new(MacWrapper))
             .expect("tink_mac::init() failed"); // safe: init
-        tink_core::registry::register_key_manager(
-            std::sync::Arc::new(AesCmacKeyManager::default()),
-        )
-        .expect("tink_mac::init() failed"); // safe: init
 
         tink_core::registry::register_template_generator(
             "HMAC_SHA256_128BITTAG",
             hmac_sha256_tag128_key_template,
-        );
+        )
+        .expect("tink_mac::init() failed"); // safe: init
         tink_core::registry::register_template_generator(
             "HMAC_SHA256_256BITTAG",
             hmac_sha256_tag256_key_template,
-        );
+        )
+        .expect("tink_mac::init() failed"); // safe: init
         tink_core::registry::register_template_generator(
             "HMAC_SHA512_256BITTAG",
             hmac_sha512_tag256_key_template,
-        );
+        )
+        .expect("tink_mac::init() failed"); // safe: init
         tink_core::registry::register_template_generator(
             "HMAC_SHA512_512BITTAG",
             hmac_sha512_tag512_key_template,
-        );
-        tink_core::registry::register_template_generator("AES_CMAC", aes_cmac_tag128_key_template);
+        )
+        .expect("tink_mac::init() failed"); // safe: init
+        tink_core::registry::register_template_generator("AES_CMAC", aes_cmac_tag128_key_template)
+            .expect("tink_mac::init() failed"); // safe: init
     });
 }
@@ -16,7 +16,7 @@
 
 //! Provides an implementation of MAC using HMAC.
 
-use tink_core::{utils::wrap_err, Prf, TinkError};
+use tink_core::{utils::wrap_err, TinkError};
 use tink_proto::HashType;
 
 /// Minimum key size in bytes.
@@ -42,6 +42,44 @@ impl Hmac {
         let prf = tink_prf::subtle::HmacPrf::new(hash_alg, key)?;
         Ok(Hmac { prf, tag_size })
     }
+
+    /// Start an incremental MAC computation, for data that is too large to hold in memory at
+    /// once.  Feed the data in pieces via [`HmacContext::update`], then compute or check the tag.
+    pub fn start(&self) -> HmacContext {
+        HmacContext {
+            ctx: self.prf.start(),
+            tag_size: self.tag_size,
+        }
+    }
+}
+
+/// In-progress incremental MAC computation, created by [`Hmac::start`].
+#[derive(Clone)]
+pub struct HmacContext {
+    ctx: tink_prf::subtle::HmacPrfContext,
+    tag_size: usize,
+}
+
+impl HmacContext {
+    /// Feed more data into the MAC computation.
+    pub fn update(&mut self, data: &[u8]) {
+        self.ctx.update(data)
+    }
+
+    /// Complete the MAC computation, returning the tag.
+    pub fn finalize(self) -> Result<Vec<u8>, TinkError> {
+        self.ctx.finalize(self.tag_size)
+    }
+
+    /// Complete the MAC computation, checking that `tag` is correct for the data supplied.
+    pub fn finalize_verify(self, tag: &[u8]) -> Result<(), TinkError> {
+        let computed = self.finalize()?;
+        if tink_core::subtle::constant_time_compare(tag, &computed) {
+            Ok(())
+        } else {
+            Err(TinkError::new("Invalid MAC").with_kind(tink_core::ErrorKind::CryptoFailure))
+        }
+    }
 }
 
 /// Validate parameters of [`Hmac`] constructor.
@@ -67,6 +105,8 @@ pub fn validate_hmac_params(
 
 impl tink_core::Mac for Hmac {
     fn compute_mac(&self, data: &[u8]) -> Result<Vec<u8>, TinkError> {
-        self.prf.compute_prf(data, self.tag_size)
+        let mut ctx = self.start();
+        ctx.update(data);
+        ctx.finalize()
     }
 }
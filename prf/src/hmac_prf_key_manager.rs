@@ -94,12 +94,18 @@ fn validate_key(
     Ok((params.clone(), hash))
 }
 
-/// Validates the given [`HmacPrfKeyFormat`](tink_proto::HmacPrfKeyFormat).
+/// Validates the given [`HmacPrfKeyFormat`](tink_proto::HmacPrfKeyFormat).  New keys are only
+/// generated for SHA-256, SHA-384 and SHA-512; existing keys with other hashes (e.g. SHA-1) are
+/// still accepted by [`validate_key`].
 fn validate_key_format(format: &tink_proto::HmacPrfKeyFormat) -> Result<(), TinkError> {
     let params = format
         .params
         .as_ref()
         .ok_or_else(|| TinkError::new("no params"))?;
     let hash = HashType::from_i32(params.hash).unwrap_or(HashType::UnknownHash);
-    subtle::validate_hmac_prf_params(hash, format.key_size as usize)
+    subtle::validate_hmac_prf_params(hash, format.key_size as usize)?;
+    match hash {
+        HashType::Sha256 | HashType::Sha384 | HashType::Sha512 => Ok(()),
+        h => Err(format!("hash {h:?} not supported for new keys").into()),
+    }
 }
@@ -44,25 +44,31 @@ static INIT: Once = Once::new();
 /// Tink.
 pub fn init() {
     INIT.call_once(|| {
-        register_key_manager(std::sync::Arc::new(HmacPrfKeyManager::default()))
+        register_key_manager(std::sync::Arc::new(HmacPrfKeyManager))
             .expect("tink_prf::init() failed"); // safe: init
-        register_key_manager(std::sync::Arc::new(HkdfPrfKeyManager::default()))
+        register_key_manager(std::sync::Arc::new(HkdfPrfKeyManager))
             .expect("tink_prf::init() failed"); // safe: init
-        register_key_manager(std::sync::Arc::new(AesCmacPrfKeyManager::default()))
+        register_key_manager(std::sync::Arc::new(AesCmacPrfKeyManager))
+            .expect("tink_prf::init() failed"); // safe: init
+        tink_core::registry::register_primitive_wrapper(std::sync::Arc::new(PrfWrapper))
             .expect("tink_prf::init() failed"); // safe: init
 
         tink_core::registry::register_template_generator(
             "HKDF_SHA256",
             hkdf_sha256_prf_key_template,
-        );
+        )
+        .expect("tink_prf::init() failed"); // safe: init
         tink_core::registry::register_template_generator(
             "HMAC_SHA256_PRF",
             hmac_sha256_prf_key_template,
-        );
+        )
+        .expect("tink_prf::init() failed"); // safe: init
         tink_core::registry::register_template_generator(
             "HMAC_SHA512_PRF",
             hmac_sha512_prf_key_template,
-        );
-        tink_core::registry::register_template_generator("AES_CMAC_PRF", aes_cmac_prf_key_template);
+        )
+        .expect("tink_prf::init() failed"); // safe: init
+        tink_core::registry::register_template_generator("AES_CMAC_PRF", aes_cmac_prf_key_template)
+            .expect("tink_prf::init() failed"); // safe: init
     });
 }
@@ -63,6 +63,32 @@ impl Set {
     }
 }
 
+/// [`PrimitiveWrapper`](tink_core::registry::PrimitiveWrapper) that turns the PRFs of a keyset
+/// into the single PRF for its primary key (as used by [`Set::compute_primary_prf`]), registered
+/// by [`init`](crate::init).
+pub struct PrfWrapper;
+
+impl tink_core::registry::PrimitiveWrapper for PrfWrapper {
+    fn primitive_name(&self) -> &'static str {
+        "prf"
+    }
+
+    fn wrap(
+        &self,
+        ps: tink_core::primitiveset::PrimitiveSet,
+        _keyset_info: &tink_proto::KeysetInfo,
+    ) -> Result<tink_core::Primitive, TinkError> {
+        let mut set = wrap_prf_set(ps)?;
+        let prf = set.prfs.remove(&set.primary_id).ok_or_else(|| {
+            TinkError::new(&format!(
+                "Could not find primary ID {} in prf.Set",
+                set.primary_id
+            ))
+        })?;
+        Ok(tink_core::Primitive::Prf(prf))
+    }
+}
+
 fn wrap_prf_set(ps: tink_core::primitiveset::PrimitiveSet) -> Result<Set, TinkError> {
     let entry = match &ps.primary {
         None => return Err("prf::Set: no primary available".into()),
@@ -16,7 +16,7 @@
 
 //! Provides an implementation of PRF using HKDF.
 
-use tink_core::TinkError;
+use tink_core::{PrfContext, TinkError};
 use tink_proto::HashType;
 
 // We use a somewhat larger minimum key size than usual, because PRFs might be
@@ -25,6 +25,9 @@ use tink_proto::HashType;
 // https://eprint.iacr.org/2012/159)
 const MIN_HKDF_KEY_SIZE_IN_BYTES: usize = 32;
 
+/// Maximum permitted length of the HKDF salt; anything longer indicates a malformed key.
+pub const MAX_HKDF_SALT_SIZE_IN_BYTES: usize = 1024;
+
 /// `HkdfPrf` is a type that can be used to compute several HKDFs with the same key material.
 #[derive(Clone)]
 pub struct HkdfPrf {
@@ -54,17 +57,51 @@ impl HkdfPrf {
         };
         Ok(HkdfPrf { prk })
     }
+
+    /// Compute the PRF for `input`, with separate contextual `info`, returning `out_len` bytes.
+    ///
+    /// The HKDF info parameter is the encoding `len(input) || input || info`, where the length
+    /// is a big-endian 64-bit value, so that distinct `(input, info)` pairs never map to the same
+    /// HKDF info.
+    pub fn compute_prf_with_info(
+        &self,
+        input: &[u8],
+        info: &[u8],
+        out_len: usize,
+    ) -> Result<Vec<u8>, TinkError> {
+        let input_len = (input.len() as u64).to_be_bytes();
+        self.expand(&[&input_len, input, info], out_len)
+    }
+
+    /// Run HKDF-Expand with the concatenation of `info` as the info parameter.
+    fn expand(&self, info: &[&[u8]], out_len: usize) -> Result<Vec<u8>, TinkError> {
+        let mut okm = vec![0; out_len];
+        match &self.prk {
+            HkdfPrfVariant::Sha1(prk) => prk
+                .expand_multi_info(info, &mut okm)
+                .map_err(|_| "HkdfPrf: compute of hkdf failed")?,
+            HkdfPrfVariant::Sha256(prk) => prk
+                .expand_multi_info(info, &mut okm)
+                .map_err(|_| "HkdfPrf: compute of hkdf failed")?,
+            HkdfPrfVariant::Sha512(prk) => prk
+                .expand_multi_info(info, &mut okm)
+                .map_err(|_| "HkdfPrf: compute of hkdf failed")?,
+        }
+        Ok(okm)
+    }
 }
 
 /// Validate parameters of HKDF constructor.
 pub fn validate_hkdf_prf_params(
     hash: HashType,
     key_size: usize,
-    _salt: &[u8],
+    salt: &[u8],
 ) -> Result<(), TinkError> {
     // validate key size
     if key_size < MIN_HKDF_KEY_SIZE_IN_BYTES {
         Err("key too short".into())
+    } else if salt.len() > MAX_HKDF_SALT_SIZE_IN_BYTES {
+        Err("salt too long".into())
     } else if tink_core::subtle::get_hash_func(hash).is_none() {
         Err("invalid hash function".into())
     } else if hash != HashType::Sha256 && hash != HashType::Sha512 {
@@ -74,20 +111,34 @@ pub fn validate_hkdf_prf_params(
     }
 }
 
+/// In-progress incremental HKDF-PRF computation.
+///
+/// The PRF input is used as the HKDF info parameter, which HKDF-Expand processes once per block
+/// of output, so the input is buffered until the output length is known.
+struct HkdfPrfContext {
+    prf: HkdfPrf,
+    input: Vec<u8>,
+}
+
+impl PrfContext for HkdfPrfContext {
+    fn update(&mut self, data: &[u8]) {
+        self.input.extend_from_slice(data);
+    }
+
+    fn finalize(self: Box<Self>, output_length: usize) -> Result<Vec<u8>, TinkError> {
+        self.prf.expand(&[&self.input], output_length)
+    }
+}
+
 impl tink_core::Prf for HkdfPrf {
     fn compute_prf(&self, data: &[u8], out_len: usize) -> Result<Vec<u8>, TinkError> {
-        let mut okm = vec![0; out_len];
-        match &self.prk {
-            HkdfPrfVariant::Sha1(prk) => prk
-                .expand(data, &mut okm)
-                .map_err(|_| "HkdfPrf: compute of hkdf failed")?,
-            HkdfPrfVariant::Sha256(prk) => prk
-                .expand(data, &mut okm)
-                .map_err(|_| "HkdfPrf: compute of hkdf failed")?,
-            HkdfPrfVariant::Sha512(prk) => prk
-                .expand(data, &mut okm)
-                .map_err(|_| "HkdfPrf: compute of hkdf failed")?,
-        }
-        Ok(okm)
+        self.expand(&[data], out_len)
+    }
+
+    fn start_incremental(&self) -> Result<Box<dyn PrfContext>, TinkError> {
+        Ok(Box::new(HkdfPrfContext {
+            prf: self.clone(),
+            input: Vec::new(),
+        }))
     }
 }
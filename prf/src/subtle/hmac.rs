@@ -17,12 +17,8 @@
 //! Provides an implementation of PRF using HMAC.
 
 use ::hmac::{Hmac, Mac};
-use std::{
-    cmp::min,
-    ops::DerefMut,
-    sync::{Arc, Mutex},
-};
-use tink_core::TinkError;
+use std::cmp::min;
+use tink_core::{PrfContext, TinkError};
 use tink_proto::HashType;
 
 const MIN_HMAC_KEY_SIZE_IN_BYTES: usize = 16;
@@ -30,10 +26,11 @@ const MIN_HMAC_KEY_SIZE_IN_BYTES: usize = 16;
 /// `HmacPrf` is a type that can be used to compute several HMACs with the same key material.
 #[derive(Clone)]
 pub struct HmacPrf {
-    mac: Arc<Mutex<HmacPrfVariant>>,
+    mac: HmacPrfVariant,
     mac_size: usize,
 }
 
+#[derive(Clone)]
 enum HmacPrfVariant {
     Sha1(Hmac<sha1::Sha1>),
     Sha224(Hmac<sha2::Sha224>),
@@ -75,10 +72,55 @@ impl HmacPrf {
             HmacPrfVariant::Sha512(_) => 64,
         };
 
-        Ok(HmacPrf {
-            mac: Arc::new(Mutex::new(mac)),
-            mac_size,
-        })
+        Ok(HmacPrf { mac, mac_size })
+    }
+
+    /// Start an incremental HMAC computation, for data that is supplied in pieces via
+    /// [`HmacPrfContext::update`].
+    pub fn start(&self) -> HmacPrfContext {
+        HmacPrfContext {
+            mac: self.mac.clone(),
+            mac_size: self.mac_size,
+        }
+    }
+}
+
+/// In-progress incremental HMAC computation, created by [`HmacPrf::start`].
+#[derive(Clone)]
+pub struct HmacPrfContext {
+    mac: HmacPrfVariant,
+    mac_size: usize,
+}
+
+impl HmacPrfContext {
+    /// Feed more data into the HMAC computation.
+    pub fn update(&mut self, data: &[u8]) {
+        match &mut self.mac {
+            HmacPrfVariant::Sha1(mac) => mac.update(data),
+            HmacPrfVariant::Sha224(mac) => mac.update(data),
+            HmacPrfVariant::Sha256(mac) => mac.update(data),
+            HmacPrfVariant::Sha384(mac) => mac.update(data),
+            HmacPrfVariant::Sha512(mac) => mac.update(data),
+        }
+    }
+
+    /// Complete the HMAC computation, returning the first `output_length` bytes of the result.
+    pub fn finalize(self, output_length: usize) -> Result<Vec<u8>, TinkError> {
+        if output_length > self.mac_size {
+            return Err(format!(
+                "HmacPrf: output_length must be between 0 and {}",
+                self.mac_size
+            )
+            .into());
+        }
+        let result = match self.mac {
+            HmacPrfVariant::Sha1(mac) => mac.finalize().into_bytes().to_vec(),
+            HmacPrfVariant::Sha224(mac) => mac.finalize().into_bytes().to_vec(),
+            HmacPrfVariant::Sha256(mac) => mac.finalize().into_bytes().to_vec(),
+            HmacPrfVariant::Sha384(mac) => mac.finalize().into_bytes().to_vec(),
+            HmacPrfVariant::Sha512(mac) => mac.finalize().into_bytes().to_vec(),
+        };
+        Ok(result[..min(result.len(), output_length)].to_vec())
     }
 }
 
@@ -94,48 +136,24 @@ pub fn validate_hmac_prf_params(hash: HashType, key_size: usize) -> Result<(), T
     }
 }
 
+impl PrfContext for HmacPrfContext {
+    fn update(&mut self, data: &[u8]) {
+        HmacPrfContext::update(self, data)
+    }
+
+    fn finalize(self: Box<Self>, output_length: usize) -> Result<Vec<u8>, TinkError> {
+        HmacPrfContext::finalize(*self, output_length)
+    }
+}
+
 impl tink_core::Prf for HmacPrf {
     fn compute_prf(&self, data: &[u8], output_length: usize) -> Result<Vec<u8>, TinkError> {
-        if output_length > self.mac_size {
-            return Err(format!(
-                "HmacPrf: output_length must be between 0 and {}",
-                self.mac_size
-            )
-            .into());
-        }
-        Ok(
-            match self
-                .mac
-                .lock()
-                .expect("internal lock corrupted") // safe: lock
-                .deref_mut()
-            {
-                HmacPrfVariant::Sha1(mac) => {
-                    mac.update(data);
-                    let result = mac.finalize_reset().into_bytes();
-                    result[..min(result.len(), output_length)].to_vec()
-                }
-                HmacPrfVariant::Sha224(mac) => {
-                    mac.update(data);
-                    let result = mac.finalize_reset().into_bytes();
-                    result[..min(result.len(), output_length)].to_vec()
-                }
-                HmacPrfVariant::Sha256(mac) => {
-                    mac.update(data);
-                    let result = mac.finalize_reset().into_bytes();
-                    result[..min(result.len(), output_length)].to_vec()
-                }
-                HmacPrfVariant::Sha384(mac) => {
-                    mac.update(data);
-                    let result = mac.finalize_reset().into_bytes();
-                    result[..min(result.len(), output_length)].to_vec()
-                }
-                HmacPrfVariant::Sha512(mac) => {
-                    mac.update(data);
-                    let result = mac.finalize_reset().into_bytes();
-                    result[..min(result.len(), output_length)].to_vec()
-                }
-            },
-        )
+        let mut ctx = self.start();
+        ctx.update(data);
+        ctx.finalize(output_length)
+    }
+
+    fn start_incremental(&self) -> Result<Box<dyn PrfContext>, TinkError> {
+        Ok(Box::new(self.start()))
     }
 }
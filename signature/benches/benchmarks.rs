@@ -62,6 +62,17 @@ fn bench_ecdsa_p256_verify(b: &mut Bencher) {
     b.iter(|| v.verify(&sig, MSG).unwrap());
 }
 
+/// Comparison point for `bench_ecdsa_p256_verify`, showing the cost of parsing the public key
+/// for every verification if the `Verifier` were not reused.
+#[bench]
+fn bench_ecdsa_p256_new_verifier_verify(b: &mut Bencher) {
+    tink_signature::init();
+    let kh = tink_core::keyset::Handle::new(&tink_signature::ecdsa_p256_key_template()).unwrap();
+    let sig = tink_signature::sign(&kh, MSG).unwrap();
+    let pubkh = kh.public().unwrap();
+    b.iter(|| tink_signature::verify(&pubkh, &sig, MSG).unwrap());
+}
+
 #[bench]
 fn bench_ecdsa_p256_verify_fail(b: &mut Bencher) {
     let (v, sig) = setup_failure(tink_signature::ecdsa_p256_key_template());
@@ -80,6 +91,16 @@ fn bench_ed25519_verify(b: &mut Bencher) {
     b.iter(|| v.verify(&sig, MSG).unwrap());
 }
 
+/// Comparison point for `bench_ed25519_verify`, as for `bench_ecdsa_p256_new_verifier_verify`.
+#[bench]
+fn bench_ed25519_new_verifier_verify(b: &mut Bencher) {
+    tink_signature::init();
+    let kh = tink_core::keyset::Handle::new(&tink_signature::ed25519_key_template()).unwrap();
+    let sig = tink_signature::sign(&kh, MSG).unwrap();
+    let pubkh = kh.public().unwrap();
+    b.iter(|| tink_signature::verify(&pubkh, &sig, MSG).unwrap());
+}
+
 #[bench]
 fn bench_ed25519_verify_fail(b: &mut Bencher) {
     let (v, sig) = setup_failure(tink_signature::ed25519_key_template());
@@ -158,6 +158,8 @@ fn validate_key(key: &tink_proto::EcdsaPrivateKey) -> Result<tink_proto::EcdsaPa
     crate::subtle::EcdsaVerifier::new(hash, curve, encoding, &pub_key.x, &pub_key.y)
         .map_err(|e| wrap_err("EcdsaVerifierKeyManager: invalid key", e))?;
     crate::subtle::validate_ecdsa_params(hash, curve, encoding)?;
+    tink_core::subtle::ec_util::validate_private_scalar(curve, &key.key_value)
+        .map_err(|e| wrap_err("EcdsaSignerKeyManager: invalid private key", e))?;
     Ok(params)
 }
 
@@ -34,6 +34,12 @@ impl tink_core::registry::KeyManager for EcdsaVerifierKeyManager {
         if serialized_key.is_empty() {
             return Err("EcdsaVerifierKeyManager: invalid key".into());
         }
+        if looks_like_private_key(serialized_key) {
+            return Err(
+                "EcdsaVerifierKeyManager: invalid key: got an EcdsaPrivateKey, expected an EcdsaPublicKey"
+                    .into(),
+            );
+        }
         let key = tink_proto::EcdsaPublicKey::decode(serialized_key)
             .map_err(|e| wrap_err("EcdsaVerifierKeyManager: invalid key", e))?;
         let params =
@@ -73,3 +79,14 @@ pub(crate) fn validate_ecdsa_public_key(
     crate::subtle::validate_ecdsa_params(hash, curve, encoding)?;
     Ok(params.clone())
 }
+
+/// Indicate whether the given serialized key is an [`EcdsaPrivateKey`](tink_proto::EcdsaPrivateKey)
+/// rather than an [`EcdsaPublicKey`](tink_proto::EcdsaPublicKey).  A serialized public key does not
+/// decode as a private key with both an embedded public key and private key material, because the
+/// field that holds the embedded public key in the latter holds the parameters in the former.
+fn looks_like_private_key(serialized_key: &[u8]) -> bool {
+    match tink_proto::EcdsaPrivateKey::decode(serialized_key) {
+        Ok(key) => key.public_key.is_some() && !key.key_value.is_empty(),
+        Err(_) => false,
+    }
+}
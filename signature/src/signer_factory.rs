@@ -24,6 +24,14 @@ pub fn new_signer(h: &tink_core::keyset::Handle) -> Result<Box<dyn tink_core::Si
     new_signer_with_key_manager(h, None)
 }
 
+/// Sign `data` once with the primary key of the given keyset handle.
+///
+/// This is a convenience wrapper around [`new_signer`] for callers that only need a single
+/// signature; callers that sign repeatedly should keep the [`tink_core::Signer`] instead.
+pub fn sign(h: &tink_core::keyset::Handle, data: &[u8]) -> Result<Vec<u8>, TinkError> {
+    new_signer(h)?.sign(data)
+}
+
 /// Return a [`tink_core::Signer`] primitive from the given keyset handle and custom key manager.
 fn new_signer_with_key_manager(
     h: &tink_core::keyset::Handle,
@@ -37,6 +45,26 @@ fn new_signer_with_key_manager(
     Ok(Box::new(ret))
 }
 
+/// [`PrimitiveWrapper`](tink_core::registry::PrimitiveWrapper) that combines signing
+/// primitives, registered by [`init`](crate::init).
+pub struct SignerWrapper;
+
+impl tink_core::registry::PrimitiveWrapper for SignerWrapper {
+    fn primitive_name(&self) -> &'static str {
+        "signer"
+    }
+
+    fn wrap(
+        &self,
+        ps: tink_core::primitiveset::PrimitiveSet,
+        _keyset_info: &tink_proto::KeysetInfo,
+    ) -> Result<tink_core::Primitive, TinkError> {
+        Ok(tink_core::Primitive::Signer(Box::new(WrappedSigner::new(
+            ps,
+        )?)))
+    }
+}
+
 /// A [`tink_core::Signer`] implementation that uses the underlying primitive set for signing.
 #[derive(Clone)]
 struct WrappedSigner {
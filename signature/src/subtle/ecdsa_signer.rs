@@ -16,8 +16,7 @@
 
 use generic_array::typenum::Unsigned;
 use p256::elliptic_curve;
-use signature::RandomizedSigner;
-use std::convert::TryInto;
+use signature::{RandomizedSigner, Signer};
 use tink_core::{utils::wrap_err, TinkError};
 use tink_proto::{EcdsaSignatureEncoding, EllipticCurveType, HashType};
 
@@ -45,6 +44,7 @@ impl Clone for EcdsaPrivateKey {
 pub struct EcdsaSigner {
     private_key: EcdsaPrivateKey,
     encoding: super::SignatureEncoding,
+    deterministic: bool,
 }
 
 impl EcdsaSigner {
@@ -63,12 +63,8 @@ impl EcdsaSigner {
                     return Err("EcdsaSigner: invalid private key len".into());
                 }
                 EcdsaPrivateKey::NistP256(
-                    p256::ecdsa::SigningKey::from_bytes(
-                        key_value
-                            .try_into()
-                            .map_err(|e| wrap_err("EcdsaSigner: invalid private key", e))?,
-                    )
-                    .map_err(|e| wrap_err("EcdsaSigner: invalid private key", e))?,
+                    p256::ecdsa::SigningKey::from_bytes(key_value.into())
+                        .map_err(|e| wrap_err("EcdsaSigner: invalid private key", e))?,
                 )
             }
             _ => return Err(format!("EcdsaSigner: unsupported curve {curve:?}").into()),
@@ -88,8 +84,20 @@ impl EcdsaSigner {
         Ok(EcdsaSigner {
             private_key,
             encoding,
+            deterministic: false,
         })
     }
+
+    /// Select whether signing uses fully deterministic nonces as per RFC 6979, so that signing
+    /// the same data always produces the same signature.
+    ///
+    /// By default, the RFC 6979 nonce derivation is additionally seeded with fresh randomness,
+    /// which guards against fault attacks while still not relying on the quality of the random
+    /// number generator for security.
+    pub fn with_deterministic_nonces(mut self, deterministic: bool) -> Self {
+        self.deterministic = deterministic;
+        self
+    }
 }
 
 impl tink_core::Signer for EcdsaSigner {
@@ -97,8 +105,11 @@ impl tink_core::Signer for EcdsaSigner {
         let mut csprng = signature::rand_core::OsRng {};
         match &self.private_key {
             EcdsaPrivateKey::NistP256(secret_key) => {
-                let signature: ecdsa::Signature<p256::NistP256> =
-                    secret_key.sign_with_rng(&mut csprng, data);
+                let signature: ecdsa::Signature<p256::NistP256> = if self.deterministic {
+                    secret_key.sign(data)
+                } else {
+                    secret_key.sign_with_rng(&mut csprng, data)
+                };
                 match self.encoding {
                     super::SignatureEncoding::Der => Ok(signature.to_der().as_bytes().to_vec()),
                     super::SignatureEncoding::IeeeP1363 => Ok(signature.to_bytes().to_vec()),
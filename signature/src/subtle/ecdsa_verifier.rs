@@ -18,11 +18,10 @@ use generic_array::typenum::Unsigned;
 use p256::{
     ecdsa::{signature::Verifier, Signature},
     elliptic_curve,
-    elliptic_curve::sec1::EncodedPoint,
 };
 use std::convert::TryFrom;
-use tink_core::{utils::wrap_err, TinkError};
-use tink_proto::{EcdsaSignatureEncoding, EllipticCurveType, HashType};
+use tink_core::{subtle::ec_util, utils::wrap_err, TinkError};
+use tink_proto::{EcPointFormat, EcdsaSignatureEncoding, EllipticCurveType, HashType};
 
 /// An ECDSA public key.
 #[derive(Clone)]
@@ -49,12 +48,9 @@ impl EcdsaVerifier {
     ) -> Result<Self, TinkError> {
         let public_key = match curve {
             EllipticCurveType::NistP256 => {
-                let x = element_from_padded_slice::<p256::NistP256>(x)?;
-                let y = element_from_padded_slice::<p256::NistP256>(y)?;
-                let pt = EncodedPoint::<p256::NistP256>::from_affine_coordinates(
-                    &x, &y, /* compress= */ false,
-                );
-                let verify_key = p256::ecdsa::VerifyingKey::from_encoded_point(&pt)
+                let pt = ec_util::point_encode(curve, EcPointFormat::Uncompressed, x, y)
+                    .map_err(|e| wrap_err("EcdsaVerifier", e))?;
+                let verify_key = p256::ecdsa::VerifyingKey::from_sec1_bytes(&pt)
                     .map_err(|e| wrap_err("EcdsaVerifier: invalid point", e))?;
                 EcdsaPublicKey::NistP256(verify_key)
             }
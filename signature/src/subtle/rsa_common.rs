@@ -0,0 +1,112 @@
+// Copyright 2020 The Tink-Rust Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::One;
+use tink_core::TinkError;
+
+/// Components of an RSA private key, each an unsigned big integer in big-endian representation.
+pub struct RsaPrivateKeyComponents<'a> {
+    pub n: &'a [u8],
+    pub e: &'a [u8],
+    pub d: &'a [u8],
+    pub p: &'a [u8],
+    pub q: &'a [u8],
+    pub dp: &'a [u8],
+    pub dq: &'a [u8],
+    pub crt: &'a [u8],
+}
+
+/// Validate that the CRT components of an RSA private key are consistent with its modulus
+/// and exponents, so that a corrupted or tampered key is rejected before use.  Checks that:
+///  - `p * q == n`
+///  - `e * d ≡ 1 mod lcm(p - 1, q - 1)`
+///  - `dp == d mod (p - 1)` and `dq == d mod (q - 1)`
+///  - `crt * q ≡ 1 mod p`, with `crt < p`.
+pub fn validate_rsa_private_key(key: &RsaPrivateKeyComponents) -> Result<(), TinkError> {
+    let one = BigUint::one();
+    let n = BigUint::from_bytes_be(key.n);
+    let e = BigUint::from_bytes_be(key.e);
+    let d = BigUint::from_bytes_be(key.d);
+    let p = BigUint::from_bytes_be(key.p);
+    let q = BigUint::from_bytes_be(key.q);
+    let dp = BigUint::from_bytes_be(key.dp);
+    let dq = BigUint::from_bytes_be(key.dq);
+    let crt = BigUint::from_bytes_be(key.crt);
+
+    if p <= one || q <= one {
+        return Err("rsa: invalid prime factor".into());
+    }
+    if e <= one || d <= one {
+        return Err("rsa: invalid exponent".into());
+    }
+    if &p * &q != n {
+        return Err("rsa: prime factors inconsistent with modulus".into());
+    }
+    let p1 = &p - &one;
+    let q1 = &q - &one;
+    if (&e * &d) % p1.lcm(&q1) != one {
+        return Err("rsa: private exponent inconsistent with public exponent".into());
+    }
+    if &d % &p1 != dp || &d % &q1 != dq {
+        return Err("rsa: CRT exponent inconsistent with private exponent".into());
+    }
+    if crt >= p || (&crt * &q) % &p != one {
+        return Err("rsa: CRT coefficient inconsistent with prime factors".into());
+    }
+    Ok(())
+}
+
+/// Validate the CRT components of an [`RsaSsaPkcs1PrivateKey`](tink_proto::RsaSsaPkcs1PrivateKey).
+pub fn validate_rsa_ssa_pkcs1_private_key(
+    key: &tink_proto::RsaSsaPkcs1PrivateKey,
+) -> Result<(), TinkError> {
+    let public_key = key
+        .public_key
+        .as_ref()
+        .ok_or_else(|| TinkError::new("rsa: no public key"))?;
+    validate_rsa_private_key(&RsaPrivateKeyComponents {
+        n: &public_key.n,
+        e: &public_key.e,
+        d: &key.d,
+        p: &key.p,
+        q: &key.q,
+        dp: &key.dp,
+        dq: &key.dq,
+        crt: &key.crt,
+    })
+}
+
+/// Validate the CRT components of an [`RsaSsaPssPrivateKey`](tink_proto::RsaSsaPssPrivateKey).
+pub fn validate_rsa_ssa_pss_private_key(
+    key: &tink_proto::RsaSsaPssPrivateKey,
+) -> Result<(), TinkError> {
+    let public_key = key
+        .public_key
+        .as_ref()
+        .ok_or_else(|| TinkError::new("rsa: no public key"))?;
+    validate_rsa_private_key(&RsaPrivateKeyComponents {
+        n: &public_key.n,
+        e: &public_key.e,
+        d: &key.d,
+        p: &key.p,
+        q: &key.q,
+        dp: &key.dp,
+        dq: &key.dq,
+        crt: &key.crt,
+    })
+}
@@ -26,6 +26,19 @@ pub fn new_verifier(
     new_verifier_with_key_manager(h, None)
 }
 
+/// Verify `signature` over `data` once, using the keys of the given keyset handle.
+///
+/// This is a convenience wrapper around [`new_verifier`] for callers that only need a single
+/// verification; callers that verify repeatedly should keep the [`tink_core::Verifier`]
+/// instead.
+pub fn verify(
+    h: &tink_core::keyset::Handle,
+    signature: &[u8],
+    data: &[u8],
+) -> Result<(), TinkError> {
+    new_verifier(h)?.verify(signature, data)
+}
+
 /// Return a [`tink_core::Verifier`] primitive from the given keyset handle and custom key manager.
 fn new_verifier_with_key_manager(
     h: &tink_core::keyset::Handle,
@@ -39,6 +52,26 @@ fn new_verifier_with_key_manager(
     Ok(Box::new(ret))
 }
 
+/// [`PrimitiveWrapper`](tink_core::registry::PrimitiveWrapper) that combines verifying
+/// primitives, registered by [`init`](crate::init).
+pub struct VerifierWrapper;
+
+impl tink_core::registry::PrimitiveWrapper for VerifierWrapper {
+    fn primitive_name(&self) -> &'static str {
+        "verifier"
+    }
+
+    fn wrap(
+        &self,
+        ps: tink_core::primitiveset::PrimitiveSet,
+        _keyset_info: &tink_proto::KeysetInfo,
+    ) -> Result<tink_core::Primitive, TinkError> {
+        Ok(tink_core::Primitive::Verifier(Box::new(
+            WrappedVerifier::new(ps)?,
+        )))
+    }
+}
+
 /// A [`tink_core::Verifier`] implementation that uses the underlying primitive set for verifying.
 #[derive(Clone)]
 struct WrappedVerifier {
@@ -72,14 +105,15 @@ impl WrappedVerifier {
 impl tink_core::Verifier for WrappedVerifier {
     fn verify(&self, signature: &[u8], data: &[u8]) -> Result<(), TinkError> {
         let prefix_size = tink_core::cryptofmt::NON_RAW_PREFIX_SIZE;
-        if signature.len() < prefix_size {
-            return Err("verifier::factory: invalid signature".into());
-        }
 
-        // try non-raw keys
-        let prefix = &signature[..prefix_size];
-        let signature_no_prefix = &signature[prefix_size..];
-        if let Some(entries) = self.ps.entries_for_prefix(prefix) {
+        // try non-raw keys, if the signature is long enough to carry a prefix
+        let entries = if signature.len() >= prefix_size {
+            self.ps.entries_for_prefix(&signature[..prefix_size])
+        } else {
+            None
+        };
+        if let Some(entries) = entries {
+            let signature_no_prefix = &signature[prefix_size..];
             for entry in entries {
                 let result = if entry.prefix_type == tink_proto::OutputPrefixType::Legacy {
                     let mut signed_data_copy = Vec::with_capacity(data.len() + 1);
@@ -97,7 +131,8 @@ impl tink_core::Verifier for WrappedVerifier {
             }
         }
 
-        // try raw keys
+        // try raw keys over the full signature, since a RAW signature carries no key ID and may
+        // happen to start with bytes that match another key's prefix
         if let Some(entries) = self.ps.raw_entries() {
             for entry in entries {
                 if entry.primitive.verify(signature, data).is_ok() {
@@ -117,9 +117,9 @@ fn validate_key_format(
 fn validate_params(params: &tink_proto::AesGcmHkdfStreamingParams) -> Result<HashType, TinkError> {
     crate::subtle::validate_aes_key_size(params.derived_key_size as usize)?;
     let hkdf_hash = match HashType::from_i32(params.hkdf_hash_type) {
-        Some(HashType::UnknownHash) => return Err("unknown HKDF hash type".into()),
-        Some(h) => h,
-        None => return Err("unknown HKDF hash type".into()),
+        Some(h @ HashType::Sha256) | Some(h @ HashType::Sha512) => h,
+        Some(HashType::UnknownHash) | None => return Err("unknown HKDF hash type".into()),
+        Some(h) => return Err(format!("unsupported HKDF hash type {h:?}").into()),
     };
     let min_segment_size = (params.derived_key_size as usize)
         + subtle::AES_GCM_HKDF_NONCE_PREFIX_SIZE_IN_BYTES
@@ -0,0 +1,356 @@
+// Copyright 2020 The Tink-Rust Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! Asynchronous adapters for streaming AEAD, based on `tokio`'s I/O traits.
+
+use std::{
+    cell::RefCell,
+    io,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll},
+};
+use tink_core::{EncryptingWrite, StreamingAead, TinkError};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+/// Size of the chunks read from the underlying [`AsyncRead`].
+const READ_CHUNK_SIZE: usize = 16 * 1024;
+
+/// Return a wrapper around an underlying [`AsyncWrite`], such that data written via the wrapper
+/// is encrypted by `a` using `aad` as associated authenticated data.  The ciphertext has the same
+/// segment framing as that written by [`StreamingAead::new_encrypting_writer`].
+///
+/// The stream must be finished by shutting it down (e.g. with `AsyncWriteExt::shutdown`), which
+/// writes the final segment.
+pub fn new_async_encrypting_writer<W: AsyncWrite + Unpin>(
+    a: &dyn StreamingAead,
+    w: W,
+    aad: &[u8],
+) -> Result<AsyncEncryptingWriter<W>, TinkError> {
+    let sink = SharedBuf::default();
+    let writer = a.new_encrypting_writer(Box::new(sink.clone()), aad)?;
+    Ok(AsyncEncryptingWriter {
+        w,
+        writer: Some(writer),
+        sink,
+        pending: Vec::new(),
+        pending_pos: 0,
+    })
+}
+
+/// Return a wrapper around an underlying [`AsyncRead`], such that data read via the wrapper is
+/// the decryption by `a` of the ciphertext in `r`, using `aad` as associated authenticated data.
+///
+/// Before each decryption step, ciphertext is buffered up to the bound given by
+/// [`StreamingAead::max_read_ahead`], so at most about a segment of ciphertext is held in memory.
+/// Fails if `a` gives no such bound.
+pub fn new_async_decrypting_reader<R: AsyncRead + Unpin>(
+    a: &dyn StreamingAead,
+    r: R,
+    aad: &[u8],
+) -> Result<AsyncDecryptingReader<R>, TinkError> {
+    let read_ahead = a.max_read_ahead().ok_or_else(|| {
+        TinkError::new("new_async_decrypting_reader: no bound on ciphertext read ahead")
+    })?;
+    Ok(AsyncDecryptingReader {
+        r,
+        primitive: a.box_clone(),
+        aad: aad.to_vec(),
+        read_ahead,
+        want: read_ahead,
+        source: SharedSource::default(),
+        reader: None,
+    })
+}
+
+/// Encrypting wrapper around an [`AsyncWrite`], created by [`new_async_encrypting_writer`].
+///
+/// Like the primitives it is built from, this is neither `Send` nor `Sync`.
+pub struct AsyncEncryptingWriter<W> {
+    w: W,
+    /// Synchronous encrypting writer, which writes complete segments into `sink`.  Set to `None`
+    /// once the stream has been closed.
+    writer: Option<Box<dyn EncryptingWrite>>,
+    sink: SharedBuf,
+    /// Ciphertext that has been produced but not yet written to `w`.
+    pending: Vec<u8>,
+    pending_pos: usize,
+}
+
+impl<W: AsyncWrite + Unpin> AsyncEncryptingWriter<W> {
+    /// Return the underlying writer.  Any ciphertext that has not yet been written to it (for
+    /// example, if the stream has not been shut down) is discarded.
+    pub fn into_inner(self) -> W {
+        self.w
+    }
+
+    /// Move any newly produced ciphertext into the pending buffer.
+    fn collect(&mut self) {
+        let mut sink = self.sink.0.borrow_mut();
+        if sink.is_empty() {
+            return;
+        }
+        if self.pending_pos == self.pending.len() {
+            self.pending.clear();
+            self.pending_pos = 0;
+            std::mem::swap(&mut self.pending, &mut sink);
+        } else {
+            self.pending.append(&mut sink);
+        }
+    }
+
+    /// Write pending ciphertext to the underlying writer.
+    fn poll_drain(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while self.pending_pos < self.pending.len() {
+            let n = match Pin::new(&mut self.w).poll_write(cx, &self.pending[self.pending_pos..]) {
+                Poll::Ready(Ok(n)) => n,
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            };
+            if n == 0 {
+                return Poll::Ready(Err(io::ErrorKind::WriteZero.into()));
+            }
+            self.pending_pos += n;
+        }
+        self.pending.clear();
+        self.pending_pos = 0;
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWrite for AsyncEncryptingWriter<W> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        // Only accept more plaintext once earlier ciphertext has been written out, so that the
+        // amount of buffered ciphertext stays bounded.
+        match this.poll_drain(cx) {
+            Poll::Ready(Ok(())) => {}
+            Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+            Poll::Pending => return Poll::Pending,
+        }
+        let writer = match &mut this.writer {
+            Some(w) => w,
+            None => {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "write on closed writer",
+                )))
+            }
+        };
+        writer.write_all(buf)?;
+        this.collect();
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    /// Flush any complete segments to the underlying writer.  As for the synchronous writer,
+    /// plaintext for an incomplete segment remains buffered until shutdown.
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        match this.poll_drain(cx) {
+            Poll::Ready(Ok(())) => Pin::new(&mut this.w).poll_flush(cx),
+            other => other,
+        }
+    }
+
+    /// Write the final segment, then shut down the underlying writer.
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if let Some(mut writer) = this.writer.take() {
+            writer
+                .close()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, format!("{e:?}")))?;
+            drop(writer);
+            this.collect();
+        }
+        match this.poll_drain(cx) {
+            Poll::Ready(Ok(())) => Pin::new(&mut this.w).poll_shutdown(cx),
+            other => other,
+        }
+    }
+}
+
+/// Decrypting wrapper around an [`AsyncRead`], created by [`new_async_decrypting_reader`].
+///
+/// Like the primitives it is built from, this is neither `Send` nor `Sync`.
+pub struct AsyncDecryptingReader<R> {
+    r: R,
+    primitive: Box<dyn StreamingAead>,
+    aad: Vec<u8>,
+    read_ahead: usize,
+    /// Amount of ciphertext to buffer before the next decryption step.  Normally `read_ahead`,
+    /// but raised if a step runs out of ciphertext regardless.
+    want: usize,
+    /// Ciphertext read from `r` but not yet consumed by `reader`.
+    source: SharedSource,
+    /// Synchronous decrypting reader, which reads from `source`.  Created on first use, as
+    /// creation may consume the ciphertext header.
+    reader: Option<Box<dyn io::Read>>,
+}
+
+impl<R: AsyncRead + Unpin> AsyncDecryptingReader<R> {
+    /// Read from the underlying reader until `source` holds enough ciphertext for the next
+    /// decryption step, or the underlying reader is exhausted.
+    fn poll_fill(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let mut chunk = [0u8; READ_CHUNK_SIZE];
+        loop {
+            {
+                let source = self.source.0.borrow();
+                if source.eof || source.available() >= self.want {
+                    return Poll::Ready(Ok(()));
+                }
+            }
+            let mut read_buf = ReadBuf::new(&mut chunk);
+            match Pin::new(&mut self.r).poll_read(cx, &mut read_buf) {
+                Poll::Ready(Ok(())) => {}
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            }
+            let mut source = self.source.0.borrow_mut();
+            if read_buf.filled().is_empty() {
+                source.eof = true;
+            } else {
+                source.push(read_buf.filled());
+            }
+        }
+    }
+
+    /// If the last decryption step ran out of buffered ciphertext, arrange for more to be
+    /// buffered before the next attempt, and return `true`.
+    fn take_starved(&mut self) -> bool {
+        let mut source = self.source.0.borrow_mut();
+        if !source.starved {
+            return false;
+        }
+        source.starved = false;
+        self.want = source.available() + 1;
+        true
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for AsyncDecryptingReader<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if buf.remaining() == 0 {
+            return Poll::Ready(Ok(()));
+        }
+        loop {
+            match this.poll_fill(cx) {
+                Poll::Ready(Ok(())) => {}
+                other => return other,
+            }
+            if this.reader.is_none() {
+                // Creating the reader consumes the ciphertext header; if that is not all
+                // available yet, put back what was consumed and try again with more data.
+                let pos = this.source.0.borrow().pos;
+                match this
+                    .primitive
+                    .new_decrypting_reader(Box::new(this.source.clone()), &this.aad)
+                {
+                    Ok(reader) => this.reader = Some(reader),
+                    Err(_) if this.source.0.borrow().starved => {
+                        this.source.0.borrow_mut().pos = pos;
+                        this.take_starved();
+                        continue;
+                    }
+                    Err(e) => {
+                        return Poll::Ready(Err(io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            format!("{e:?}"),
+                        )))
+                    }
+                }
+            }
+            let reader = this.reader.as_mut().unwrap(); // safe: set above
+            match reader.read(buf.initialize_unfilled()) {
+                Ok(n) => {
+                    buf.advance(n);
+                    this.want = this.read_ahead;
+                    return Poll::Ready(Ok(()));
+                }
+                // The decrypting reader keeps any partial segment, so can carry on once more
+                // ciphertext is available.
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock && this.take_starved() => {}
+                Err(e) => return Poll::Ready(Err(e)),
+            }
+        }
+    }
+}
+
+/// In-memory buffer that collects the output of a synchronous encrypting writer.
+#[derive(Clone, Default)]
+struct SharedBuf(Rc<RefCell<Vec<u8>>>);
+
+impl io::Write for SharedBuf {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Buffered ciphertext that feeds a synchronous decrypting reader.
+#[derive(Default)]
+struct Source {
+    data: Vec<u8>,
+    pos: usize,
+    /// Whether the underlying reader has been exhausted.
+    eof: bool,
+    /// Whether a read found no data even though the underlying reader was not exhausted (and so
+    /// failed with [`io::ErrorKind::WouldBlock`]).
+    starved: bool,
+}
+
+impl Source {
+    fn available(&self) -> usize {
+        self.data.len() - self.pos
+    }
+
+    fn push(&mut self, data: &[u8]) {
+        if self.pos > 0 {
+            self.data.drain(..self.pos);
+            self.pos = 0;
+        }
+        self.data.extend_from_slice(data);
+    }
+}
+
+#[derive(Clone, Default)]
+struct SharedSource(Rc<RefCell<Source>>);
+
+impl io::Read for SharedSource {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut source = self.0.borrow_mut();
+        let n = std::cmp::min(buf.len(), source.available());
+        if n == 0 && !buf.is_empty() && !source.eof {
+            source.starved = true;
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
+        let pos = source.pos;
+        buf[..n].copy_from_slice(&source.data[pos..pos + n]);
+        source.pos += n;
+        Ok(n)
+    }
+}
@@ -21,8 +21,9 @@ use std::{
 
 /// Possible states for a [`DecryptReader`].
 enum State {
-    // Matching primitive not yet determined, raw ciphertext reader available.
-    Pending(Box<dyn io::Read>),
+    // Matching primitive not yet determined, raw ciphertext reader available (holding a copy of
+    // any ciphertext already read).
+    Pending(SharedCopyReader),
     // Matching primitive that correctly decrypts has been found.
     Found(Box<dyn io::Read>),
     // No matching primitive available.
@@ -46,7 +47,7 @@ impl DecryptReader {
         Self {
             wrapped,
             aad: aad.to_vec(),
-            state: State::Pending(reader),
+            state: State::Pending(SharedCopyReader::new(reader)),
         }
     }
 }
@@ -63,13 +64,12 @@ impl io::Read for DecryptReader {
             }
             State::Pending(_) => {}
         }
-        // Move the underlying raw reader out of self and into a `SharedCopyReader`
+        // Move the copying reader out of self.
         let state = std::mem::replace(&mut self.state, State::Failed);
-        let raw_reader = match state {
+        let mut copy_reader = match state {
             State::Pending(reader) => reader,
             _ => unreachable!(), // safe: checked above
         };
-        let mut copy_reader = SharedCopyReader::new(raw_reader);
 
         // find proper key to decrypt ciphertext
         if let Some(entries) = self.wrapped.ps.raw_entries() {
@@ -84,6 +84,9 @@ impl io::Read for DecryptReader {
                     Ok(r) => r,
                     Err(_) => {
                         copy_reader.rewind();
+                        if copy_reader.take_would_block() {
+                            return self.would_block(copy_reader);
+                        }
                         continue;
                     }
                 };
@@ -94,6 +97,9 @@ impl io::Read for DecryptReader {
                         // there is a copy of the data that has been read. Ensure that this
                         // already-read data is re-used next time around.
                         copy_reader.rewind();
+                        if copy_reader.take_would_block() {
+                            return self.would_block(copy_reader);
+                        }
                         continue;
                     }
                 };
@@ -112,6 +118,15 @@ impl io::Read for DecryptReader {
     }
 }
 
+impl DecryptReader {
+    /// Give up on the current key search because the underlying reader has no data available
+    /// yet, keeping the ciphertext read so far so that the search can be retried.
+    fn would_block(&mut self, copy_reader: SharedCopyReader) -> io::Result<usize> {
+        self.state = State::Pending(copy_reader);
+        Err(io::ErrorKind::WouldBlock.into())
+    }
+}
+
 /// Wrapper around an [`io::Read`] trait object that stores a copy of all of the data
 /// read from the underlying object.
 struct CopyReader {
@@ -119,6 +134,8 @@ struct CopyReader {
     copying: bool,
     read_pos: usize,
     copied_data: Vec<u8>,
+    /// Whether a read from the underlying object failed with [`io::ErrorKind::WouldBlock`].
+    would_block: bool,
 }
 
 impl CopyReader {
@@ -128,6 +145,7 @@ impl CopyReader {
             copying: true,
             read_pos: 0,
             copied_data: vec![],
+            would_block: false,
         }
     }
     fn rewind(&mut self) {
@@ -152,7 +170,15 @@ impl io::Read for CopyReader {
             Ok(n)
         } else {
             // Read from the underlying object
-            let n = self.reader.read(buf)?;
+            let n = match self.reader.read(buf) {
+                Ok(n) => n,
+                Err(e) => {
+                    if e.kind() == io::ErrorKind::WouldBlock {
+                        self.would_block = true;
+                    }
+                    return Err(e);
+                }
+            };
             if self.copying {
                 // Store a copy of the data read.
                 self.copied_data.extend_from_slice(&buf[..n]);
@@ -178,6 +204,12 @@ impl SharedCopyReader {
         let mut cr: RefMut<_> = self.0.borrow_mut();
         cr.stop_copying();
     }
+    /// Return whether a read from the underlying object has failed with
+    /// [`io::ErrorKind::WouldBlock`] since the last call.
+    fn take_would_block(&mut self) -> bool {
+        let mut cr: RefMut<_> = self.0.borrow_mut();
+        std::mem::replace(&mut cr.would_block, false)
+    }
 }
 
 impl io::Read for SharedCopyReader {
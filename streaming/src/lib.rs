@@ -19,6 +19,7 @@
 //! AEAD encryption assures the confidentiality and authenticity of the data.
 //! This primitive is CPA secure.
 
+#![cfg_attr(docsrs, feature(doc_cfg))]
 #![deny(broken_intra_doc_links)]
 
 use std::sync::Once;
@@ -28,6 +29,11 @@ mod aes_ctr_hmac_key_manager;
 pub use aes_ctr_hmac_key_manager::*;
 mod aes_gcm_hkdf_key_manager;
 pub use aes_gcm_hkdf_key_manager::*;
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+mod async_io;
+#[cfg(feature = "tokio")]
+pub use async_io::*;
 mod decrypt_reader;
 use decrypt_reader::*;
 mod streamingaead_factory;
@@ -51,38 +57,48 @@ pub fn init() {
             .expect("tink_streaming_aead::init() failed"); // safe: init
         register_key_manager(std::sync::Arc::new(AesGcmHkdfKeyManager::default()))
             .expect("tink_streaming_aead::init() failed"); // safe: init
+        tink_core::registry::register_primitive_wrapper(std::sync::Arc::new(StreamingAeadWrapper))
+            .expect("tink_streaming_aead::init() failed"); // safe: init
 
         tink_core::registry::register_template_generator(
             "AES128_CTR_HMAC_SHA256_4KB",
             aes128_ctr_hmac_sha256_segment_4kb_key_template,
-        );
+        )
+        .expect("tink_streaming_aead::init() failed"); // safe: init
         tink_core::registry::register_template_generator(
             "AES128_CTR_HMAC_SHA256_1MB",
             aes128_ctr_hmac_sha256_segment_1mb_key_template,
-        );
+        )
+        .expect("tink_streaming_aead::init() failed"); // safe: init
         tink_core::registry::register_template_generator(
             "AES256_CTR_HMAC_SHA256_4KB",
             aes256_ctr_hmac_sha256_segment_4kb_key_template,
-        );
+        )
+        .expect("tink_streaming_aead::init() failed"); // safe: init
         tink_core::registry::register_template_generator(
             "AES256_CTR_HMAC_SHA256_1MB",
             aes256_ctr_hmac_sha256_segment_1mb_key_template,
-        );
+        )
+        .expect("tink_streaming_aead::init() failed"); // safe: init
         tink_core::registry::register_template_generator(
             "AES128_GCM_HKDF_4KB",
             aes128_gcm_hkdf_4kb_key_template,
-        );
+        )
+        .expect("tink_streaming_aead::init() failed"); // safe: init
         tink_core::registry::register_template_generator(
             "AES128_GCM_HKDF_1MB",
             aes128_gcm_hkdf_1mb_key_template,
-        );
+        )
+        .expect("tink_streaming_aead::init() failed"); // safe: init
         tink_core::registry::register_template_generator(
             "AES256_GCM_HKDF_4KB",
             aes256_gcm_hkdf_4kb_key_template,
-        );
+        )
+        .expect("tink_streaming_aead::init() failed"); // safe: init
         tink_core::registry::register_template_generator(
             "AES256_GCM_HKDF_1MB",
             aes256_gcm_hkdf_1mb_key_template,
-        );
+        )
+        .expect("tink_streaming_aead::init() failed"); // safe: init
     });
 }
@@ -38,6 +38,26 @@ fn new_with_key_manager(
     Ok(Box::new(ret))
 }
 
+/// [`PrimitiveWrapper`](tink_core::registry::PrimitiveWrapper) that combines streaming AEAD
+/// primitives, registered by [`init`](crate::init).
+pub struct StreamingAeadWrapper;
+
+impl tink_core::registry::PrimitiveWrapper for StreamingAeadWrapper {
+    fn primitive_name(&self) -> &'static str {
+        "streaming_aead"
+    }
+
+    fn wrap(
+        &self,
+        ps: tink_core::primitiveset::PrimitiveSet,
+        _keyset_info: &tink_proto::KeysetInfo,
+    ) -> Result<tink_core::Primitive, TinkError> {
+        Ok(tink_core::Primitive::StreamingAead(Box::new(
+            WrappedStreamingAead::new(ps)?,
+        )))
+    }
+}
+
 /// `WrappedStreamingAead` is a  [`tink_core::StreamingAead`] implementation that uses the
 /// underlying primitive set for deterministic encryption and decryption.
 #[derive(Clone)]
@@ -92,4 +112,44 @@ impl tink_core::StreamingAead for WrappedStreamingAead {
     ) -> Result<Box<dyn std::io::Read>, TinkError> {
         Ok(Box::new(crate::DecryptReader::new(self.clone(), r, aad)))
     }
+
+    /// Candidate keys re-read the same buffered ciphertext, so the bound is the largest bound
+    /// over all of the keys.
+    fn max_read_ahead(&self) -> Option<usize> {
+        let mut max = 0;
+        if let Some(entries) = self.ps.raw_entries() {
+            for e in entries {
+                max = std::cmp::max(max, e.primitive.max_read_ahead()?);
+            }
+        }
+        Some(max)
+    }
+
+    /// New ciphertext is produced by the primary key, so its size is predicted by the primary.
+    fn ciphertext_size(&self, plaintext_len: u64) -> Option<u64> {
+        self.ps
+            .primary
+            .as_ref()?
+            .primitive
+            .ciphertext_size(plaintext_len)
+    }
+
+    fn encrypt_all(&self, plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>, TinkError> {
+        let entry = match &self.ps.primary {
+            None => return Err("streaming_aead::factory: no primary primitive".into()),
+            Some(p) => p,
+        };
+        entry.primitive.encrypt_all(plaintext, aad)
+    }
+
+    fn decrypt_all(&self, ciphertext: &[u8], aad: &[u8]) -> Result<Vec<u8>, TinkError> {
+        if let Some(entries) = self.ps.raw_entries() {
+            for e in entries {
+                if let Ok(pt) = e.primitive.decrypt_all(ciphertext, aad) {
+                    return Ok(pt);
+                }
+            }
+        }
+        Err("no matching key found for the ciphertext".into())
+    }
 }
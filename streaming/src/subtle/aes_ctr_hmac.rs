@@ -17,9 +17,12 @@
 //! AES-CTR-HMAC based implementation of the [`tink_core::StreamingAead`] trait.
 
 use super::{noncebased, AesVariant};
-use aes::cipher::{KeyIvInit, StreamCipher};
 use std::convert::TryInto;
-use tink_core::{subtle::random::get_random_bytes, utils::wrap_err, Mac, TinkError};
+use tink_core::{
+    subtle::{random::get_random_bytes, AesCtr},
+    utils::wrap_err,
+    Mac, TinkError,
+};
 use tink_proto::HashType;
 
 /// The size of the nonces used as IVs for CTR.
@@ -31,16 +34,6 @@ pub const AES_CTR_HMAC_NONCE_PREFIX_SIZE_IN_BYTES: usize = 7;
 /// The size of the HMAC key.
 pub const AES_CTR_HMAC_KEY_SIZE_IN_BYTES: usize = 32;
 
-type Aes128Ctr = ::ctr::Ctr64BE<aes::Aes128>;
-type Aes256Ctr = ::ctr::Ctr64BE<aes::Aes256>;
-
-#[allow(clippy::large_enum_variant)]
-#[derive(Clone)]
-enum AesCtrKeyVariant {
-    Aes128([u8; 16]),
-    Aes256([u8; 32]),
-}
-
 /// `AesCtrHmac` implements streaming AEAD encryption using AES-CTR and HMAC.
 ///
 /// Each ciphertext uses new AES-CTR and HMAC keys. These keys are derived using
@@ -125,39 +118,32 @@ impl AesCtrHmac {
         let key_material_size = self.aes_variant.key_size() + AES_CTR_HMAC_KEY_SIZE_IN_BYTES;
         tink_core::subtle::compute_hkdf(self.hkdf_alg, &self.main_key, salt, aad, key_material_size)
     }
-}
 
-impl tink_core::StreamingAead for AesCtrHmac {
-    /// Return a wrapper around an underlying [`std::io.Write`], such that
-    /// any write-operation via the wrapper results in AEAD-encryption of the
-    /// written data, using `aad` as associated authenticated data. The associated
-    /// data is not included in the ciphertext and has to be passed in as parameter
-    /// for decryption.
-    fn new_encrypting_writer(
+    /// Return the AES-CTR key and HMAC derived from the main key using `salt` and `aad`.
+    fn derive_keys(
         &self,
-        mut w: Box<dyn std::io::Write>,
+        salt: &[u8],
         aad: &[u8],
-    ) -> Result<Box<dyn tink_core::EncryptingWrite>, TinkError> {
+    ) -> Result<(AesCtr, tink_mac::subtle::Hmac), TinkError> {
         let key_size = self.aes_variant.key_size();
-        let salt = get_random_bytes(key_size);
-        let nonce_prefix = get_random_bytes(AES_CTR_HMAC_NONCE_PREFIX_SIZE_IN_BYTES);
+        let km = self.derive_key_material(salt, aad)?;
 
-        let km = self.derive_key_material(&salt, aad)?;
-
-        let aes_key = match self.aes_variant {
-            AesVariant::Aes128 => {
-                AesCtrKeyVariant::Aes128(
-                    km[..key_size].to_vec().try_into().unwrap(/* safe: len checked */),
-                )
-            }
-            AesVariant::Aes256 => {
-                AesCtrKeyVariant::Aes256(
-                    km[..key_size].to_vec().try_into().unwrap(/* safe: len checked */),
-                )
-            }
-        };
+        let aes_key = AesCtr::new(&km[..key_size], AES_CTR_HMAC_NONCE_SIZE_IN_BYTES)?;
         let hmac_key = &km[key_size..];
         let hmac = tink_mac::subtle::Hmac::new(self.tag_alg, hmac_key, self.tag_size_in_bytes)?;
+        Ok((aes_key, hmac))
+    }
+
+    /// Generate a fresh header (with random salt and nonce prefix) for a new ciphertext, together
+    /// with the nonce prefix and the segment encrypter to use for it.
+    fn new_segment_encrypter(
+        &self,
+        aad: &[u8],
+    ) -> Result<(Vec<u8>, Vec<u8>, AesCtrHmacSegmentEncrypter), TinkError> {
+        let salt = get_random_bytes(self.aes_variant.key_size());
+        let nonce_prefix = get_random_bytes(AES_CTR_HMAC_NONCE_PREFIX_SIZE_IN_BYTES);
+
+        let (aes_key, hmac) = self.derive_keys(&salt, aad)?;
 
         let mut header = Vec::with_capacity(self.header_length());
         header.push(
@@ -167,15 +153,49 @@ impl tink_core::StreamingAead for AesCtrHmac {
         );
         header.extend_from_slice(&salt);
         header.extend_from_slice(&nonce_prefix);
+        Ok((
+            header,
+            nonce_prefix,
+            AesCtrHmacSegmentEncrypter {
+                aes_key,
+                hmac,
+                tag_size_in_bytes: self.tag_size_in_bytes,
+            },
+        ))
+    }
+
+    /// Return the segment decrypter for a ciphertext with the given header `salt`.
+    fn new_segment_decrypter(
+        &self,
+        salt: &[u8],
+        aad: &[u8],
+    ) -> Result<AesCtrHmacSegmentDecrypter, TinkError> {
+        let (aes_key, hmac) = self.derive_keys(salt, aad)?;
+        Ok(AesCtrHmacSegmentDecrypter {
+            aes_key,
+            hmac,
+            tag_size_in_bytes: self.tag_size_in_bytes,
+        })
+    }
+}
+
+impl tink_core::StreamingAead for AesCtrHmac {
+    /// Return a wrapper around an underlying [`std::io.Write`], such that
+    /// any write-operation via the wrapper results in AEAD-encryption of the
+    /// written data, using `aad` as associated authenticated data. The associated
+    /// data is not included in the ciphertext and has to be passed in as parameter
+    /// for decryption.
+    fn new_encrypting_writer(
+        &self,
+        mut w: Box<dyn std::io::Write>,
+        aad: &[u8],
+    ) -> Result<Box<dyn tink_core::EncryptingWrite>, TinkError> {
+        let (header, nonce_prefix, segment_encrypter) = self.new_segment_encrypter(aad)?;
         w.write(&header).map_err(|e| wrap_err("write failed", e))?;
 
         let nw = noncebased::Writer::new(noncebased::WriterParams {
             w,
-            segment_encrypter: Box::new(AesCtrHmacSegmentEncrypter {
-                aes_key,
-                hmac,
-                tag_size_in_bytes: self.tag_size_in_bytes,
-            }),
+            segment_encrypter: Box::new(segment_encrypter),
             nonce_size: AES_CTR_HMAC_NONCE_SIZE_IN_BYTES,
             nonce_prefix,
             plaintext_segment_size: self.plaintext_segment_size,
@@ -208,30 +228,9 @@ impl tink_core::StreamingAead for AesCtrHmac {
         r.read_exact(&mut nonce_prefix)
             .map_err(|e| wrap_err("cannot read nonce_prefix", e))?;
 
-        let km = self.derive_key_material(&salt, aad)?;
-
-        let aes_key = match self.aes_variant {
-            AesVariant::Aes128 => {
-                AesCtrKeyVariant::Aes128(
-                    km[..key_size].to_vec().try_into().unwrap(/* safe: len checked */),
-                )
-            }
-            AesVariant::Aes256 => {
-                AesCtrKeyVariant::Aes256(
-                    km[..key_size].to_vec().try_into().unwrap(/* safe: len checked */),
-                )
-            }
-        };
-        let hmac_key = &km[self.aes_variant.key_size()..];
-        let hmac = tink_mac::subtle::Hmac::new(self.tag_alg, hmac_key, self.tag_size_in_bytes)?;
-
         let nr = noncebased::Reader::new(noncebased::ReaderParams {
             r,
-            segment_decrypter: Box::new(AesCtrHmacSegmentDecrypter {
-                aes_key,
-                hmac,
-                tag_size_in_bytes: self.tag_size_in_bytes,
-            }),
+            segment_decrypter: Box::new(self.new_segment_decrypter(&salt, aad)?),
             nonce_size: AES_CTR_HMAC_NONCE_SIZE_IN_BYTES,
             nonce_prefix,
             ciphertext_segment_size: self.ciphertext_segment_size,
@@ -240,11 +239,83 @@ impl tink_core::StreamingAead for AesCtrHmac {
 
         Ok(Box::new(nr))
     }
+
+    /// A decrypting reader reads the header, then at most a full ciphertext segment plus one
+    /// byte (to detect the final segment) per `read`.
+    fn max_read_ahead(&self) -> Option<usize> {
+        Some(self.header_length() + self.ciphertext_segment_size + 1)
+    }
+
+    /// The ciphertext size excludes the `first_segment_offset` bytes that precede the
+    /// ciphertext.
+    fn ciphertext_size(&self, plaintext_len: u64) -> Option<u64> {
+        Some(
+            self.header_length() as u64
+                + noncebased::ciphertext_size(
+                    plaintext_len,
+                    self.plaintext_segment_size,
+                    self.ciphertext_segment_size,
+                    self.first_ciphertext_segment_offset,
+                ),
+        )
+    }
+
+    fn plaintext_size(&self, ciphertext_len: u64) -> Result<u64, TinkError> {
+        let segments_len = ciphertext_len
+            .checked_sub(self.header_length() as u64)
+            .ok_or_else(|| TinkError::new("ciphertext too short"))?;
+        noncebased::plaintext_size(
+            segments_len,
+            self.plaintext_segment_size,
+            self.ciphertext_segment_size,
+            self.first_ciphertext_segment_offset,
+        )
+    }
+
+    /// Encrypt all of `plaintext` in memory, without going via a [`std::io::Write`].
+    fn encrypt_all(&self, plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>, TinkError> {
+        let (header, nonce_prefix, segment_encrypter) = self.new_segment_encrypter(aad)?;
+        let mut ciphertext = Vec::with_capacity(header.len() + plaintext.len());
+        ciphertext.extend_from_slice(&header);
+        noncebased::encrypt_all(
+            &segment_encrypter,
+            AES_CTR_HMAC_NONCE_SIZE_IN_BYTES,
+            &nonce_prefix,
+            self.plaintext_segment_size,
+            self.first_ciphertext_segment_offset,
+            plaintext,
+            &mut ciphertext,
+        )?;
+        Ok(ciphertext)
+    }
+
+    /// Decrypt all of `ciphertext` in memory, without going via a [`std::io::Read`].
+    fn decrypt_all(&self, ciphertext: &[u8], aad: &[u8]) -> Result<Vec<u8>, TinkError> {
+        let header_len = self.header_length();
+        if ciphertext.first().map(|b| *b as usize) != Some(header_len) {
+            return Err("invalid header length".into());
+        }
+        if ciphertext.len() < header_len {
+            return Err("ciphertext too short".into());
+        }
+        let key_size = self.aes_variant.key_size();
+        let salt = &ciphertext[1..1 + key_size];
+        let nonce_prefix = &ciphertext[1 + key_size..header_len];
+
+        noncebased::decrypt_all(
+            &self.new_segment_decrypter(salt, aad)?,
+            AES_CTR_HMAC_NONCE_SIZE_IN_BYTES,
+            nonce_prefix,
+            self.ciphertext_segment_size,
+            self.first_ciphertext_segment_offset,
+            &ciphertext[header_len..],
+        )
+    }
 }
 
 /// A [`noncebased::SegmentEncrypter`] based on AES-CTR-HMAC.
 struct AesCtrHmacSegmentEncrypter {
-    aes_key: AesCtrKeyVariant,
+    aes_key: AesCtr,
     hmac: tink_mac::subtle::Hmac,
     tag_size_in_bytes: usize,
 }
@@ -257,18 +328,8 @@ impl noncebased::SegmentEncrypter for AesCtrHmacSegmentEncrypter {
         let mut ciphertext = vec![0; ct_len];
 
         ciphertext[..s_len].copy_from_slice(segment);
-        match &self.aes_key {
-            AesCtrKeyVariant::Aes128(key) => {
-                let mut stream =
-                    Aes128Ctr::new_from_slices(key, nonce).unwrap(/* safe: len checked */);
-                stream.apply_keystream(&mut ciphertext[..s_len]);
-            }
-            AesCtrKeyVariant::Aes256(key) => {
-                let mut stream =
-                    Aes256Ctr::new_from_slices(key, nonce).unwrap(/* safe: len checked */);
-                stream.apply_keystream(&mut ciphertext[..s_len]);
-            }
-        }
+        self.aes_key
+            .apply_keystream(nonce, &mut ciphertext[..s_len])?;
 
         let mut mac_input = Vec::with_capacity(n_len + s_len);
         mac_input.extend_from_slice(nonce);
@@ -282,7 +343,7 @@ impl noncebased::SegmentEncrypter for AesCtrHmacSegmentEncrypter {
 
 /// A [`noncebased::SegmentDecrypter`] based on AES-CTR-HMAC.
 struct AesCtrHmacSegmentDecrypter {
-    aes_key: AesCtrKeyVariant,
+    aes_key: AesCtr,
     hmac: tink_mac::subtle::Hmac,
     tag_size_in_bytes: usize,
 }
@@ -305,18 +366,7 @@ impl noncebased::SegmentDecrypter for AesCtrHmacSegmentDecrypter {
         }
 
         let mut result = segment[..tag_start].to_vec();
-        match &self.aes_key {
-            AesCtrKeyVariant::Aes128(key) => {
-                let mut stream =
-                    Aes128Ctr::new_from_slices(key, nonce).unwrap(/* safe: len checked */);
-                stream.apply_keystream(&mut result);
-            }
-            AesCtrKeyVariant::Aes256(key) => {
-                let mut stream =
-                    Aes256Ctr::new_from_slices(key, nonce).unwrap(/* safe: len checked */);
-                stream.apply_keystream(&mut result);
-            }
-        }
+        self.aes_key.apply_keystream(nonce, &mut result)?;
 
         Ok(result)
     }
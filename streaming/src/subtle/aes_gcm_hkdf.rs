@@ -70,7 +70,8 @@ impl AesGcmHkdf {
     ///
     /// `main_key` is input keying material used to derive sub keys.  This must be
     /// longer than the size of the sub keys (`key_size_in_bytes`).
-    /// `hkdf_alg` is a MAC algorithm hash type, used for the HKDF key derivation.
+    /// `hkdf_alg` is a MAC algorithm hash type, used for the HKDF key derivation; SHA-256 and
+    /// SHA-512 are supported.
     /// `key_size_in_bytes` argument is a key size of the sub keys.
     /// `ciphertext_segment_size` argument is the size of ciphertext segments.
     /// `first_segment_offset` argument is the offset of the first ciphertext segment.
@@ -84,6 +85,9 @@ impl AesGcmHkdf {
         if main_key.len() < 16 || main_key.len() < key_size_in_bytes {
             return Err("main_key too short".into());
         }
+        if hkdf_alg != HashType::Sha256 && hkdf_alg != HashType::Sha512 {
+            return Err(format!("unsupported HKDF hash {hkdf_alg:?}").into());
+        }
         let aes_variant = super::validate_aes_key_size(key_size_in_bytes)?;
         let header_len = header_length_for(key_size_in_bytes);
         if ciphertext_segment_size
@@ -117,19 +121,13 @@ impl AesGcmHkdf {
             self.aes_variant.key_size(),
         )
     }
-}
 
-impl tink_core::StreamingAead for AesGcmHkdf {
-    /// Return a wrapper around an underlying [`std::io::Write`], such that
-    /// any write-operation via the wrapper results in AEAD-encryption of the
-    /// written data, using aad as associated authenticated data. The associated
-    /// data is not included in the ciphertext and has to be passed in as parameter
-    /// for decryption.
-    fn new_encrypting_writer(
+    /// Generate a fresh header (with random salt and nonce prefix) for a new ciphertext, together
+    /// with the nonce prefix and the segment encrypter to use for it.
+    fn new_segment_encrypter(
         &self,
-        mut w: Box<dyn std::io::Write>,
         aad: &[u8],
-    ) -> Result<Box<dyn tink_core::EncryptingWrite>, TinkError> {
+    ) -> Result<(Vec<u8>, Vec<u8>, AesGcmHkdfSegmentEncrypter), TinkError> {
         let salt = get_random_bytes(self.aes_variant.key_size());
         let nonce_prefix = get_random_bytes(AES_GCM_HKDF_NONCE_PREFIX_SIZE_IN_BYTES);
 
@@ -144,11 +142,42 @@ impl tink_core::StreamingAead for AesGcmHkdf {
         );
         header.extend_from_slice(&salt);
         header.extend_from_slice(&nonce_prefix);
+        Ok((
+            header,
+            nonce_prefix,
+            AesGcmHkdfSegmentEncrypter { cipher_key },
+        ))
+    }
+
+    /// Return the segment decrypter for a ciphertext with the given header `salt`.
+    fn new_segment_decrypter(
+        &self,
+        salt: &[u8],
+        aad: &[u8],
+    ) -> Result<AesGcmHkdfSegmentDecrypter, TinkError> {
+        let dkey = self.derive_key(salt, aad)?;
+        let cipher_key = new_cipher_key(self.aes_variant, &dkey)?;
+        Ok(AesGcmHkdfSegmentDecrypter { cipher_key })
+    }
+}
+
+impl tink_core::StreamingAead for AesGcmHkdf {
+    /// Return a wrapper around an underlying [`std::io::Write`], such that
+    /// any write-operation via the wrapper results in AEAD-encryption of the
+    /// written data, using aad as associated authenticated data. The associated
+    /// data is not included in the ciphertext and has to be passed in as parameter
+    /// for decryption.
+    fn new_encrypting_writer(
+        &self,
+        mut w: Box<dyn std::io::Write>,
+        aad: &[u8],
+    ) -> Result<Box<dyn tink_core::EncryptingWrite>, TinkError> {
+        let (header, nonce_prefix, segment_encrypter) = self.new_segment_encrypter(aad)?;
         w.write(&header).map_err(|e| wrap_err("write failed", e))?;
 
         let nw = noncebased::Writer::new(noncebased::WriterParams {
             w,
-            segment_encrypter: Box::new(AesGcmHkdfSegmentEncrypter { cipher_key }),
+            segment_encrypter: Box::new(segment_encrypter),
             nonce_size: AES_GCM_HKDF_NONCE_SIZE_IN_BYTES,
             nonce_prefix,
             plaintext_segment_size: self.plaintext_segment_size,
@@ -181,12 +210,9 @@ impl tink_core::StreamingAead for AesGcmHkdf {
         r.read_exact(&mut nonce_prefix)
             .map_err(|e| wrap_err("cannot read nonce_prefix", e))?;
 
-        let dkey = self.derive_key(&salt, aad)?;
-        let cipher_key = new_cipher_key(self.aes_variant, &dkey)?;
-
         let nr = noncebased::Reader::new(noncebased::ReaderParams {
             r,
-            segment_decrypter: Box::new(AesGcmHkdfSegmentDecrypter { cipher_key }),
+            segment_decrypter: Box::new(self.new_segment_decrypter(&salt, aad)?),
             nonce_size: AES_GCM_HKDF_NONCE_SIZE_IN_BYTES,
             nonce_prefix,
             ciphertext_segment_size: self.ciphertext_segment_size,
@@ -195,6 +221,78 @@ impl tink_core::StreamingAead for AesGcmHkdf {
 
         Ok(Box::new(nr))
     }
+
+    /// A decrypting reader reads the header, then at most a full ciphertext segment plus one
+    /// byte (to detect the final segment) per `read`.
+    fn max_read_ahead(&self) -> Option<usize> {
+        Some(self.header_length() + self.ciphertext_segment_size + 1)
+    }
+
+    /// The ciphertext size excludes the `first_segment_offset` bytes that precede the
+    /// ciphertext.
+    fn ciphertext_size(&self, plaintext_len: u64) -> Option<u64> {
+        Some(
+            self.header_length() as u64
+                + noncebased::ciphertext_size(
+                    plaintext_len,
+                    self.plaintext_segment_size,
+                    self.ciphertext_segment_size,
+                    self.first_ciphertext_segment_offset,
+                ),
+        )
+    }
+
+    fn plaintext_size(&self, ciphertext_len: u64) -> Result<u64, TinkError> {
+        let segments_len = ciphertext_len
+            .checked_sub(self.header_length() as u64)
+            .ok_or_else(|| TinkError::new("ciphertext too short"))?;
+        noncebased::plaintext_size(
+            segments_len,
+            self.plaintext_segment_size,
+            self.ciphertext_segment_size,
+            self.first_ciphertext_segment_offset,
+        )
+    }
+
+    /// Encrypt all of `plaintext` in memory, without going via a [`std::io::Write`].
+    fn encrypt_all(&self, plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>, TinkError> {
+        let (header, nonce_prefix, segment_encrypter) = self.new_segment_encrypter(aad)?;
+        let mut ciphertext = Vec::with_capacity(header.len() + plaintext.len());
+        ciphertext.extend_from_slice(&header);
+        noncebased::encrypt_all(
+            &segment_encrypter,
+            AES_GCM_HKDF_NONCE_SIZE_IN_BYTES,
+            &nonce_prefix,
+            self.plaintext_segment_size,
+            self.first_ciphertext_segment_offset,
+            plaintext,
+            &mut ciphertext,
+        )?;
+        Ok(ciphertext)
+    }
+
+    /// Decrypt all of `ciphertext` in memory, without going via a [`std::io::Read`].
+    fn decrypt_all(&self, ciphertext: &[u8], aad: &[u8]) -> Result<Vec<u8>, TinkError> {
+        let header_len = self.header_length();
+        if ciphertext.first().map(|b| *b as usize) != Some(header_len) {
+            return Err("invalid header length".into());
+        }
+        if ciphertext.len() < header_len {
+            return Err("ciphertext too short".into());
+        }
+        let key_size = self.aes_variant.key_size();
+        let salt = &ciphertext[1..1 + key_size];
+        let nonce_prefix = &ciphertext[1 + key_size..header_len];
+
+        noncebased::decrypt_all(
+            &self.new_segment_decrypter(salt, aad)?,
+            AES_GCM_HKDF_NONCE_SIZE_IN_BYTES,
+            nonce_prefix,
+            self.ciphertext_segment_size,
+            self.first_ciphertext_segment_offset,
+            &ciphertext[header_len..],
+        )
+    }
 }
 
 /// Create a new AES-GCM cipher key using the given key and the crypto library.
@@ -168,13 +168,10 @@ impl io::Write for Writer {
             // At this point there is a full segment's worth of plaintext in
             // `self.plaintext[..pt_lim]`, ready to encrypt and write out.
             if self.plaintext_pos != pt_lim {
-                return Err(io::Error::new(
-                    io::ErrorKind::Other,
-                    format!(
-                        "internal error: pos={} != pt_lim={}",
-                        self.plaintext_pos, pt_lim
-                    ),
-                ));
+                return Err(io::Error::other(format!(
+                    "internal error: pos={} != pt_lim={}",
+                    self.plaintext_pos, pt_lim
+                )));
             }
             let nonce = generate_segment_nonce(
                 self.nonce_size,
@@ -237,6 +234,95 @@ impl Drop for Writer {
     }
 }
 
+/// Encrypt all of `plaintext` in memory, appending the resulting segments to `ciphertext`.
+///
+/// The output is identical to that written by a [`Writer`] with the same parameters (and the
+/// same nonce prefix) that has all of `plaintext` written to it and is then closed.
+pub fn encrypt_all(
+    segment_encrypter: &dyn SegmentEncrypter,
+    nonce_size: usize,
+    nonce_prefix: &[u8],
+    plaintext_segment_size: usize,
+    first_ciphertext_segment_offset: usize,
+    plaintext: &[u8],
+    ciphertext: &mut Vec<u8>,
+) -> Result<(), TinkError> {
+    let mut pt_lim = plaintext_segment_size
+        .checked_sub(first_ciphertext_segment_offset)
+        .ok_or("first ciphertext segment offset bigger than plaintext segment size")?;
+    let mut segment_cnt = 0;
+    let mut pos = 0;
+    // As for `Writer`, a full segment is only known not to be the last segment once there is
+    // more plaintext following it.
+    while plaintext.len() - pos > pt_lim {
+        let nonce = generate_segment_nonce(
+            nonce_size,
+            nonce_prefix,
+            segment_cnt,
+            /* last= */ false,
+        )
+        .map_err(|e| wrap_err("internal error", e))?;
+        ciphertext.extend_from_slice(
+            &segment_encrypter.encrypt_segment(&plaintext[pos..pos + pt_lim], &nonce)?,
+        );
+        pos += pt_lim;
+        pt_lim = plaintext_segment_size;
+        segment_cnt += 1;
+    }
+    let nonce =
+        generate_segment_nonce(nonce_size, nonce_prefix, segment_cnt, /* last= */ true)
+            .map_err(|e| wrap_err("internal error", e))?;
+    ciphertext.extend_from_slice(&segment_encrypter.encrypt_segment(&plaintext[pos..], &nonce)?);
+    Ok(())
+}
+
+/// Return the total size of the segments produced by a [`Writer`] (or [`encrypt_all`]) with the
+/// given parameters for `plaintext_len` bytes of plaintext, where each full segment of
+/// `plaintext_segment_size` bytes of plaintext becomes `ciphertext_segment_size` bytes of
+/// ciphertext.
+pub fn ciphertext_size(
+    plaintext_len: u64,
+    plaintext_segment_size: usize,
+    ciphertext_segment_size: usize,
+    first_ciphertext_segment_offset: usize,
+) -> u64 {
+    let pt_segment_size = plaintext_segment_size as u64;
+    let overhead = (ciphertext_segment_size - plaintext_segment_size) as u64;
+    let first_pt_lim = pt_segment_size.saturating_sub(first_ciphertext_segment_offset as u64);
+    // As for `Writer`, a full segment is only known not to be the last segment once there is
+    // more plaintext following it.
+    let segment_cnt = if plaintext_len <= first_pt_lim {
+        1
+    } else {
+        1 + (plaintext_len - first_pt_lim).div_ceil(pt_segment_size)
+    };
+    plaintext_len + segment_cnt * overhead
+}
+
+/// Return the size of the plaintext held in segments of total size `ciphertext_len`, as the
+/// inverse of [`ciphertext_size`].
+pub fn plaintext_size(
+    ciphertext_len: u64,
+    plaintext_segment_size: usize,
+    ciphertext_segment_size: usize,
+    first_ciphertext_segment_offset: usize,
+) -> Result<u64, TinkError> {
+    let ct_segment_size = ciphertext_segment_size as u64;
+    let overhead = (ciphertext_segment_size - plaintext_segment_size) as u64;
+    let first_ct_lim = ct_segment_size.saturating_sub(first_ciphertext_segment_offset as u64);
+    let (segment_cnt, last_segment_len) = if ciphertext_len <= first_ct_lim {
+        (1, ciphertext_len)
+    } else {
+        let rest = ciphertext_len - first_ct_lim;
+        let cnt = rest.div_ceil(ct_segment_size);
+        (1 + cnt, rest - (cnt - 1) * ct_segment_size)
+    };
+    if last_segment_len < overhead {
+        return Err("ciphertext segment too short".into());
+    }
+    Ok(ciphertext_len - segment_cnt * overhead)
+}
+
 /// `SegmentDecrypter` facilitates implementing various streaming AEAD encryption modes.
 pub trait SegmentDecrypter {
     fn decrypt_segment(&self, segment: &[u8], nonce: &[u8]) -> Result<Vec<u8>, TinkError>;
@@ -263,6 +349,9 @@ pub struct Reader {
     ciphertext: Vec<u8>,
 
     ciphertext_pos: usize,
+    /// Amount of data held in `ciphertext`, which may include a partial segment left over from a
+    /// `read` that failed (e.g. with [`io::ErrorKind::WouldBlock`]).
+    ciphertext_len: usize,
 }
 
 /// `ReaderParams` contains the options for instantiating a [`Reader`] via `Reader::new()`.
@@ -327,52 +416,11 @@ impl Reader {
             //  - 0 (for first segment), or
             //  - 1 (for all subsequent segments).
             ciphertext_pos: 0,
+            ciphertext_len: 0,
         })
     }
 }
 
-/// Extension trait for [`std::io::Read`] to support `read_full()` method.
-trait ReadFullExt {
-    /// Read the exact number of bytes required to fill `buf`, if possible.
-    ///
-    /// This function reads as many bytes as necessary to completely fill the
-    /// specified buffer `buf`.
-    ///
-    /// If this function encounters an error of the kind
-    /// [`std::io::ErrorKind::Interrupted`] then the error is ignored and the
-    /// operation will continue.
-    ///
-    /// If this function encounters an "end of file" before completely filling
-    /// the buffer, it returns an `Ok(n)` value holding the number of bytes read
-    /// into `buf`.
-    ///
-    /// If any other read error is encountered then this function immediately
-    /// returns. The contents of `buf` are unspecified in this case.
-    ///
-    /// (This is similar to `Read::read_exact` except for partial read behaviour,
-    /// and also behaves like Go's `io::ReadFull`, as used in the upstream Go code.)
-    fn read_full(&mut self, buf: &mut [u8]) -> std::io::Result<usize>;
-}
-
-impl ReadFullExt for dyn std::io::Read {
-    fn read_full(&mut self, mut buf: &mut [u8]) -> std::io::Result<usize> {
-        let mut count = 0;
-        while !buf.is_empty() {
-            match self.read(buf) {
-                Ok(0) => break,
-                Ok(n) => {
-                    count += n;
-                    let tmp = buf;
-                    buf = &mut tmp[n..];
-                }
-                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
-                Err(e) => return Err(e),
-            }
-        }
-        Ok(count)
-    }
-}
-
 impl io::Read for Reader {
     /// Read decrypts data from underlying reader and passes it to `buf`.
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
@@ -384,7 +432,9 @@ impl io::Read for Reader {
             self.plaintext_pos += n;
             return Ok(n);
         }
-        // No available plaintext.
+        // No available plaintext.  Discard the consumed plaintext, in case this `read` fails
+        // part way through and is retried.
+        self.plaintext.clear();
         self.plaintext_pos = 0;
 
         // Read up to a segment's worth of ciphertext.
@@ -393,9 +443,20 @@ impl io::Read for Reader {
             // The first segment of ciphertext might be offset in the stream.
             ct_lim -= self.first_ciphertext_segment_offset;
         }
-        let n = self
-            .r
-            .read_full(&mut self.ciphertext[self.ciphertext_pos..ct_lim])?;
+        // Ciphertext read before an error is kept, so that a `read` that fails with (say)
+        // `WouldBlock` can be retried later without losing data.
+        while self.ciphertext_len < ct_lim {
+            match self
+                .r
+                .read(&mut self.ciphertext[self.ciphertext_len..ct_lim])
+            {
+                Ok(0) => break,
+                Ok(n) => self.ciphertext_len += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        let n = self.ciphertext_len - self.ciphertext_pos;
         if n == 0 {
             // No ciphertext available, so therefore no plaintext available for now.
             return Ok(0);
@@ -436,6 +497,7 @@ impl io::Read for Reader {
             self.ciphertext[0] = self.ciphertext[remainder_offset];
             self.ciphertext_pos = 1;
         }
+        self.ciphertext_len = self.ciphertext_pos;
         self.decrypted_segment_cnt += 1;
 
         // A segment's worth of plaintext is now available in `self.plaintext`;
@@ -447,6 +509,43 @@ impl io::Read for Reader {
     }
 }
 
+/// Decrypt all of the segments in `ciphertext` in memory.
+///
+/// This accepts the output of a [`Writer`] with the same parameters, except that (unlike
+/// [`Reader`]) an empty `ciphertext` with no final segment is rejected.
+pub fn decrypt_all(
+    segment_decrypter: &dyn SegmentDecrypter,
+    nonce_size: usize,
+    nonce_prefix: &[u8],
+    ciphertext_segment_size: usize,
+    first_ciphertext_segment_offset: usize,
+    ciphertext: &[u8],
+) -> Result<Vec<u8>, TinkError> {
+    if ciphertext.is_empty() {
+        return Err("ciphertext segment too short".into());
+    }
+    let mut ct_lim = ciphertext_segment_size
+        .checked_sub(first_ciphertext_segment_offset)
+        .ok_or("first ciphertext segment offset bigger than ciphertext segment size")?;
+    let mut plaintext = Vec::with_capacity(ciphertext.len());
+    let mut segment_cnt = 0;
+    let mut pos = 0;
+    loop {
+        let last = ciphertext.len() - pos <= ct_lim;
+        let end = if last { ciphertext.len() } else { pos + ct_lim };
+        let nonce = generate_segment_nonce(nonce_size, nonce_prefix, segment_cnt, last)
+            .map_err(|e| wrap_err("internal error", e))?;
+        plaintext
+            .extend_from_slice(&segment_decrypter.decrypt_segment(&ciphertext[pos..end], &nonce)?);
+        if last {
+            return Ok(plaintext);
+        }
+        pos = end;
+        ct_lim = ciphertext_segment_size;
+        segment_cnt += 1;
+    }
+}
+
 /// Return a nonce for a segment.
 ///
 /// The format of the nonce is:
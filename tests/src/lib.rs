@@ -160,6 +160,133 @@ impl tink_core::Verifier for DummyVerifier {
     }
 }
 
+/// Dummy implementation of the [`tink_core::HybridEncrypt`] trait.
+#[derive(Clone)]
+pub struct DummyHybridEncrypt {
+    aead: DummyAead,
+}
+
+impl DummyHybridEncrypt {
+    /// Create a new dummy hybrid encrypter with the specified name. The name is used to pair with
+    /// [`DummyHybridDecrypt`].
+    pub fn new(name: &str) -> DummyHybridEncrypt {
+        DummyHybridEncrypt {
+            aead: DummyAead {
+                name: format!("dummy hybrid key: {name}"),
+            },
+        }
+    }
+}
+
+impl tink_core::HybridEncrypt for DummyHybridEncrypt {
+    fn encrypt(&self, plaintext: &[u8], context_info: &[u8]) -> Result<Vec<u8>, TinkError> {
+        self.aead.encrypt(plaintext, context_info)
+    }
+}
+
+/// Dummy implementation of the [`tink_core::HybridDecrypt`] trait.
+#[derive(Clone)]
+pub struct DummyHybridDecrypt {
+    aead: DummyAead,
+}
+
+impl DummyHybridDecrypt {
+    /// Create a new dummy hybrid decrypter with the specified name. The name is used to pair with
+    /// [`DummyHybridEncrypt`].
+    pub fn new(name: &str) -> DummyHybridDecrypt {
+        DummyHybridDecrypt {
+            aead: DummyAead {
+                name: format!("dummy hybrid key: {name}"),
+            },
+        }
+    }
+}
+
+impl tink_core::HybridDecrypt for DummyHybridDecrypt {
+    fn decrypt(&self, ciphertext: &[u8], context_info: &[u8]) -> Result<Vec<u8>, TinkError> {
+        self.aead.decrypt(ciphertext, context_info)
+    }
+}
+
+/// Dummy implementation of the [`tink_core::DeterministicAead`] trait, relying on the fact that
+/// [`DummyAead`] is itself deterministic.
+#[derive(Clone, Debug, Default)]
+pub struct DummyDeterministicAead {
+    pub aead: DummyAead,
+}
+
+impl tink_core::DeterministicAead for DummyDeterministicAead {
+    fn encrypt_deterministically(
+        &self,
+        plaintext: &[u8],
+        additional_data: &[u8],
+    ) -> Result<Vec<u8>, TinkError> {
+        self.aead.encrypt(plaintext, additional_data)
+    }
+
+    fn decrypt_deterministically(
+        &self,
+        ciphertext: &[u8],
+        additional_data: &[u8],
+    ) -> Result<Vec<u8>, TinkError> {
+        self.aead.decrypt(ciphertext, additional_data)
+    }
+}
+
+/// Dummy implementation of the [`tink_core::StreamingAead`] trait, which passes data through
+/// unmodified (and ignores the associated data).
+#[derive(Clone, Debug, Default)]
+pub struct DummyStreamingAead;
+
+/// Passthrough writer for [`DummyStreamingAead`].
+struct DummyEncryptingWriter {
+    w: Option<Box<dyn std::io::Write>>,
+}
+
+impl std::io::Write for DummyEncryptingWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match &mut self.w {
+            Some(w) => w.write(buf),
+            None => Err(std::io::Error::other("write on closed writer")),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match &mut self.w {
+            Some(w) => w.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl tink_core::EncryptingWrite for DummyEncryptingWriter {
+    fn close(&mut self) -> Result<(), TinkError> {
+        if let Some(mut w) = self.w.take() {
+            w.flush()
+                .map_err(|e| wrap_err("dummy streaming aead close", e))?;
+        }
+        Ok(())
+    }
+}
+
+impl tink_core::StreamingAead for DummyStreamingAead {
+    fn new_encrypting_writer(
+        &self,
+        w: Box<dyn std::io::Write>,
+        _aad: &[u8],
+    ) -> Result<Box<dyn tink_core::EncryptingWrite>, TinkError> {
+        Ok(Box::new(DummyEncryptingWriter { w: Some(w) }))
+    }
+
+    fn new_decrypting_reader(
+        &self,
+        r: Box<dyn std::io::Read>,
+        _aad: &[u8],
+    ) -> Result<Box<dyn std::io::Read>, TinkError> {
+        Ok(r)
+    }
+}
+
 /// Dummy implementation of [`tink_core::Mac`] trait.
 #[derive(Clone, Debug)]
 pub struct DummyMac {
@@ -181,6 +308,24 @@ impl tink_core::Mac for DummyMac {
     }
 }
 
+/// Dummy implementation of [`tink_core::Prf`] trait, which repeats the input (followed by the
+/// name) to fill the requested output length.
+#[derive(Clone, Debug)]
+pub struct DummyPrf {
+    pub name: String,
+}
+
+impl tink_core::Prf for DummyPrf {
+    fn compute_prf(&self, input: &[u8], output_length: usize) -> Result<Vec<u8>, TinkError> {
+        let mut seed = input.to_vec();
+        seed.extend_from_slice(self.name.as_bytes());
+        if seed.is_empty() {
+            return Err("dummy prf: nothing to repeat".into());
+        }
+        Ok(seed.iter().cycle().take(output_length).cloned().collect())
+    }
+}
+
 /// Dummy implementation of a [`tink_core::registry::KmsClient`].
 pub struct DummyKmsClient;
 
@@ -737,6 +882,33 @@ pub fn new_hkdf_prf_key_format(hash_type: HashType, salt: &[u8]) -> tink_proto::
     }
 }
 
+/// Return a new [`KeyData`] that contains a [`HmacPrfKey`](tink_proto::HmacPrfKey).
+pub fn new_hmac_prf_key_data(hash_type: HashType) -> KeyData {
+    new_key_data(
+        HMAC_PRF_TYPE_URL,
+        &proto_encode(&new_hmac_prf_key(hash_type)),
+        tink_proto::key_data::KeyMaterialType::Symmetric,
+    )
+}
+
+/// Return a new [`KeyData`] that contains a [`HkdfPrfKey`](tink_proto::HkdfPrfKey).
+pub fn new_hkdf_prf_key_data(hash_type: HashType, salt: &[u8]) -> KeyData {
+    new_key_data(
+        HKDF_PRF_TYPE_URL,
+        &proto_encode(&new_hkdf_prf_key(hash_type, salt)),
+        tink_proto::key_data::KeyMaterialType::Symmetric,
+    )
+}
+
+/// Return a new [`KeyData`] that contains an [`AesCmacPrfKey`](tink_proto::AesCmacPrfKey).
+pub fn new_aes_cmac_prf_key_data() -> KeyData {
+    new_key_data(
+        AES_CMAC_PRF_TYPE_URL,
+        &proto_encode(&new_aes_cmac_prf_key()),
+        tink_proto::key_data::KeyMaterialType::Symmetric,
+    )
+}
+
 /// Create a new [`AesCmacPrfKey`](tink_proto::AesCmacPrfKey) with the specified parameters.
 pub fn new_aes_cmac_prf_key() -> tink_proto::AesCmacPrfKey {
     let key_value = get_random_bytes(32);
@@ -971,6 +1143,74 @@ pub fn generate_ecies_aead_hkdf_private_key(
     Ok(ecies_aead_hkdf_private_key(pub_key, &pvt.d_bytes()))
 }
 
+/// Check that the given [`Aead`] binds ciphertexts to their additional authenticated data:
+/// decryption must succeed with the matching AAD and fail with a different, truncated or empty
+/// AAD.  Returns an error describing the first check that failed.
+pub fn aead_round_trip_with_aad(aead: &dyn Aead) -> Result<(), TinkError> {
+    let pt = b"plaintext for AAD binding";
+    let aad = b"additional data";
+
+    let ct = aead
+        .encrypt(pt, aad)
+        .map_err(|e| wrap_err("encryption failed", e))?;
+    let decrypted = aead
+        .decrypt(&ct, aad)
+        .map_err(|e| wrap_err("decryption with matching AAD failed", e))?;
+    if decrypted != pt {
+        return Err("decryption with matching AAD returned wrong plaintext".into());
+    }
+    for (desc, wrong_aad) in &[
+        ("different", &b"other data"[..]),
+        ("truncated", &aad[..aad.len() - 1]),
+        ("empty", &[][..]),
+    ] {
+        if aead.decrypt(&ct, wrong_aad).is_ok() {
+            return Err(format!("decryption with {desc} AAD unexpectedly succeeded").into());
+        }
+    }
+
+    let ct = aead
+        .encrypt(pt, &[])
+        .map_err(|e| wrap_err("encryption with empty AAD failed", e))?;
+    if aead.decrypt(&ct, aad).is_ok() {
+        return Err(
+            "decryption of empty-AAD ciphertext with non-empty AAD unexpectedly succeeded".into(),
+        );
+    }
+    Ok(())
+}
+
+/// Generate [`KeyData`] for the given template via the registry and check that the resulting key
+/// proto (of type `K`) has the same version as the template's key format proto (of type `F`),
+/// as extracted by `format_version` and `key_version`.  This catches mismatches between a key
+/// manager and the templates for its key type.
+pub fn key_template_proto_round_trip<F, K>(
+    kt: &tink_proto::KeyTemplate,
+    format_version: fn(&F) -> u32,
+    key_version: fn(&K) -> u32,
+) -> Result<(), TinkError>
+where
+    F: prost::Message + Default,
+    K: prost::Message + Default,
+{
+    let format = F::decode(kt.value.as_ref()).map_err(|e| wrap_err("invalid key format", e))?;
+    let key_data =
+        tink_core::registry::new_key_data(kt).map_err(|e| wrap_err("cannot create key data", e))?;
+    if key_data.type_url != kt.type_url {
+        return Err(format!(
+            "key data has type URL {}, want {}",
+            key_data.type_url, kt.type_url
+        )
+        .into());
+    }
+    let key = K::decode(key_data.value.as_ref()).map_err(|e| wrap_err("invalid key", e))?;
+    let (want, got) = (format_version(&format), key_version(&key));
+    if want != got {
+        return Err(format!("key has version {got}, template has version {want}").into());
+    }
+    Ok(())
+}
+
 /// Convert a protocol buffer message to its serialized form.
 pub fn proto_encode<T>(msg: &T) -> Vec<u8>
 where
@@ -1017,19 +1257,13 @@ pub struct IoFailure {}
 
 impl std::io::Read for IoFailure {
     fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
-        Err(std::io::Error::new(
-            std::io::ErrorKind::Other,
-            "failure object",
-        ))
+        Err(std::io::Error::other("failure object"))
     }
 }
 
 impl std::io::Write for IoFailure {
     fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
-        Err(std::io::Error::new(
-            std::io::ErrorKind::Other,
-            "failure object",
-        ))
+        Err(std::io::Error::other("failure object"))
     }
     fn flush(&mut self) -> std::io::Result<()> {
         Ok(())
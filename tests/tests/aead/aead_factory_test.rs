@@ -14,6 +14,7 @@
 //
 ////////////////////////////////////////////////////////////////////////////////
 
+use std::sync::{atomic::Ordering, Arc};
 use tink_aead::subtle;
 use tink_core::{subtle::random::get_random_bytes, utils::wrap_err, TinkError};
 use tink_proto::OutputPrefixType;
@@ -171,3 +172,305 @@ fn test_factory_with_aes256_ctr_hmac_sha512() {
 
     tink_aead::new(&good_kh).expect("calling new() with good keyset::Handle failed");
 }
+
+#[test]
+fn test_factory_empty_aad() {
+    tink_aead::init();
+    let templates = vec![
+        tink_aead::aes128_gcm_key_template(),
+        tink_aead::aes256_gcm_no_prefix_key_template(),
+        tink_aead::aes128_gcm_siv_key_template(),
+        tink_aead::aes128_ctr_hmac_sha256_key_template(),
+        tink_aead::cha_cha20_poly1305_key_template(),
+        tink_aead::x_cha_cha20_poly1305_key_template(),
+    ];
+    for template in templates {
+        let kh = tink_core::keyset::Handle::new(&template).unwrap();
+        let a = tink_aead::new(&kh).unwrap();
+        let pt = b"some plaintext";
+
+        let ct_empty = a.encrypt(pt, b"").unwrap();
+        assert_eq!(
+            a.decrypt(&ct_empty, b"").unwrap(),
+            pt,
+            "{}",
+            template.type_url
+        );
+        let ct_one = a.encrypt(pt, b"a").unwrap();
+        assert_eq!(
+            a.decrypt(&ct_one, b"a").unwrap(),
+            pt,
+            "{}",
+            template.type_url
+        );
+
+        assert!(
+            a.decrypt(&ct_empty, b"a").is_err(),
+            "{}: empty-AAD ciphertext decrypted with non-empty AAD",
+            template.type_url
+        );
+        assert!(
+            a.decrypt(&ct_one, b"").is_err(),
+            "{}: ciphertext decrypted with empty AAD",
+            template.type_url
+        );
+    }
+}
+
+#[test]
+fn test_factory_encrypt_batch() {
+    tink_aead::init();
+    let kh = tink_core::keyset::Handle::new(&tink_aead::aes128_gcm_key_template())
+        .expect("failed to build keyset::Handle");
+    let a = tink_aead::new(&kh).expect("tink_aead::new failed");
+
+    let inputs: Vec<(Vec<u8>, Vec<u8>)> = (0..10_000u32)
+        .map(|i| {
+            (
+                i.to_be_bytes().repeat(i as usize % 7),
+                i.to_le_bytes().to_vec(),
+            )
+        })
+        .collect();
+    let items: Vec<(&[u8], &[u8])> = inputs
+        .iter()
+        .map(|(pt, aad)| (pt.as_slice(), aad.as_slice()))
+        .collect();
+
+    let results = a.encrypt_batch(&items);
+    assert_eq!(results.len(), items.len());
+    for ((pt, aad), result) in items.iter().zip(results) {
+        let ct = result.expect("encrypt_batch failed");
+        let decrypted = a.decrypt(&ct, aad).expect("decrypt failed");
+        assert_eq!(&decrypted, pt);
+    }
+
+    assert!(a.encrypt_batch(&[]).is_empty());
+}
+
+#[test]
+fn test_factory_max_decrypt_attempts() {
+    tink_aead::init();
+    let keys: Vec<tink_proto::keyset::Key> = (1..=100)
+        .map(|key_id| {
+            tink_tests::new_key(
+                &tink_tests::new_aes_gcm_key_data(16),
+                tink_proto::KeyStatusType::Enabled,
+                key_id,
+                OutputPrefixType::Raw,
+            )
+        })
+        .collect();
+    // Encrypt with a keyset holding only the 6th key.
+    let sixth_key_handle =
+        tink_core::keyset::insecure::new_handle(tink_tests::new_keyset(6, vec![keys[5].clone()]))
+            .unwrap();
+    let ct = tink_aead::new(&sixth_key_handle)
+        .unwrap()
+        .encrypt(b"plaintext", b"aad")
+        .unwrap();
+
+    let kh = tink_core::keyset::insecure::new_handle(tink_tests::new_keyset(1, keys)).unwrap();
+
+    // Without a limit, the 6th candidate key decrypts the ciphertext.
+    let a = tink_aead::new(&kh).unwrap();
+    assert_eq!(a.decrypt(&ct, b"aad").unwrap(), b"plaintext");
+    let a = tink_aead::new_with_options(
+        &kh,
+        tink_aead::AeadOptions {
+            max_decrypt_attempts: Some(6),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(a.decrypt(&ct, b"aad").unwrap(), b"plaintext");
+
+    let a = tink_aead::new_with_options(
+        &kh,
+        tink_aead::AeadOptions {
+            max_decrypt_attempts: Some(5),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    tink_tests::expect_err(a.decrypt(&ct, b"aad"), "within limit of 5 candidate keys");
+    // Encryption is unaffected.
+    let ct = a.encrypt(b"plaintext", b"aad").unwrap();
+    assert_eq!(a.decrypt(&ct, b"aad").unwrap(), b"plaintext");
+
+    tink_tests::expect_err(
+        tink_aead::new_with_options(
+            &kh,
+            tink_aead::AeadOptions {
+                max_decrypt_attempts: Some(0),
+                ..Default::default()
+            },
+        ),
+        "must be positive",
+    );
+}
+
+#[test]
+fn test_factory_raw_output() {
+    tink_aead::init();
+    let keys: Vec<tink_proto::keyset::Key> = (1..=3)
+        .map(|key_id| {
+            tink_tests::new_key(
+                &tink_tests::new_aes_gcm_key_data(16),
+                tink_proto::KeyStatusType::Enabled,
+                key_id,
+                OutputPrefixType::Tink,
+            )
+        })
+        .collect();
+    let kh =
+        tink_core::keyset::insecure::new_handle(tink_tests::new_keyset(2, keys.clone())).unwrap();
+    let prefixed = tink_aead::new(&kh).unwrap();
+    let raw = tink_aead::new_with_options(
+        &kh,
+        tink_aead::AeadOptions {
+            raw_output: true,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let pt = b"plaintext";
+    let prefixed_ct = prefixed.encrypt(pt, b"aad").unwrap();
+    let raw_ct = raw.encrypt(pt, b"aad").unwrap();
+    assert_eq!(
+        raw_ct.len() + tink_core::cryptofmt::NON_RAW_PREFIX_SIZE,
+        prefixed_ct.len()
+    );
+    assert_eq!(
+        prefixed_ct[0],
+        tink_core::cryptofmt::TINK_START_BYTE,
+        "prefixed ciphertext should have a TINK prefix"
+    );
+    // The raw ciphertext is exactly what the primary key's primitive produces.
+    let primary_only = tink_core::keyset::insecure::new_handle(tink_tests::new_keyset(
+        2,
+        vec![tink_tests::new_key(
+            keys[1].key_data.as_ref().unwrap(),
+            tink_proto::KeyStatusType::Enabled,
+            2,
+            OutputPrefixType::Raw,
+        )],
+    ))
+    .unwrap();
+    assert_eq!(
+        tink_aead::new(&primary_only)
+            .unwrap()
+            .decrypt(&raw_ct, b"aad")
+            .unwrap(),
+        pt
+    );
+
+    // The raw output wrapper decrypts both forms.
+    assert_eq!(raw.decrypt(&raw_ct, b"aad").unwrap(), pt);
+    assert_eq!(raw.decrypt(&prefixed_ct, b"aad").unwrap(), pt);
+    assert!(raw.decrypt(&raw_ct, b"other aad").is_err());
+
+    // Raw ciphertext from an earlier primary still decrypts after rotation.
+    let rotated = tink_core::keyset::insecure::new_handle(tink_tests::new_keyset(3, keys)).unwrap();
+    let raw_rotated = tink_aead::new_with_options(
+        &rotated,
+        tink_aead::AeadOptions {
+            raw_output: true,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(raw_rotated.decrypt(&raw_ct, b"aad").unwrap(), pt);
+}
+
+#[test]
+fn test_factory_stats() {
+    tink_aead::init();
+    let kh = tink_core::keyset::Handle::new(&tink_aead::aes128_gcm_key_template()).unwrap();
+    let stats = Arc::new(tink_core::monitoring::Stats::default());
+    let a = tink_aead::new_with_options(
+        &kh,
+        tink_aead::AeadOptions {
+            stats: Some(stats.clone()),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let ct = a.encrypt(b"plaintext", b"aad").unwrap();
+    a.encrypt(b"more plaintext", b"aad").unwrap();
+    assert_eq!(a.decrypt(&ct, b"aad").unwrap(), b"plaintext");
+    assert!(a.decrypt(&ct, b"other aad").is_err());
+    assert!(a.decrypt(b"junk", b"aad").is_err());
+
+    assert_eq!(stats.encryptions.load(Ordering::Relaxed), 2);
+    assert_eq!(stats.decryptions.load(Ordering::Relaxed), 3);
+    assert_eq!(stats.failures.load(Ordering::Relaxed), 2);
+
+    // Clones of the primitive share the counters.
+    let a2 = a.box_clone();
+    a2.encrypt(b"plaintext", b"aad").unwrap();
+    assert_eq!(stats.encryptions.load(Ordering::Relaxed), 3);
+}
+
+#[test]
+fn test_factory_combined_options() {
+    tink_aead::init();
+    let keys: Vec<tink_proto::keyset::Key> = (1..=3)
+        .map(|key_id| {
+            tink_tests::new_key(
+                &tink_tests::new_aes_gcm_key_data(16),
+                tink_proto::KeyStatusType::Enabled,
+                key_id,
+                OutputPrefixType::Tink,
+            )
+        })
+        .collect();
+    let kh = tink_core::keyset::insecure::new_handle(tink_tests::new_keyset(3, keys)).unwrap();
+    let stats = Arc::new(tink_core::monitoring::Stats::default());
+    let a = tink_aead::new_with_options(
+        &kh,
+        tink_aead::AeadOptions {
+            max_decrypt_attempts: Some(1),
+            stats: Some(stats.clone()),
+            raw_output: true,
+        },
+    )
+    .unwrap();
+
+    // RAW output from the primary is found by the first candidate key.
+    let ct = a.encrypt(b"plaintext", b"aad").unwrap();
+    assert_eq!(
+        ct.len() + tink_core::cryptofmt::NON_RAW_PREFIX_SIZE,
+        tink_aead::new(&kh)
+            .unwrap()
+            .encrypt(b"plaintext", b"aad")
+            .unwrap()
+            .len()
+    );
+    assert_eq!(a.decrypt(&ct, b"aad").unwrap(), b"plaintext");
+    tink_tests::expect_err(
+        a.decrypt(&ct, b"other aad"),
+        "within limit of 1 candidate keys",
+    );
+
+    assert_eq!(stats.encryptions.load(Ordering::Relaxed), 1);
+    assert_eq!(stats.decryptions.load(Ordering::Relaxed), 2);
+    assert_eq!(stats.failures.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn test_factory_no_enabled_keys() {
+    tink_aead::init();
+    let key = tink_tests::new_key(
+        &tink_tests::new_aes_gcm_key_data(16),
+        tink_proto::KeyStatusType::Disabled,
+        42,
+        OutputPrefixType::Tink,
+    );
+    let kh =
+        tink_core::keyset::insecure::new_handle(tink_tests::new_keyset(42, vec![key])).unwrap();
+    tink_tests::expect_err(tink_aead::new(&kh), "no enabled keys");
+    tink_tests::expect_err(kh.primitives(), "no enabled keys");
+}
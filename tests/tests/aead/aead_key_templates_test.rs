@@ -54,6 +54,23 @@ fn test_key_templates() {
     }
 }
 
+#[test]
+fn test_aes_gcm_key_template_proto_round_trip() {
+    tink_aead::init();
+    for template in &[
+        tink_aead::aes128_gcm_key_template(),
+        tink_aead::aes256_gcm_key_template(),
+        tink_aead::aes256_gcm_no_prefix_key_template(),
+    ] {
+        tink_tests::key_template_proto_round_trip(
+            template,
+            |f: &tink_proto::AesGcmKeyFormat| f.version,
+            |k: &tink_proto::AesGcmKey| k.version,
+        )
+        .unwrap();
+    }
+}
+
 #[test]
 fn test_no_prefix_key_templates() {
     tink_aead::init();
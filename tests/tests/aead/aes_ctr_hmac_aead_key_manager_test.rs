@@ -110,6 +110,40 @@ fn test_new_key_with_invalid_format() {
                 }),
             },
         ),
+        (
+            "IV size out of range",
+            AesCtrHmacAeadKeyFormat {
+                aes_ctr_key_format: Some(tink_proto::AesCtrKeyFormat {
+                    params: Some(tink_proto::AesCtrParams { iv_size: 11 }), // invalid
+                    key_size: 16,
+                }),
+                hmac_key_format: Some(tink_proto::HmacKeyFormat {
+                    version: tink_aead::AES_CTR_HMAC_AEAD_KEY_VERSION,
+                    params: Some(tink_proto::HmacParams {
+                        hash: HashType::Sha256 as i32,
+                        tag_size: 16,
+                    }),
+                    key_size: 32,
+                }),
+            },
+        ),
+        (
+            "IV size out of range",
+            AesCtrHmacAeadKeyFormat {
+                aes_ctr_key_format: Some(tink_proto::AesCtrKeyFormat {
+                    params: Some(tink_proto::AesCtrParams { iv_size: 17 }), // invalid
+                    key_size: 16,
+                }),
+                hmac_key_format: Some(tink_proto::HmacKeyFormat {
+                    version: tink_aead::AES_CTR_HMAC_AEAD_KEY_VERSION,
+                    params: Some(tink_proto::HmacParams {
+                        hash: HashType::Sha256 as i32,
+                        tag_size: 16,
+                    }),
+                    key_size: 32,
+                }),
+            },
+        ),
         (
             "invalid AES key size",
             AesCtrHmacAeadKeyFormat {
@@ -39,6 +39,38 @@ fn test_aes_gcm_get_primitive_basic() {
     }
 }
 
+#[test]
+fn test_aes_gcm_decrypt_short_ciphertext() {
+    tink_aead::init();
+    let key_manager = tink_core::registry::get_key_manager(tink_tests::AES_GCM_TYPE_URL)
+        .expect("cannot obtain AES-GCM key manager");
+    let min_size = tink_aead::subtle::AES_GCM_IV_SIZE + tink_aead::subtle::AES_GCM_TAG_SIZE;
+    for key_size in KEY_SIZES {
+        let key = tink_tests::new_aes_gcm_key(tink_tests::AES_GCM_KEY_VERSION, *key_size);
+        let p = match key_manager.primitive(&proto_encode(&key)).unwrap() {
+            tink_core::Primitive::Aead(p) => p,
+            _ => panic!("not an AEAD primitive"),
+        };
+        for size in 0..min_size {
+            let ct = get_random_bytes(size);
+            tink_tests::expect_err(p.decrypt(&ct, &[]), "ciphertext too short");
+        }
+    }
+
+    // Same again via keysets, which strip any output prefix before decrypting.
+    for template in &[
+        tink_aead::aes128_gcm_key_template(),
+        tink_aead::aes256_gcm_no_prefix_key_template(),
+    ] {
+        let kh = tink_core::keyset::Handle::new(template).unwrap();
+        let a = tink_aead::new(&kh).unwrap();
+        for size in 0..=min_size + tink_core::cryptofmt::NON_RAW_PREFIX_SIZE {
+            let ct = get_random_bytes(size);
+            assert!(a.decrypt(&ct, &[]).is_err());
+        }
+    }
+}
+
 #[test]
 fn test_aes_gcm_get_primitive_with_invalid_input() {
     tink_aead::init();
@@ -252,5 +284,5 @@ fn validate_aes_gcm_primitive(
     if decrypted != pt {
         return Err("decryption failed".into());
     }
-    Ok(())
+    tink_tests::aead_round_trip_with_aad(cipher.as_ref())
 }
@@ -259,3 +259,62 @@ fn validate_aes_gcm_siv_primitive(
     }
     Ok(())
 }
+
+#[test]
+fn test_aes_gcm_siv_templates_nonce_misuse_resistance() {
+    tink_aead::init();
+    for (template, key_size) in &[
+        (tink_aead::aes128_gcm_siv_key_template(), 16),
+        (tink_aead::aes256_gcm_siv_key_template(), 32),
+    ] {
+        let kh = tink_core::keyset::Handle::new(template).unwrap();
+        let a = tink_aead::new(&kh).unwrap();
+        let ct = a.encrypt(b"plaintext", b"aad").unwrap();
+        assert_eq!(a.decrypt(&ct, b"aad").unwrap(), b"plaintext");
+
+        // Check the same key with an injected nonce.
+        let ks = tink_core::keyset::insecure::keyset_material(&kh);
+        let key_data = ks.key[0].key_data.as_ref().unwrap();
+        let key = tink_proto::AesGcmSivKey::decode(key_data.value.as_ref()).unwrap();
+        assert_eq!(key.version, 0);
+        assert_eq!(key.key_value.len(), *key_size);
+        let siv = tink_aead::subtle::AesGcmSiv::new(&key.key_value).unwrap();
+        let prefix_len = tink_core::cryptofmt::NON_RAW_PREFIX_SIZE;
+        assert_eq!(
+            tink_core::Aead::decrypt(&siv, &ct[prefix_len..], b"aad").unwrap(),
+            b"plaintext"
+        );
+
+        let nonce = get_random_bytes(tink_aead::subtle::AES_GCM_SIV_NONCE_SIZE);
+        let ct1 = siv
+            .encrypt_with_nonce(&nonce, b"plaintext", b"aad")
+            .unwrap();
+        let ct2 = siv
+            .encrypt_with_nonce(&nonce, b"plaintext", b"aad")
+            .unwrap();
+        assert_eq!(
+            ct1, ct2,
+            "same nonce and plaintext should give the same ciphertext"
+        );
+        let ct3 = siv
+            .encrypt_with_nonce(&nonce, b"plaintexu", b"aad")
+            .unwrap();
+        let tag_start = ct1.len() - tink_aead::subtle::AES_GCM_SIV_TAG_SIZE;
+        assert_ne!(ct1[tag_start..], ct3[tag_start..]);
+        // With a synthetic IV, a one-bit change at the end of the plaintext also changes the
+        // start of the ciphertext body.
+        let body_start = tink_aead::subtle::AES_GCM_SIV_NONCE_SIZE;
+        assert_ne!(
+            ct1[body_start..body_start + 8],
+            ct3[body_start..body_start + 8]
+        );
+        assert_eq!(
+            tink_core::Aead::decrypt(&siv, &ct1, b"aad").unwrap(),
+            b"plaintext"
+        );
+        tink_tests::expect_err(
+            siv.encrypt_with_nonce(&nonce[1..], b"plaintext", b"aad"),
+            "invalid nonce size",
+        );
+    }
+}
@@ -0,0 +1,44 @@
+// Copyright 2020 The Tink-Rust Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+use tink_aead::{subtle::AesGcm, FallbackAead};
+use tink_core::{subtle::random::get_random_bytes, Aead};
+
+#[test]
+fn test_fallback_aead_migration() {
+    let new_key = AesGcm::new(&get_random_bytes(32)).unwrap();
+    let old_key = AesGcm::new(&get_random_bytes(32)).unwrap();
+    let old_ct = old_key.encrypt(b"plaintext", b"aad").unwrap();
+
+    let a = FallbackAead::new(vec![Box::new(new_key.clone()), Box::new(old_key.clone())]).unwrap();
+    assert_eq!(a.decrypt(&old_ct, b"aad").unwrap(), b"plaintext");
+    assert!(a.decrypt(&old_ct, b"wrong aad").is_err());
+
+    // New ciphertexts are produced under the first AEAD only.
+    let new_ct = a.encrypt(b"plaintext", b"aad").unwrap();
+    assert_eq!(new_key.decrypt(&new_ct, b"aad").unwrap(), b"plaintext");
+    assert!(old_key.decrypt(&new_ct, b"aad").is_err());
+    assert_eq!(a.clone().decrypt(&new_ct, b"aad").unwrap(), b"plaintext");
+
+    let other = AesGcm::new(&get_random_bytes(32)).unwrap();
+    let other_ct = other.encrypt(b"plaintext", b"aad").unwrap();
+    tink_tests::expect_err(a.decrypt(&other_ct, b"aad"), "decryption failed");
+}
+
+#[test]
+fn test_fallback_aead_empty() {
+    tink_tests::expect_err(FallbackAead::new(vec![]), "no AEADs provided");
+}
@@ -14,6 +14,32 @@
 //
 ////////////////////////////////////////////////////////////////////////////////
 
+use std::{
+    convert::TryInto,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+use tink_core::{Aead, TinkError};
+
+/// Remote AEAD that counts decryption calls.
+#[derive(Clone)]
+struct CountingAead {
+    inner: tink_tests::DummyAead,
+    decrypts: Arc<AtomicUsize>,
+}
+
+impl Aead for CountingAead {
+    fn encrypt(&self, pt: &[u8], aad: &[u8]) -> Result<Vec<u8>, TinkError> {
+        self.inner.encrypt(pt, aad)
+    }
+    fn decrypt(&self, ct: &[u8], aad: &[u8]) -> Result<Vec<u8>, TinkError> {
+        self.decrypts.fetch_add(1, Ordering::SeqCst);
+        self.inner.decrypt(ct, aad)
+    }
+}
+
 fn create_kms_envelope_aead() -> Box<dyn tink_core::Aead> {
     let kh = tink_core::keyset::Handle::new(&tink_aead::aes256_gcm_key_template())
         .expect("failed to create new handle");
@@ -52,6 +78,43 @@ fn test_kms_envelope_roundtrip() {
     assert_eq!(plaintext, original_plaintext,);
 }
 
+#[test]
+fn test_kms_envelope_deterministic_dek() {
+    tink_aead::init();
+    tink_daead::init();
+    let remote = tink_tests::DummyAead::default();
+    let a = tink_aead::KmsEnvelopeAead::new(tink_daead::aes_siv_key_template(), Box::new(remote));
+
+    let pt = b"hello world";
+    let ct = a.encrypt(pt, b"aad").unwrap();
+    assert_eq!(a.decrypt(&ct, b"aad").unwrap(), pt);
+    assert!(a.decrypt(&ct, b"other aad").is_err());
+    // Each encryption uses a fresh DEK, so ciphertexts still differ.
+    assert_ne!(a.encrypt(pt, b"aad").unwrap(), ct);
+
+    // The payload is the deterministic encryption of the plaintext under the DEK.
+    let ed = u32::from_be_bytes(ct[..4].try_into().unwrap()) as usize;
+    let dek = tink_tests::DummyAead::default()
+        .decrypt(&ct[4..4 + ed], &[])
+        .unwrap();
+    let daead =
+        match tink_core::registry::primitive(&tink_daead::aes_siv_key_template().type_url, &dek)
+            .unwrap()
+        {
+            tink_core::Primitive::DeterministicAead(p) => p,
+            _ => panic!("expected deterministic AEAD"),
+        };
+    assert_eq!(
+        daead.encrypt_deterministically(pt, b"aad").unwrap(),
+        &ct[4 + ed..]
+    );
+
+    // Cached DEK primitives work too.
+    let a = a.with_dek_cache(4);
+    assert_eq!(a.decrypt(&ct, b"aad").unwrap(), pt);
+    assert_eq!(a.decrypt(&ct, b"aad").unwrap(), pt);
+}
+
 #[test]
 fn test_kms_envelope_short_ciphertext() {
     tink_aead::init();
@@ -63,3 +126,46 @@ fn test_kms_envelope_short_ciphertext() {
     let result = a.decrypt(&[0, 0, 0, 3, 1], &[]); // length of 3, only 1 byte available
     tink_tests::expect_err(result, "invalid ciphertext");
 }
+
+#[test]
+fn test_kms_envelope_dek_cache() {
+    tink_aead::init();
+    let decrypts = Arc::new(AtomicUsize::new(0));
+    let remote = CountingAead {
+        inner: tink_tests::DummyAead::default(),
+        decrypts: decrypts.clone(),
+    };
+    let a = tink_aead::KmsEnvelopeAead::new(tink_aead::aes128_gcm_key_template(), Box::new(remote))
+        .with_dek_cache(1);
+    let ct1 = a.encrypt(b"first", b"aad").unwrap();
+    let ct2 = a.encrypt(b"second", b"aad").unwrap();
+
+    assert_eq!(a.decrypt(&ct1, b"aad").unwrap(), b"first");
+    assert_eq!(decrypts.load(Ordering::SeqCst), 1);
+    // Second decrypt using the same DEK hits the cache, as does a clone.
+    assert_eq!(a.decrypt(&ct1, b"aad").unwrap(), b"first");
+    assert_eq!(a.clone().decrypt(&ct1, b"aad").unwrap(), b"first");
+    assert_eq!(decrypts.load(Ordering::SeqCst), 1);
+    // A cached DEK primitive still checks the payload.
+    assert!(a.decrypt(&ct1, b"wrong aad").is_err());
+    assert_eq!(decrypts.load(Ordering::SeqCst), 1);
+
+    // A different DEK evicts the first from the single-entry cache.
+    assert_eq!(a.decrypt(&ct2, b"aad").unwrap(), b"second");
+    assert_eq!(a.decrypt(&ct2, b"aad").unwrap(), b"second");
+    assert_eq!(decrypts.load(Ordering::SeqCst), 2);
+    assert_eq!(a.decrypt(&ct1, b"aad").unwrap(), b"first");
+    assert_eq!(decrypts.load(Ordering::SeqCst), 3);
+
+    // Without a cache every decrypt goes to the remote AEAD.
+    let decrypts = Arc::new(AtomicUsize::new(0));
+    let remote = CountingAead {
+        inner: tink_tests::DummyAead::default(),
+        decrypts: decrypts.clone(),
+    };
+    let a = tink_aead::KmsEnvelopeAead::new(tink_aead::aes128_gcm_key_template(), Box::new(remote));
+    let ct = a.encrypt(b"data", b"aad").unwrap();
+    a.decrypt(&ct, b"aad").unwrap();
+    a.decrypt(&ct, b"aad").unwrap();
+    assert_eq!(decrypts.load(Ordering::SeqCst), 2);
+}
@@ -20,9 +20,12 @@ mod aes_ctr_hmac_aead_key_manager_test;
 mod aes_gcm_key_manager_test;
 mod aes_gcm_siv_key_manager_test;
 mod chacha20poly1305_key_manager_test;
+mod fallback_aead_test;
 mod integration_test;
 mod kms_envelope_aead_test;
 mod kms_envelope_key_manager_test;
+mod rate_limited_aead_test;
+mod versioned_aead_test;
 mod xchacha20poly1305_key_manager_test;
 
 mod subtle;
@@ -0,0 +1,88 @@
+// Copyright 2020 The Tink-Rust Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+use std::time::Duration;
+use tink_aead::{RateLimitedAead, RATE_LIMITED_AEAD_TRIPPED};
+use tink_core::Aead;
+
+fn create_rate_limited_aead(threshold: usize, window: Duration) -> RateLimitedAead {
+    tink_aead::init();
+    let kh = tink_core::keyset::Handle::new(&tink_aead::aes128_gcm_key_template())
+        .expect("failed to create new handle");
+    let inner = tink_aead::new(&kh).expect("failed to create AEAD");
+    RateLimitedAead::new(inner, threshold, window).expect("failed to create RateLimitedAead")
+}
+
+#[test]
+fn test_rate_limited_aead_trips_after_threshold() {
+    let a = create_rate_limited_aead(10, Duration::from_secs(3600));
+    let ct = a.encrypt(b"plaintext", b"aad").expect("failed to encrypt");
+
+    for i in 0..10 {
+        let result = a.decrypt(b"not a valid ciphertext", b"aad");
+        assert!(result.is_err(), "decryption {} unexpectedly succeeded", i);
+        assert!(
+            !format!("{:?}", result.unwrap_err()).contains(RATE_LIMITED_AEAD_TRIPPED),
+            "breaker tripped early at failure {}",
+            i
+        );
+    }
+    assert!(a.is_tripped());
+
+    // The 11th call fails with the breaker error, even for a valid ciphertext.
+    tink_tests::expect_err(a.decrypt(&ct, b"aad"), RATE_LIMITED_AEAD_TRIPPED);
+    // Clones share the breaker state.
+    tink_tests::expect_err(a.clone().decrypt(&ct, b"aad"), RATE_LIMITED_AEAD_TRIPPED);
+    // Encryption is still delegated.
+    assert!(a.encrypt(b"plaintext", b"aad").is_ok());
+}
+
+#[test]
+fn test_rate_limited_aead_success_resets() {
+    let a = create_rate_limited_aead(3, Duration::from_secs(3600));
+    let ct = a.encrypt(b"plaintext", b"aad").expect("failed to encrypt");
+
+    for _ in 0..2 {
+        assert!(a.decrypt(&ct, b"wrong aad").is_err());
+    }
+    let pt = a.decrypt(&ct, b"aad").expect("failed to decrypt");
+    assert_eq!(pt, b"plaintext");
+    for _ in 0..2 {
+        assert!(a.decrypt(&ct, b"wrong aad").is_err());
+    }
+    assert!(!a.is_tripped());
+    assert!(a.decrypt(&ct, b"aad").is_ok());
+}
+
+#[test]
+fn test_rate_limited_aead_window_expiry() {
+    let a = create_rate_limited_aead(1, Duration::from_millis(10));
+    let ct = a.encrypt(b"plaintext", b"aad").expect("failed to encrypt");
+
+    assert!(a.decrypt(&ct, b"wrong aad").is_err());
+    tink_tests::expect_err(a.decrypt(&ct, b"aad"), RATE_LIMITED_AEAD_TRIPPED);
+    std::thread::sleep(Duration::from_millis(20));
+    assert!(!a.is_tripped());
+    assert!(a.decrypt(&ct, b"aad").is_ok());
+}
+
+#[test]
+fn test_rate_limited_aead_zero_threshold() {
+    tink_aead::init();
+    let kh = tink_core::keyset::Handle::new(&tink_aead::aes128_gcm_key_template()).unwrap();
+    let inner = tink_aead::new(&kh).unwrap();
+    assert!(RateLimitedAead::new(inner, 0, Duration::from_secs(1)).is_err());
+}
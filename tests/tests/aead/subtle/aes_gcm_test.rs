@@ -41,6 +41,123 @@ fn test_aes_gcm_tag_length() {
     }
 }
 
+#[test]
+fn test_aes_gcm_nonce_tag_sizes() {
+    assert_eq!(subtle::AES_GCM_IV_SIZE, 12);
+    assert_eq!(subtle::AES_GCM_TAG_SIZE, 16);
+    for key_size in KEY_SIZES {
+        let key = get_random_bytes(*key_size);
+        let a = subtle::AesGcm::new(&key).unwrap();
+        assert_eq!(a.nonce_size(), subtle::AES_GCM_IV_SIZE);
+        assert_eq!(a.tag_size(), subtle::AES_GCM_TAG_SIZE);
+        for pt_size in &[0, 1, 16, 100] {
+            let pt = get_random_bytes(*pt_size);
+            let ct = a.encrypt(&pt, &[]).unwrap();
+            assert_eq!(ct.len(), a.nonce_size() + pt.len() + a.tag_size());
+        }
+    }
+}
+
+#[test]
+fn test_aes_gcm_truncated_ciphertext() {
+    for key_size in KEY_SIZES {
+        let key = get_random_bytes(*key_size);
+        let a = subtle::AesGcm::new(&key).unwrap();
+        tink_tests::expect_err(a.decrypt(&[0u8; 10], &[]), "ciphertext too short");
+        let min_size = subtle::AES_GCM_IV_SIZE + subtle::AES_GCM_TAG_SIZE;
+        tink_tests::expect_err(
+            a.decrypt(&vec![0u8; min_size - 1], &[]),
+            "ciphertext too short",
+        );
+    }
+}
+
+#[test]
+fn test_aes_gcm_detached() {
+    for key_size in KEY_SIZES {
+        let key = get_random_bytes(*key_size);
+        let a = subtle::AesGcm::new(&key).unwrap();
+        let ad = get_random_bytes(20);
+        for pt_size in &[0, 1, 16, 100] {
+            let pt = get_random_bytes(*pt_size);
+            let (ct, iv, tag) = a.encrypt_detached(&pt, &ad).unwrap();
+            assert_eq!(ct.len(), pt.len());
+            assert_eq!(iv.len(), subtle::AES_GCM_IV_SIZE);
+            assert_eq!(tag.len(), subtle::AES_GCM_TAG_SIZE);
+            assert_eq!(a.decrypt_detached(&ct, &iv, &tag, &ad).unwrap(), pt);
+
+            // The detached parts reassemble into the combined format.
+            let combined = [iv.as_slice(), &ct, &tag].concat();
+            assert_eq!(a.decrypt(&combined, &ad).unwrap(), pt);
+
+            // And the combined format splits into the detached parts.
+            let ct2 = a.encrypt(&pt, &ad).unwrap();
+            let (iv2, rest) = ct2.split_at(subtle::AES_GCM_IV_SIZE);
+            let (ct2, tag2) = rest.split_at(rest.len() - subtle::AES_GCM_TAG_SIZE);
+            assert_eq!(a.decrypt_detached(ct2, iv2, tag2, &ad).unwrap(), pt);
+
+            let mut bad_tag = tag.clone();
+            bad_tag[0] ^= 1;
+            assert!(a.decrypt_detached(&ct, &iv, &bad_tag, &ad).is_err());
+            assert!(a.decrypt_detached(&ct, &iv, &tag, &[]).is_err());
+        }
+        tink_tests::expect_err(
+            a.decrypt_detached(&[], &[0u8; 11], &[0u8; 16], &[]),
+            "invalid IV size",
+        );
+        tink_tests::expect_err(
+            a.decrypt_detached(&[], &[0u8; 12], &[0u8; 15], &[]),
+            "invalid tag size",
+        );
+    }
+}
+
+#[test]
+fn test_aes_gcm_empty_aad() {
+    for key_size in KEY_SIZES {
+        let key = get_random_bytes(*key_size);
+        let a = subtle::AesGcm::new(&key).unwrap();
+        for pt_size in &[0, 1, 16, 100] {
+            let pt = get_random_bytes(*pt_size);
+            let ct_empty = a.encrypt(&pt, b"").unwrap();
+            assert_eq!(a.decrypt(&ct_empty, b"").unwrap(), pt);
+            assert_eq!(a.decrypt(&ct_empty, &[]).unwrap(), pt);
+
+            let ct_one = a.encrypt(&pt, b"\x00").unwrap();
+            assert_eq!(a.decrypt(&ct_one, b"\x00").unwrap(), pt);
+
+            // A zero-length AAD is distinct from a single zero byte of AAD.
+            assert!(a.decrypt(&ct_empty, b"\x00").is_err());
+            assert!(a.decrypt(&ct_one, b"").is_err());
+
+            let (ct, iv, tag) = a.encrypt_detached(&pt, b"").unwrap();
+            assert_eq!(a.decrypt_detached(&ct, &iv, &tag, b"").unwrap(), pt);
+            assert!(a.decrypt_detached(&ct, &iv, &tag, b"\x00").is_err());
+        }
+    }
+}
+
+#[test]
+fn test_aes_gcm_max_aad_size() {
+    for key_size in KEY_SIZES {
+        let key = get_random_bytes(*key_size);
+        let a = subtle::AesGcm::new(&key).unwrap().with_max_aad_size(8);
+        let pt = b"plaintext";
+
+        let ct = a.encrypt(pt, b"12345678").unwrap();
+        assert_eq!(a.decrypt(&ct, b"12345678").unwrap(), pt);
+
+        tink_tests::expect_err(a.encrypt(pt, b"123456789"), "additional data too long");
+        tink_tests::expect_err(a.decrypt(&ct, b"123456789"), "additional data too long");
+
+        // The default limit accepts ordinary additional data.
+        let b = subtle::AesGcm::new(&key).unwrap();
+        let aad = get_random_bytes(1024);
+        let ct = b.encrypt(pt, &aad).unwrap();
+        assert_eq!(b.decrypt(&ct, &aad).unwrap(), pt);
+    }
+}
+
 #[test]
 fn test_aes_gcm_key_size() {
     for key_size in KEY_SIZES {
@@ -139,6 +256,59 @@ fn test_aes_gcm_modify_ciphertext() {
     }
 }
 
+// A single instance (and its clones) reuses the same key schedule; check that sequential
+// encryptions through it still use fresh nonces and remain decryptable.
+#[test]
+fn test_aes_gcm_reused_instance() {
+    for key_size in KEY_SIZES {
+        let key = get_random_bytes(*key_size);
+        let a = subtle::AesGcm::new(&key).unwrap();
+        let a2 = a.clone();
+        let fresh = subtle::AesGcm::new(&key).unwrap();
+        let mut nonces = HashSet::new();
+        for i in 0..10_000u32 {
+            let pt = i.to_be_bytes();
+            let enc = if i % 2 == 0 { &a } else { &a2 };
+            let ct = enc.encrypt(&pt, b"aad").unwrap();
+            assert!(
+                nonces.insert(ct[..subtle::AES_GCM_IV_SIZE].to_vec()),
+                "nonce is repeated after {} encryptions",
+                i
+            );
+            assert_eq!(fresh.decrypt(&ct, b"aad").unwrap(), pt);
+        }
+    }
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "nonce reuse detected")]
+fn test_aes_gcm_nonce_reuse_detected() {
+    let a = subtle::AesGcm::new(&get_random_bytes(16)).unwrap();
+    let ct = a.encrypt(b"message", b"").unwrap();
+    // Simulate a broken nonce source handing out the same nonce again (via a clone, which
+    // shares the detector).
+    a.clone()
+        .nonce_reuse_detector()
+        .observe(&ct[..subtle::AES_GCM_IV_SIZE]);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn test_nonce_reuse_detector_window() {
+    let detector = subtle::NonceReuseDetector::new();
+    for i in 0..=subtle::NONCE_REUSE_DETECTOR_WINDOW as u32 {
+        detector.observe(&i.to_be_bytes());
+    }
+    // The oldest nonce has dropped out of the window, the most recent has not.
+    detector.observe(&0u32.to_be_bytes());
+    let last = subtle::NONCE_REUSE_DETECTOR_WINDOW as u32;
+    let result = std::panic::catch_unwind(|| detector.observe(&last.to_be_bytes()));
+    assert!(result.is_err());
+    // The detector remains usable after reporting a reuse.
+    detector.observe(b"fresh nonce");
+}
+
 // This is a very simple test for the randomness of the nonce. The test simply checks that the
 // multiple ciphertexts of the same message are distinct.
 #[test]
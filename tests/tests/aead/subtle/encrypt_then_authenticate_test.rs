@@ -258,6 +258,28 @@ fn test_eta_decrypt_modified_ciphertext() {
     }
 }
 
+#[test]
+fn test_eta_mac_covers_iv() {
+    let iv_size = 12;
+    let cipher = create_aead(16, iv_size, HashType::Sha256, 16, 16).unwrap();
+
+    let message = b"Some data to encrypt.";
+    let aad = b"Some data to authenticate.";
+    let ciphertext = cipher.encrypt(message, aad).expect("encryption failed");
+
+    // Move the first byte of the encrypted payload into the IV region, keeping the overall
+    // length unchanged.
+    let mut modct = Vec::with_capacity(ciphertext.len());
+    modct.push(ciphertext[iv_size]);
+    modct.extend_from_slice(&ciphertext[..iv_size]);
+    modct.extend_from_slice(&ciphertext[iv_size + 1..]);
+    assert_eq!(modct.len(), ciphertext.len());
+    assert_ne!(modct, ciphertext);
+    cipher
+        .decrypt(&modct, aad)
+        .expect_err("successfully decrypted ciphertext with shifted IV");
+}
+
 #[test]
 fn test_eta_empty_params() {
     let key_size = 16;
@@ -19,6 +19,7 @@
 use serde::Deserialize;
 use tink_tests::hex_string;
 
+#[allow(dead_code)]
 #[derive(Debug, Deserialize)]
 pub struct TestData {
     #[serde(flatten)]
@@ -27,6 +28,7 @@ pub struct TestData {
     pub test_groups: Vec<TestGroup>,
 }
 
+#[allow(dead_code)]
 #[derive(Debug, Deserialize)]
 pub struct TestGroup {
     #[serde(flatten)]
@@ -0,0 +1,66 @@
+// Copyright 2020 The Tink-Rust Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+use std::collections::HashMap;
+use tink_aead::VersionedAead;
+use tink_core::Aead;
+
+fn new_aead(template: tink_proto::KeyTemplate) -> Box<dyn Aead> {
+    tink_aead::init();
+    let kh = tink_core::keyset::Handle::new(&template).expect("failed to create new handle");
+    tink_aead::new(&kh).expect("failed to create AEAD")
+}
+
+#[test]
+fn test_versioned_aead_migration() {
+    let v1 = new_aead(tink_aead::aes128_gcm_key_template());
+    let v2 = new_aead(tink_aead::cha_cha20_poly1305_key_template());
+
+    let mut old_aeads = HashMap::new();
+    old_aeads.insert(1u8, v1.box_clone());
+    let old = VersionedAead::new(1, old_aeads).unwrap();
+    let ct1 = old.encrypt(b"plaintext", b"aad").unwrap();
+    assert_eq!(ct1[0], 1);
+
+    // After migrating to version 2, version 1 ciphertexts remain readable.
+    let mut aeads = HashMap::new();
+    aeads.insert(1u8, v1);
+    aeads.insert(2u8, v2);
+    let new = VersionedAead::new(2, aeads).unwrap();
+    assert_eq!(new.decrypt(&ct1, b"aad").unwrap(), b"plaintext");
+
+    let ct2 = new.encrypt(b"plaintext", b"aad").unwrap();
+    assert_eq!(ct2[0], 2);
+    assert_eq!(new.clone().decrypt(&ct2, b"aad").unwrap(), b"plaintext");
+    tink_tests::expect_err(old.decrypt(&ct2, b"aad"), "unknown version 2");
+
+    // Relabelling a ciphertext with the wrong version fails.
+    let mut relabelled = ct1.clone();
+    relabelled[0] = 2;
+    tink_tests::expect_err(new.decrypt(&relabelled, b"aad"), "decryption failed");
+    tink_tests::expect_err(new.decrypt(&[], b"aad"), "too short");
+    assert!(new.decrypt(&ct1, b"wrong aad").is_err());
+}
+
+#[test]
+fn test_versioned_aead_missing_current() {
+    let mut aeads = HashMap::new();
+    aeads.insert(1u8, new_aead(tink_aead::aes128_gcm_key_template()));
+    tink_tests::expect_err(
+        VersionedAead::new(2, aeads),
+        "no AEAD for current version 2",
+    );
+}
@@ -14,6 +14,7 @@
 //
 ////////////////////////////////////////////////////////////////////////////////
 
+use proptest::prelude::*;
 use tink_core::keyset::{Reader, Writer};
 
 #[test]
@@ -66,7 +67,7 @@ fn test_binary_io_read_fail() {
     let result = r.read();
     tink_tests::expect_err(result, "read failed");
 
-    let buf = vec![1, 2, 3];
+    let buf = [1, 2, 3];
     let mut r = tink_core::keyset::BinaryReader::new(&buf[..]);
     let result = r.read();
     tink_tests::expect_err(result, "decode failed");
@@ -84,3 +85,204 @@ fn test_binary_io_write_fail() {
     let result = w.write(&ks);
     tink_tests::expect_err(result, "write failed");
 }
+
+#[test]
+fn test_streaming_binary_io_large_keyset() {
+    tink_mac::init();
+
+    let mut manager = tink_core::keyset::Manager::new();
+    let kt = tink_mac::hmac_sha256_tag128_key_template();
+    for _ in 0..1000 {
+        manager.rotate(&kt).expect("cannot rotate keyset manager");
+    }
+    let h = manager.handle().expect("cannot get keyset handle");
+    let ks1 = tink_core::keyset::insecure::keyset_material(&h);
+
+    let mut buf = Vec::new();
+    tink_core::keyset::BinaryWriter::new(&mut buf)
+        .write(&ks1)
+        .expect("cannot write keyset");
+
+    // Use a small buffer so that keys straddle buffer boundaries.
+    let mut r = tink_core::keyset::StreamingBinaryReader::new(std::io::BufReader::with_capacity(
+        7,
+        &buf[..],
+    ));
+    let ks2 = r.read().expect("cannot read keyset");
+    assert_eq!(ks2.key.len(), 1000);
+    assert_eq!(ks1, ks2);
+    tink_core::keyset::validate(&ks2).expect("keyset is not valid");
+
+    let h2 = tink_core::keyset::insecure::read(&mut tink_core::keyset::StreamingBinaryReader::new(
+        &buf[..],
+    ))
+    .expect("cannot create handle");
+    let primitive = tink_mac::new(&h2).unwrap();
+    let tag = tink_mac::new(&h).unwrap().compute_mac(b"data").unwrap();
+    assert!(primitive.verify_mac(&tag, b"data").is_ok());
+}
+
+#[test]
+fn test_streaming_binary_io_read_fail() {
+    let mut r = tink_core::keyset::StreamingBinaryReader::new(std::io::BufReader::new(
+        tink_tests::IoFailure {},
+    ));
+    tink_tests::expect_err(r.read(), "read failed");
+
+    for (data, err) in &[
+        (&[0x08][..], "truncated input"),
+        (&[0x08, 0x80], "truncated varint"),
+        (&[0x12, 0x05, 0x00], "truncated input"),
+        (&[0x12, 0x02, 0xff, 0xff], "decode failed"),
+        (&[0x0a, 0x00], "invalid field tag"),
+        (&[0x1b], "unsupported wire type"),
+    ] {
+        let mut r = tink_core::keyset::StreamingBinaryReader::new(*data);
+        tink_tests::expect_err(r.read(), err);
+    }
+
+    // Unknown fields are skipped.
+    let mut r = tink_core::keyset::StreamingBinaryReader::new(
+        &[0x18, 0x01, 0x22, 0x01, 0x00, 0x08, 0x07][..],
+    );
+    assert_eq!(r.read().unwrap().primary_key_id, 7);
+}
+
+#[test]
+fn test_binary_io_read_limits() {
+    let key_data = tink_tests::new_key_data(
+        "some type url",
+        &[0x42; 100],
+        tink_proto::key_data::KeyMaterialType::Symmetric,
+    );
+    let key = tink_tests::new_key(
+        &key_data,
+        tink_proto::KeyStatusType::Enabled,
+        1,
+        tink_proto::OutputPrefixType::Tink,
+    );
+    let buf = tink_tests::proto_encode(&tink_tests::new_keyset(1, vec![key]));
+
+    let limits = tink_core::keyset::ReadLimits {
+        max_key_data_size: 99,
+        ..Default::default()
+    };
+    let mut r = tink_core::keyset::StreamingBinaryReader::new(&buf[..]).with_limits(limits);
+    tink_tests::expect_err(r.read(), "exceeding maximum of 99");
+    let mut r = tink_core::keyset::BinaryReader::new(&buf[..]).with_limits(limits);
+    tink_tests::expect_err(
+        tink_core::keyset::insecure::read(&mut r),
+        "exceeding maximum of 99",
+    );
+
+    let limits = tink_core::keyset::ReadLimits {
+        max_serialized_size: buf.len() - 1,
+        ..Default::default()
+    };
+    let mut r = tink_core::keyset::BinaryReader::new(&buf[..]).with_limits(limits);
+    tink_tests::expect_err(r.read(), "exceeds maximum size");
+    let mut r = tink_core::keyset::BinaryReader::new(&buf[..]).with_limits(limits);
+    tink_tests::expect_err(r.read_encrypted(), "exceeds maximum size");
+    let mut r = tink_core::keyset::StreamingBinaryReader::new(&buf[..]).with_limits(limits);
+    tink_tests::expect_err(r.read(), "exceeds maximum size");
+
+    let limits = tink_core::keyset::ReadLimits {
+        max_serialized_size: buf.len(),
+        max_key_data_size: 100,
+        max_keys: 1,
+    };
+    let mut r = tink_core::keyset::BinaryReader::new(&buf[..]).with_limits(limits);
+    assert!(tink_core::keyset::insecure::read(&mut r).is_ok());
+    let mut r = tink_core::keyset::StreamingBinaryReader::new(&buf[..]).with_limits(limits);
+    assert!(tink_core::keyset::insecure::read(&mut r).is_ok());
+
+    // An oversized key is rejected from its encoded length, before its contents are read.
+    let mut r = tink_core::keyset::StreamingBinaryReader::new(
+        &[0x12, 0xff, 0xff, 0xff, 0xff, 0x0f, 0x00][..],
+    );
+    tink_tests::expect_err(r.read(), "exceeding maximum");
+}
+
+fn arb_key() -> impl Strategy<Value = tink_proto::keyset::Key> {
+    (
+        proptest::option::of((
+            ".{0,40}",
+            proptest::collection::vec(any::<u8>(), 0..64),
+            any::<i32>(),
+        )),
+        any::<i32>(),
+        any::<u32>(),
+        any::<i32>(),
+    )
+        .prop_map(
+            |(key_data, status, key_id, output_prefix_type)| tink_proto::keyset::Key {
+                key_data: key_data.map(|(type_url, value, key_material_type)| {
+                    tink_proto::KeyData {
+                        type_url,
+                        value,
+                        key_material_type,
+                    }
+                }),
+                status,
+                key_id,
+                output_prefix_type,
+            },
+        )
+}
+
+fn arb_keyset() -> impl Strategy<Value = tink_proto::Keyset> {
+    (any::<u32>(), proptest::collection::vec(arb_key(), 0..8)).prop_map(|(primary_key_id, key)| {
+        tink_proto::Keyset {
+            primary_key_id,
+            key,
+        }
+    })
+}
+
+proptest! {
+    #[test]
+    fn test_binary_io_round_trip(ks1 in arb_keyset()) {
+        let mut buf = Vec::new();
+        tink_core::keyset::BinaryWriter::new(&mut buf)
+            .write(&ks1)
+            .expect("cannot write keyset");
+        let ks2 = tink_core::keyset::BinaryReader::new(&buf[..])
+            .read()
+            .expect("cannot read keyset");
+        prop_assert_eq!(&ks1, &ks2);
+
+        let mut buf2 = Vec::new();
+        tink_core::keyset::BinaryWriter::new(&mut buf2)
+            .write(&ks2)
+            .expect("cannot write keyset");
+        prop_assert_eq!(&buf, &buf2);
+
+        let ks3 = tink_core::keyset::StreamingBinaryReader::new(&buf[..])
+            .read()
+            .expect("cannot stream-read keyset");
+        prop_assert_eq!(&ks1, &ks3);
+    }
+
+    #[test]
+    fn test_binary_io_read_arbitrary(data in proptest::collection::vec(any::<u8>(), 0..256)) {
+        // Malformed input must produce an error rather than a panic.
+        if let Ok(ks) = tink_core::keyset::BinaryReader::new(&data[..]).read() {
+            let mut buf = Vec::new();
+            tink_core::keyset::BinaryWriter::new(&mut buf)
+                .write(&ks)
+                .expect("cannot write keyset");
+            let ks2 = tink_core::keyset::BinaryReader::new(&buf[..])
+                .read()
+                .expect("cannot re-read keyset");
+            prop_assert_eq!(ks, ks2);
+        }
+        let _ = tink_core::keyset::BinaryReader::new(&data[..]).read_encrypted();
+        if let Ok(ks) = tink_core::keyset::StreamingBinaryReader::new(&data[..]).read() {
+            // Anything the streaming reader accepts, the full decoder agrees with.
+            let ks2 = tink_core::keyset::BinaryReader::new(&data[..])
+                .read()
+                .expect("full decoder rejected keyset");
+            prop_assert_eq!(ks, ks2);
+        }
+    }
+}
@@ -0,0 +1,113 @@
+// Copyright 2020 The Tink-Rust Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+use tink_core::keyset::{insecure, HandleBuilder};
+use tink_proto::OutputPrefixType;
+
+#[test]
+fn test_builder_tink_primary_raw_fallback() {
+    tink_mac::init();
+    let tink_kt = tink_mac::hmac_sha256_tag256_key_template();
+    let mut raw_kt = tink_mac::hmac_sha256_tag256_key_template();
+    raw_kt.output_prefix_type = OutputPrefixType::Raw as i32;
+
+    let mut builder = HandleBuilder::new();
+    let primary_id = builder.add_generated_entry(&tink_kt, true).unwrap();
+    let raw_id = builder.add_generated_entry(&raw_kt, false).unwrap();
+    let h = builder.build().unwrap();
+
+    let ks = insecure::keyset_material(&h);
+    assert_eq!(ks.primary_key_id, primary_id);
+    assert_eq!(ks.key.len(), 2);
+    assert_eq!(ks.key[1].key_id, raw_id);
+    assert_eq!(ks.key[1].output_prefix_type, OutputPrefixType::Raw as i32);
+
+    // The primary key produces TINK-prefixed tags.
+    let mac = tink_mac::new(&h).unwrap();
+    let tag = mac.compute_mac(b"data").unwrap();
+    assert_eq!(tag.len(), tink_core::cryptofmt::TINK_PREFIX_SIZE + 32);
+    assert!(mac.verify_mac(&tag, b"data").is_ok());
+
+    // Tags from the RAW fallback key also verify.
+    let mut raw_only = HandleBuilder::new();
+    raw_only.add_entry(ks.key[1].clone(), true).unwrap();
+    let raw_mac = tink_mac::new(&raw_only.build().unwrap()).unwrap();
+    let raw_tag = raw_mac.compute_mac(b"data").unwrap();
+    assert_eq!(raw_tag.len(), 32);
+    assert!(mac.verify_mac(&raw_tag, b"data").is_ok());
+}
+
+#[test]
+fn test_builder_add_entry() {
+    tink_mac::init();
+    let kt = tink_mac::hmac_sha256_tag128_key_template();
+    let existing = insecure::keyset_material(&tink_core::keyset::Handle::new(&kt).unwrap());
+    let key = existing.key[0].clone();
+
+    let mut builder = HandleBuilder::new();
+    let key_id = builder.add_entry(key.clone(), true).unwrap();
+    assert_eq!(key_id, key.key_id);
+    builder.add_generated_entry(&kt, false).unwrap();
+
+    // Reusing a key ID is rejected.
+    tink_tests::expect_err(builder.add_entry(key.clone(), false), "duplicate key id");
+    // Invalid keys are rejected.
+    let mut no_data = key;
+    no_data.key_id += 1;
+    no_data.key_data = None;
+    tink_tests::expect_err(builder.add_entry(no_data, false), "no key data");
+
+    let ks = insecure::keyset_material(&builder.build().unwrap());
+    assert_eq!(ks.primary_key_id, key_id);
+    assert_eq!(ks.key.len(), 2);
+}
+
+#[test]
+fn test_builder_invalid() {
+    tink_mac::init();
+    // An empty keyset is invalid.
+    tink_tests::expect_err(HandleBuilder::new().build(), "empty keyset");
+
+    // A keyset with no primary key is invalid.
+    let mut builder = HandleBuilder::new();
+    builder
+        .add_generated_entry(&tink_mac::hmac_sha256_tag128_key_template(), false)
+        .unwrap();
+    tink_tests::expect_err(builder.build(), "primary key");
+}
+
+#[test]
+fn test_builder_last_primary_wins() {
+    tink_mac::init();
+    let kt = tink_mac::hmac_sha256_tag128_key_template();
+    let mut builder = HandleBuilder::new();
+    let first_id = builder.add_generated_entry(&kt, true).unwrap();
+    let second_id = builder.add_generated_entry(&kt, true).unwrap();
+    builder.add_generated_entry(&kt, false).unwrap();
+    let h = builder.build().unwrap();
+
+    let ks = insecure::keyset_material(&h);
+    assert_ne!(first_id, second_id);
+    assert_eq!(ks.primary_key_id, second_id);
+    assert!(tink_core::keyset::validate(&ks).is_ok());
+    assert_eq!(
+        ks.key
+            .iter()
+            .filter(|k| k.key_id == ks.primary_key_id)
+            .count(),
+        1
+    );
+}
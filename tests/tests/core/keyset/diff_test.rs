@@ -0,0 +1,55 @@
+// Copyright 2020 The Tink-Rust Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+use tink_core::keyset::{diff, Handle, KeysetDiff, Manager};
+use tink_proto::KeyStatusType;
+
+#[test]
+fn test_diff_rotate() {
+    tink_mac::init();
+    let kt = tink_mac::hmac_sha256_tag128_key_template();
+    let h1 = Handle::new(&kt).unwrap();
+    let info1 = h1.keyset_info();
+
+    let mut ksm = Manager::new_from_handle(h1);
+    let new_id = ksm.rotate(&kt).unwrap();
+    let info2 = ksm.handle().unwrap().keyset_info();
+
+    let d = diff(&info1, &info2);
+    assert_eq!(d.added, vec![new_id]);
+    assert!(d.removed.is_empty());
+    assert!(d.status_changes.is_empty());
+    assert_eq!(d.primary_change, Some((info1.primary_key_id, new_id)));
+    assert!(!d.is_empty());
+
+    // Disabling then deleting the old primary shows up as a status change and a removal.
+    let old_id = info1.primary_key_id;
+    ksm.disable(old_id).unwrap();
+    let info3 = ksm.handle().unwrap().keyset_info();
+    let d = diff(&info2, &info3);
+    assert_eq!(
+        d.status_changes,
+        vec![(old_id, KeyStatusType::Enabled, KeyStatusType::Disabled)]
+    );
+    assert!(d.added.is_empty() && d.removed.is_empty() && d.primary_change.is_none());
+
+    ksm.delete(old_id).unwrap();
+    let info4 = ksm.handle().unwrap().keyset_info();
+    assert_eq!(diff(&info3, &info4).removed, vec![old_id]);
+
+    assert_eq!(diff(&info4, &info4), KeysetDiff::default());
+    assert!(diff(&info4, &info4).is_empty());
+}
@@ -0,0 +1,66 @@
+// Copyright 2020 The Tink-Rust Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+use base64::Engine;
+use tink_core::keyset::{insecure, BinaryWriter};
+
+#[test]
+fn test_read_from_env() {
+    tink_mac::init();
+    let main_key = Box::new(tink_aead::subtle::AesGcm::new(&[b'A'; 32]).unwrap());
+    let h = tink_core::keyset::Handle::new(&tink_mac::hmac_sha256_tag128_key_template()).unwrap();
+
+    let mut buf = Vec::new();
+    h.write(&mut BinaryWriter::new(&mut buf), main_key.clone())
+        .expect("cannot write keyset");
+    let var_name = "TINK_TEST_READ_FROM_ENV_KEYSET";
+    std::env::set_var(
+        var_name,
+        base64::engine::general_purpose::STANDARD.encode(&buf),
+    );
+
+    let h2 = tink_core::keyset::read_from_env(var_name, main_key).expect("cannot read keyset");
+    assert_eq!(
+        insecure::keyset_material(&h),
+        insecure::keyset_material(&h2)
+    );
+}
+
+#[test]
+fn test_read_from_env_errors() {
+    let main_key = Box::new(tink_aead::subtle::AesGcm::new(&[b'A'; 32]).unwrap());
+
+    let var_name = "TINK_TEST_READ_FROM_ENV_MISSING";
+    std::env::remove_var(var_name);
+    tink_tests::expect_err(
+        tink_core::keyset::read_from_env(var_name, main_key.clone()),
+        "cannot read environment variable TINK_TEST_READ_FROM_ENV_MISSING",
+    );
+
+    let var_name = "TINK_TEST_READ_FROM_ENV_INVALID";
+    std::env::set_var(var_name, "not*base64!");
+    tink_tests::expect_err(
+        tink_core::keyset::read_from_env(var_name, main_key.clone()),
+        "TINK_TEST_READ_FROM_ENV_INVALID is not valid base64",
+    );
+
+    let var_name = "TINK_TEST_READ_FROM_ENV_GARBAGE";
+    std::env::set_var(var_name, "AAAA");
+    tink_tests::expect_err(
+        tink_core::keyset::read_from_env(var_name, main_key),
+        "cannot read keyset",
+    );
+}
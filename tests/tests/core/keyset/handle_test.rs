@@ -86,6 +86,24 @@ fn test_read() {
     );
 }
 
+#[test]
+fn test_write_with_kms_master_key() {
+    use tink_core::registry::KmsClient;
+    tink_mac::init();
+    let kms = tink_tests::DummyKmsClient;
+    let master_key: Arc<dyn tink_core::Aead> = Arc::from(kms.get_aead("dummy").unwrap());
+
+    let h = Handle::new(&tink_mac::hmac_sha256_tag128_key_template()).unwrap();
+    let mem_keyset = &mut tink_core::keyset::MemReaderWriter::default();
+    h.write(mem_keyset, master_key.box_clone())
+        .expect("cannot write keyset");
+    let h2 = Handle::read(mem_keyset, kms.get_aead("dummy").unwrap()).unwrap();
+    assert_eq!(
+        insecure::keyset_material(&h),
+        insecure::keyset_material(&h2)
+    );
+}
+
 #[test]
 fn test_read_with_associated_data() {
     let main_key = Box::new(tink_aead::subtle::AesGcm::new(&[b'A'; 32]).unwrap());
@@ -135,6 +153,48 @@ fn test_read_with_mismatched_associated_data() {
     tink_tests::expect_err(result, "decryption failed");
 }
 
+#[test]
+fn test_read_with_tampered_keyset_info() {
+    tink_mac::init();
+    let main_key = Box::new(tink_aead::subtle::AesGcm::new(&[b'A'; 32]).unwrap());
+    let mut ksm = tink_core::keyset::Manager::new();
+    let kt = tink_mac::hmac_sha256_tag128_key_template();
+    ksm.rotate(&kt).unwrap();
+    ksm.rotate(&kt).unwrap();
+    let h = ksm.handle().unwrap();
+
+    let mem_keyset = &mut tink_core::keyset::MemReaderWriter::default();
+    h.write(mem_keyset, main_key.clone()).unwrap();
+    assert!(Handle::read(mem_keyset, main_key.clone()).is_ok());
+    let original = mem_keyset.encrypted_keyset.clone().unwrap();
+
+    // Point the cleartext keyset info at the other key, leaving the ciphertext intact.
+    let mut tampered = original.clone();
+    let info = tampered.keyset_info.as_mut().unwrap();
+    info.primary_key_id = info
+        .key_info
+        .iter()
+        .map(|ki| ki.key_id)
+        .find(|id| *id != info.primary_key_id)
+        .unwrap();
+    mem_keyset.encrypted_keyset = Some(tampered);
+    let result = Handle::read(mem_keyset, main_key.clone());
+    tink_tests::expect_err(result, "primary key id");
+
+    // Dropping a key from the keyset info is also detected.
+    let mut tampered = original.clone();
+    tampered.keyset_info.as_mut().unwrap().key_info.pop();
+    mem_keyset.encrypted_keyset = Some(tampered);
+    let result = Handle::read(mem_keyset, main_key.clone());
+    tink_tests::expect_err(result, "key ids do not match");
+
+    // An absent keyset info is still accepted.
+    let mut stripped = original;
+    stripped.keyset_info = None;
+    mem_keyset.encrypted_keyset = Some(stripped);
+    assert!(Handle::read(mem_keyset, main_key).is_ok());
+}
+
 #[test]
 fn test_read_with_no_secrets() {
     // Create a keyset containing public key material
@@ -160,6 +220,34 @@ fn test_read_with_no_secrets() {
     );
 }
 
+#[test]
+fn test_read_with_no_secrets_public_ecdsa_keyset() {
+    tink_signature::init();
+    let kh_priv = Handle::new(&tink_signature::ecdsa_p256_key_template()).unwrap();
+    let kh_pub = kh_priv.public().unwrap();
+
+    let mut mem_keyset = tink_core::keyset::MemReaderWriter::default();
+    kh_pub.write_with_no_secrets(&mut mem_keyset).unwrap();
+    let kh_read = Handle::read_with_no_secrets(&mut mem_keyset).unwrap();
+
+    let signer = tink_signature::new_signer(&kh_priv).unwrap();
+    let verifier = tink_signature::new_verifier(&kh_read).unwrap();
+    let sig = signer.sign(b"data").unwrap();
+    assert!(verifier.verify(&sig, b"data").is_ok());
+
+    // A symmetric keyset is rejected.
+    tink_mac::init();
+    let kh_sym = Handle::new(&tink_mac::hmac_sha256_tag128_key_template()).unwrap();
+    let result = Handle::read_with_no_secrets(&mut tink_core::keyset::MemReaderWriter {
+        keyset: Some(insecure::keyset_material(&kh_sym)),
+        ..Default::default()
+    });
+    tink_tests::expect_err(
+        result,
+        "importing unencrypted secret key material is forbidden",
+    );
+}
+
 #[test]
 fn test_with_no_secrets_functions_fail_when_handling_secret_key_material() {
     // Create a keyset containing secret key material (symmetric)
@@ -273,6 +361,103 @@ fn test_invalid_keyset() {
     assert!(insecure::new_handle(invalid_ks).is_err());
 }
 
+#[test]
+fn test_handle_validate() {
+    tink_mac::init();
+    let kt = tink_mac::hmac_sha256_tag128_key_template();
+    let mut ksm = tink_core::keyset::Manager::new();
+    ksm.rotate(&kt).unwrap();
+    ksm.rotate(&kt).unwrap();
+    let kh = ksm.handle().unwrap();
+    assert!(kh.validate().is_ok());
+
+    // Disabling the primary key leaves other enabled keys, but no usable primary.
+    let mut ks = insecure::keyset_material(&kh);
+    let primary_key_id = ks.primary_key_id;
+    for key in ks.key.iter_mut() {
+        if key.key_id == primary_key_id {
+            key.status = tink_proto::KeyStatusType::Disabled as i32;
+        }
+    }
+    let kh = insecure::new_handle(ks).unwrap();
+    tink_tests::expect_err(kh.validate(), "does not contain a valid primary key");
+}
+
+#[test]
+fn test_handle_key_counts() {
+    tink_aead::init();
+    let ks = tink_tests::new_test_aes_gcm_keyset(tink_proto::OutputPrefixType::Tink);
+    let kh = insecure::new_handle(ks).unwrap();
+    assert_eq!(kh.key_count(), 5);
+    assert_eq!(kh.enabled_key_count(), 5);
+
+    let mut ksm = tink_core::keyset::Manager::new_from_handle(kh);
+    ksm.disable(43).unwrap();
+    let kh = ksm.handle().unwrap();
+    assert_eq!(kh.key_count(), 5);
+    assert_eq!(kh.enabled_key_count(), 4);
+
+    ksm.destroy(44).unwrap();
+    let kh = ksm.handle().unwrap();
+    assert_eq!(kh.key_count(), 5);
+    assert_eq!(kh.enabled_key_count(), 3);
+
+    ksm.delete(43).unwrap();
+    let kh = ksm.handle().unwrap();
+    assert_eq!(kh.key_count(), 4);
+    assert_eq!(kh.enabled_key_count(), 3);
+}
+
+#[test]
+fn test_handle_from_single_key() {
+    tink_aead::init();
+    let key = tink_tests::new_aes_gcm_key(tink_tests::AES_GCM_KEY_VERSION, 16);
+    let key_data = tink_tests::new_key_data(
+        tink_tests::AES_GCM_TYPE_URL,
+        &tink_tests::proto_encode(&key),
+        KeyMaterialType::Symmetric,
+    );
+
+    // A RAW key produces ciphertexts that the bare key can decrypt.
+    let kh = tink_core::keyset::handle_from_single_key(
+        key_data.clone(),
+        tink_proto::OutputPrefixType::Raw,
+    )
+    .unwrap();
+    assert_eq!(kh.key_count(), 1);
+    let info = kh.keyset_info();
+    assert_ne!(info.primary_key_id, 0);
+    assert_eq!(info.key_info[0].key_id, info.primary_key_id);
+    let a = tink_aead::new(&kh).unwrap();
+    let ct = a.encrypt(b"plaintext", b"aad").unwrap();
+    let subtle_aead = tink_aead::subtle::AesGcm::new(&key.key_value).unwrap();
+    assert_eq!(
+        tink_core::Aead::decrypt(&subtle_aead, &ct, b"aad").unwrap(),
+        b"plaintext"
+    );
+
+    // A TINK key prefixes ciphertexts with its (random) key ID.
+    let kh = tink_core::keyset::handle_from_single_key(
+        key_data.clone(),
+        tink_proto::OutputPrefixType::Tink,
+    )
+    .unwrap();
+    let key_id = kh.keyset_info().primary_key_id;
+    let a = tink_aead::new(&kh).unwrap();
+    let ct = a.encrypt(b"plaintext", b"aad").unwrap();
+    assert_eq!(ct[0], tink_core::cryptofmt::TINK_START_BYTE);
+    assert_eq!(ct[1..5], key_id.to_be_bytes());
+    assert_eq!(a.decrypt(&ct, b"aad").unwrap(), b"plaintext");
+
+    tink_tests::expect_err(
+        tink_core::keyset::handle_from_single_key(
+            key_data,
+            tink_proto::OutputPrefixType::UnknownPrefix,
+        ),
+        "unknown prefix",
+    );
+}
+
 #[test]
 fn test_invalid_keyset_from_manager() {
     // Use a key manager that generates invalid `KeyData`.
@@ -329,6 +514,35 @@ fn test_destroyed_key_keyset() {
     assert_eq!(info.key_info[0].type_url, "");
 }
 
+#[test]
+fn test_primitives_skip_destroyed_key() {
+    tink_mac::init();
+    let mut ksm = tink_core::keyset::Manager::new();
+    let destroyed_id = ksm
+        .rotate(&tink_mac::hmac_sha256_tag128_key_template())
+        .unwrap();
+    let enabled_id = ksm
+        .rotate(&tink_mac::hmac_sha256_tag256_key_template())
+        .unwrap();
+    ksm.destroy(destroyed_id).unwrap();
+    let kh = ksm.handle().unwrap();
+    let ks = insecure::keyset_material(&kh);
+    assert!(ks
+        .key
+        .iter()
+        .any(|k| k.key_id == destroyed_id && k.key_data.is_none()));
+
+    let ps = kh.primitives().expect("cannot build primitive set");
+    let entries: Vec<_> = ps.entries.values().flatten().collect();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].key_id, enabled_id);
+    assert_eq!(ps.primary.as_ref().unwrap().key_id, enabled_id);
+
+    let mac = tink_mac::new(&kh).unwrap();
+    let tag = mac.compute_mac(b"data").unwrap();
+    assert!(mac.verify_mac(&tag, b"data").is_ok());
+}
+
 #[test]
 fn test_handle_public() {
     tink_signature::init();
@@ -420,3 +634,50 @@ fn test_insecure_read_empty() {
     let result = insecure::read(&mut mem_keyset);
     tink_tests::expect_err(result, "insecure: invalid keyset");
 }
+
+fn keyset_with_key_data_size(size: usize) -> tink_proto::Keyset {
+    let key_data = tink_tests::new_key_data(
+        "some type url",
+        &vec![0x42; size],
+        KeyMaterialType::Symmetric,
+    );
+    let key = tink_tests::new_key(
+        &key_data,
+        tink_proto::KeyStatusType::Enabled,
+        1,
+        tink_proto::OutputPrefixType::Tink,
+    );
+    tink_tests::new_keyset(1, vec![key])
+}
+
+#[test]
+fn test_read_oversized_key_data() {
+    let max = tink_core::keyset::DEFAULT_MAX_KEY_DATA_SIZE;
+
+    let buf = tink_tests::proto_encode(&keyset_with_key_data_size(max + 1));
+    let mut r = tink_core::keyset::BinaryReader::new(std::io::Cursor::new(buf));
+    let result = insecure::read(&mut r);
+    tink_tests::expect_err(result, "exceeding maximum");
+
+    let buf = tink_tests::proto_encode(&keyset_with_key_data_size(max));
+    let mut r = tink_core::keyset::BinaryReader::new(std::io::Cursor::new(buf));
+    assert!(insecure::read(&mut r).is_ok());
+
+    // The limit also applies to encrypted keysets.
+    let main_key = Box::new(tink_aead::subtle::AesGcm::new(&[b'A'; 32]).unwrap());
+    let encrypted = tink_proto::EncryptedKeyset {
+        encrypted_keyset: tink_core::Aead::encrypt(
+            main_key.as_ref(),
+            &tink_tests::proto_encode(&keyset_with_key_data_size(max + 1)),
+            &[],
+        )
+        .unwrap(),
+        keyset_info: None,
+    };
+    let mut mem_keyset = tink_core::keyset::MemReaderWriter {
+        encrypted_keyset: Some(encrypted),
+        ..Default::default()
+    };
+    let result = Handle::read(&mut mem_keyset, main_key);
+    tink_tests::expect_err(result, "exceeding maximum");
+}
@@ -250,11 +250,11 @@ fn test_json_io_encrypted() {
 #[test]
 fn test_json_io_read_fail_decode() {
     tink_mac::init();
-    let buf = vec![1, 2, 3];
+    let buf = [1, 2, 3];
     let mut r = tink_core::keyset::JsonReader::new(&buf[..]);
     assert!(r.read().is_err());
 
-    let buf = vec![1, 2, 3];
+    let buf = [1, 2, 3];
     let mut r = tink_core::keyset::JsonReader::new(&buf[..]);
     assert!(r.read_encrypted().is_err());
 }
@@ -410,3 +410,37 @@ fn test_json_read_invalid_b64() {
     let result = r.read();
     tink_tests::expect_err(result, "base64");
 }
+
+#[test]
+fn test_write_no_secret_json() {
+    tink_signature::init();
+    let priv_kh =
+        tink_core::keyset::Handle::new(&tink_signature::ecdsa_p256_key_template()).unwrap();
+    let pub_kh = priv_kh.public().unwrap();
+
+    let mut buf = Vec::new();
+    pub_kh.write_no_secret_json(&mut buf).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&buf).expect("output is not JSON");
+    assert_eq!(
+        json["key"][0]["keyData"]["keyMaterialType"],
+        "ASYMMETRIC_PUBLIC"
+    );
+
+    let mut r = tink_core::keyset::JsonReader::new(&buf[..]);
+    let read_kh = tink_core::keyset::Handle::read_with_no_secrets(&mut r).unwrap();
+    let data = b"data to be signed";
+    let sig = tink_signature::new_signer(&priv_kh)
+        .unwrap()
+        .sign(data)
+        .unwrap();
+    let verifier = tink_signature::new_verifier(&read_kh).unwrap();
+    assert!(verifier.verify(&sig, data).is_ok());
+
+    // Keysets with secret key material are rejected, and nothing is written.
+    let mut buf = Vec::new();
+    tink_tests::expect_err(
+        priv_kh.write_no_secret_json(&mut buf),
+        "exporting unencrypted secret key material is forbidden",
+    );
+    assert!(buf.is_empty());
+}
@@ -43,6 +43,134 @@ fn test_keyset_manager_basic() {
     );
 }
 
+#[test]
+fn test_new_hmac_keyset_manager() {
+    tink_mac::init();
+    let ksm = tink_tests::new_hmac_keyset_manager();
+    let h = ksm.handle().expect("cannot get keyset handle");
+    let ks = insecure::keyset_material(&h);
+    assert_eq!(1, ks.key.len());
+    let key = &ks.key[0];
+    assert_eq!(key.key_id, ks.primary_key_id);
+    assert_eq!(key.status, tink_proto::KeyStatusType::Enabled as i32);
+    assert_eq!(
+        key.key_data.as_ref().unwrap().type_url,
+        tink_tests::HMAC_TYPE_URL
+    );
+
+    let mac = tink_mac::new(&h).expect("cannot get MAC primitive");
+    let tag = mac.compute_mac(b"data").unwrap();
+    assert!(mac.verify_mac(&tag, b"data").is_ok());
+    assert!(mac.verify_mac(&tag, b"other data").is_err());
+}
+
+#[test]
+fn test_keyset_manager_merge() {
+    tink_aead::init();
+    tink_mac::init();
+    let mut ksm = tink_core::keyset::Manager::new();
+    let aes_id = ksm
+        .rotate(&tink_aead::aes128_gcm_key_template())
+        .expect("cannot rotate");
+
+    // HMAC keyset with one key whose ID collides with the AES-GCM key.
+    let hmac_data = tink_tests::new_hmac_key_data(tink_proto::HashType::Sha256, 16);
+    let hmac_keyset = tink_tests::new_keyset(
+        aes_id,
+        vec![
+            tink_tests::new_key(
+                &hmac_data,
+                tink_proto::KeyStatusType::Enabled,
+                aes_id,
+                tink_proto::OutputPrefixType::Tink,
+            ),
+            tink_tests::new_key(
+                &hmac_data,
+                tink_proto::KeyStatusType::Enabled,
+                aes_id.wrapping_add(1),
+                tink_proto::OutputPrefixType::Tink,
+            ),
+        ],
+    );
+    let merged_ids = ksm
+        .merge(insecure::new_handle(hmac_keyset).unwrap())
+        .expect("cannot merge");
+    assert_eq!(merged_ids.len(), 2);
+    assert_ne!(merged_ids[0], aes_id);
+    assert_eq!(merged_ids[1], aes_id.wrapping_add(1));
+
+    let h = ksm.handle().expect("cannot get keyset handle");
+    let ks = insecure::keyset_material(&h);
+    assert_eq!(ks.primary_key_id, aes_id);
+    assert_eq!(ks.key.len(), 3);
+    let ids: std::collections::HashSet<_> = ks.key.iter().map(|k| k.key_id).collect();
+    assert_eq!(ids.len(), 3, "key IDs not distinct");
+    let type_urls: Vec<_> = ks
+        .key
+        .iter()
+        .map(|k| k.key_data.as_ref().unwrap().type_url.as_str())
+        .collect();
+    assert_eq!(
+        type_urls,
+        vec![
+            tink_tests::AES_GCM_TYPE_URL,
+            tink_tests::HMAC_TYPE_URL,
+            tink_tests::HMAC_TYPE_URL
+        ]
+    );
+
+    // Unsupported key types are rejected without modifying the keyset.
+    let unknown_data = tink_tests::new_key_data(
+        "some unknown type url",
+        b"value",
+        tink_proto::key_data::KeyMaterialType::Symmetric,
+    );
+    let unknown_keyset = tink_tests::new_keyset(
+        1,
+        vec![tink_tests::new_key(
+            &unknown_data,
+            tink_proto::KeyStatusType::Enabled,
+            1,
+            tink_proto::OutputPrefixType::Tink,
+        )],
+    );
+    tink_tests::expect_err(
+        ksm.merge(insecure::new_handle(unknown_keyset).unwrap()),
+        "unsupported key type",
+    );
+    assert_eq!(ksm.key_count(), 3);
+}
+
+#[test]
+fn test_keyset_manager_merge_shared_key_ids() {
+    tink_aead::init();
+    let mut ksm = tink_core::keyset::Manager::new();
+    for _ in 0..3 {
+        ksm.rotate(&tink_aead::aes128_gcm_key_template()).unwrap();
+    }
+    let h = ksm.handle().unwrap();
+    let original_ids: Vec<_> = h.keyset_info().key_info.iter().map(|k| k.key_id).collect();
+
+    // Merging a keyset into itself means every incoming key ID is shared.
+    let mut merged = tink_core::keyset::Manager::new_from_handle(ksm.handle().unwrap());
+    let merged_ids = merged.merge(h).unwrap();
+    assert_eq!(merged_ids.len(), 3);
+    for id in &merged_ids {
+        assert!(!original_ids.contains(id), "key ID {} not reassigned", id);
+    }
+
+    let ks = insecure::keyset_material(&merged.handle().unwrap());
+    assert_eq!(ks.key.len(), 6);
+    let ids: std::collections::HashSet<_> = ks.key.iter().map(|k| k.key_id).collect();
+    assert_eq!(ids.len(), 6, "key IDs not distinct");
+
+    // Keys added afterwards also get distinct IDs.
+    let new_id = merged
+        .add(&tink_aead::aes128_gcm_key_template(), false)
+        .unwrap();
+    assert!(!ids.contains(&new_id));
+}
+
 #[test]
 fn test_keyset_manager_operations() {
     tink_aead::init();
@@ -383,3 +511,67 @@ fn test_unknown_output_prefix_type_fails() {
     let result = ksm1.rotate(&kt);
     tink_tests::expect_err(result, "unknown output prefix type");
 }
+
+#[test]
+fn test_manager_from_handle_rotate() {
+    tink_mac::init();
+    let ks = tink_tests::new_test_hmac_keyset(16, tink_proto::OutputPrefixType::Tink);
+    let old_primary_id = ks.primary_key_id;
+    let h = insecure::new_handle(ks).expect("cannot create keyset handle");
+
+    let mut ksm = tink_core::keyset::Manager::new_from_handle(h);
+    let new_primary_id = ksm
+        .rotate(&tink_mac::hmac_sha256_tag256_key_template())
+        .expect("failed to rotate");
+    let h2 = ksm.handle().expect("cannot get keyset handle");
+    let ks2 = insecure::keyset_material(&h2);
+
+    assert_ne!(old_primary_id, new_primary_id);
+    assert_eq!(ks2.primary_key_id, new_primary_id);
+    let old_primary = ks2
+        .key
+        .iter()
+        .find(|k| k.key_id == old_primary_id)
+        .expect("old primary key missing");
+    assert_eq!(
+        old_primary.status,
+        tink_proto::KeyStatusType::Enabled as i32
+    );
+
+    let mac = tink_mac::new(&h2).expect("cannot get MAC primitive");
+    let tag = mac.compute_mac(b"data").unwrap();
+    assert!(mac.verify_mac(&tag, b"data").is_ok());
+}
+
+#[test]
+fn test_manager_add_honors_template_output_prefix() {
+    tink_aead::init();
+    tink_streaming_aead::init();
+    let mut ksm = tink_core::keyset::Manager::new();
+    let key_id = ksm
+        .add(&tink_aead::aes256_gcm_no_prefix_key_template(), true)
+        .unwrap();
+    let streaming_key_id = ksm
+        .add(
+            &tink_streaming_aead::aes128_gcm_hkdf_4kb_key_template(),
+            false,
+        )
+        .unwrap();
+    let h = ksm.handle().unwrap();
+    let ks = insecure::keyset_material(&h);
+    for id in [key_id, streaming_key_id] {
+        let key = ks.key.iter().find(|k| k.key_id == id).unwrap();
+        assert_eq!(
+            key.output_prefix_type,
+            tink_proto::OutputPrefixType::Raw as i32
+        );
+    }
+
+    // Ciphertexts from the RAW primary carry no key ID prefix.
+    let mut ksm = tink_core::keyset::Manager::new();
+    ksm.rotate(&tink_aead::aes256_gcm_no_prefix_key_template())
+        .unwrap();
+    let a = tink_aead::new(&ksm.handle().unwrap()).unwrap();
+    let ct = a.encrypt(b"plaintext", b"").unwrap();
+    assert_eq!(ct.len(), 12 + b"plaintext".len() + 16);
+}
@@ -15,7 +15,11 @@
 ////////////////////////////////////////////////////////////////////////////////
 
 mod binary_io_test;
+mod builder_test;
+mod diff_test;
+mod env_test;
 mod handle_test;
 mod json_io_test;
 mod manager_test;
+mod serializable_test;
 mod validation_test;
@@ -0,0 +1,137 @@
+// Copyright 2020 The Tink-Rust Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+use std::convert::TryFrom;
+use tink_core::keyset::{Handle, SerializableKeyset};
+use tink_proto::{key_data::KeyMaterialType, KeyStatusType, OutputPrefixType};
+
+#[test]
+fn test_serializable_keyset_public_round_trip() {
+    tink_signature::init();
+    let priv_kh = Handle::new(&tink_signature::ecdsa_p256_key_template()).unwrap();
+    let pub_kh = priv_kh.public().unwrap();
+
+    let dto = SerializableKeyset::try_from(&pub_kh).unwrap();
+    assert_eq!(dto.key.len(), 1);
+    assert_eq!(dto.key[0].status, KeyStatusType::Enabled as i32);
+    assert_eq!(dto.key[0].output_prefix_type, OutputPrefixType::Tink as i32);
+    assert_eq!(
+        dto.key[0].key_data.key_material_type,
+        KeyMaterialType::AsymmetricPublic as i32
+    );
+
+    // The serialized form matches the Tink JSON keyset format.
+    let json = serde_json::to_value(&dto).unwrap();
+    let mut buf = Vec::new();
+    pub_kh.write_no_secret_json(&mut buf).unwrap();
+    let want: serde_json::Value = serde_json::from_slice(&buf).unwrap();
+    assert_eq!(json, want);
+
+    let dto2: SerializableKeyset = serde_json::from_value(json).unwrap();
+    assert_eq!(dto2, dto);
+    let pub_kh2 = Handle::try_from(&dto2).unwrap();
+    assert_eq!(
+        tink_core::keyset::insecure::keyset_material(&pub_kh2),
+        tink_core::keyset::insecure::keyset_material(&pub_kh)
+    );
+
+    // The round-tripped handle verifies signatures from the original private key.
+    let sig = tink_signature::sign(&priv_kh, b"data").unwrap();
+    tink_signature::verify(&pub_kh2, &sig, b"data").unwrap();
+}
+
+#[test]
+fn test_serializable_keyset_secret_material() {
+    tink_signature::init();
+    let priv_kh = Handle::new(&tink_signature::ecdsa_p256_key_template()).unwrap();
+    tink_tests::expect_err(
+        SerializableKeyset::try_from(&priv_kh),
+        "secret key material is forbidden",
+    );
+
+    let ks = tink_core::keyset::insecure::keyset_material(&priv_kh);
+    let dto = SerializableKeyset::from(&ks);
+    tink_tests::expect_err(Handle::try_from(&dto), "secret key material is forbidden");
+}
+
+#[test]
+fn test_serializable_keyset_insecure() {
+    tink_signature::init();
+    let priv_kh = Handle::new(&tink_signature::ecdsa_p256_key_template()).unwrap();
+    let ks = tink_core::keyset::insecure::keyset_material(&priv_kh);
+    let mut dto = SerializableKeyset::from(&ks);
+
+    // The `insecure` flag is needed to import secret key material, and is never serialized.
+    dto.insecure = true;
+    let kh = Handle::try_from(&dto).unwrap();
+    assert_eq!(tink_core::keyset::insecure::keyset_material(&kh), ks);
+    let json = serde_json::to_string(&dto).unwrap();
+    assert!(!json.contains("insecure"));
+    let dto2: SerializableKeyset = serde_json::from_str(&json).unwrap();
+    assert!(!dto2.insecure);
+}
+
+#[test]
+fn test_serializable_keyset_invalid() {
+    tink_signature::init();
+    let pub_kh = Handle::new(&tink_signature::ecdsa_p256_key_template())
+        .unwrap()
+        .public()
+        .unwrap();
+    let dto = SerializableKeyset::try_from(&pub_kh).unwrap();
+
+    // Unrecognized enum names deserialize to the unknown value, which is then rejected.
+    let json = serde_json::to_value(&dto).unwrap();
+    let mut bad = json.clone();
+    bad["key"][0]["status"] = "ACTIVE".into();
+    let bad: SerializableKeyset = serde_json::from_value(bad).unwrap();
+    assert_eq!(bad.key[0].status, KeyStatusType::UnknownStatus as i32);
+    tink_tests::expect_err(Handle::try_from(&bad), "unknown status");
+
+    let mut bad = json.clone();
+    bad["key"][0]["outputPrefixType"] = "TINKY".into();
+    let bad: SerializableKeyset = serde_json::from_value(bad).unwrap();
+    tink_tests::expect_err(Handle::try_from(&bad), "unknown prefix");
+
+    let mut bad = json.clone();
+    bad["key"][0]["keyData"]["keyMaterialType"] = "PUBLIC".into();
+    let bad: SerializableKeyset = serde_json::from_value(bad).unwrap();
+    assert!(Handle::try_from(&bad).is_err());
+
+    let mut bad = json;
+    bad["key"][0]["keyData"]["value"] = "not base64!".into();
+    assert!(serde_json::from_value::<SerializableKeyset>(bad).is_err());
+}
+
+#[test]
+fn test_serializable_keyset_unknown_enum_round_trip() {
+    // Enum values are held as-is, so conversion to and from a keyset is lossless.
+    let ks = tink_proto::Keyset {
+        primary_key_id: 1,
+        key: vec![tink_proto::keyset::Key {
+            key_data: Some(tink_proto::KeyData {
+                type_url: "some url".to_string(),
+                value: vec![1, 2, 3],
+                key_material_type: 99,
+            }),
+            status: 99,
+            key_id: 1,
+            output_prefix_type: 99,
+        }],
+    };
+    let dto = SerializableKeyset::from(&ks);
+    assert_eq!(tink_proto::Keyset::from(&dto), ks);
+}
@@ -14,7 +14,10 @@
 //
 ////////////////////////////////////////////////////////////////////////////////
 
-use tink_core::{keyset, subtle::random::get_random_bytes};
+use tink_core::{
+    keyset::{self, Reader, Writer},
+    subtle::random::get_random_bytes,
+};
 
 #[test]
 fn test_validate_key_version() {
@@ -145,6 +148,17 @@ fn test_validate() {
     );
 }
 
+#[test]
+fn test_validate_destroyed_key_without_key_data() {
+    let key = tink_proto::keyset::Key {
+        key_data: None,
+        status: tink_proto::KeyStatusType::Destroyed as i32,
+        key_id: 1,
+        output_prefix_type: tink_proto::OutputPrefixType::Tink as i32,
+    };
+    assert!(keyset::validate_key(&key).is_ok());
+}
+
 fn generate_invalid_keys() -> Vec<tink_proto::keyset::Key> {
     vec![
         // unknown status
@@ -177,3 +191,85 @@ fn generate_invalid_keys() -> Vec<tink_proto::keyset::Key> {
         },
     ]
 }
+
+#[test]
+fn test_validate_key_data_size() {
+    let key_data = tink_tests::new_key_data(
+        "some type url",
+        &[0; 16],
+        tink_proto::key_data::KeyMaterialType::Symmetric,
+    );
+    let key = tink_tests::new_key(
+        &key_data,
+        tink_proto::KeyStatusType::Enabled,
+        1,
+        tink_proto::OutputPrefixType::Tink,
+    );
+    let ks = tink_tests::new_keyset(1, vec![key]);
+    assert!(keyset::validate_key_data_size(&ks, 16).is_ok());
+    assert!(keyset::validate_key_data_size(&ks, 15).is_err());
+}
+
+#[test]
+fn test_validate_key_count() {
+    let keys: Vec<_> = (1..=3)
+        .map(|key_id| {
+            tink_tests::new_key(
+                &tink_tests::new_aes_gcm_key_data(16),
+                tink_proto::KeyStatusType::Enabled,
+                key_id,
+                tink_proto::OutputPrefixType::Tink,
+            )
+        })
+        .collect();
+    let ks = tink_tests::new_keyset(1, keys);
+    assert!(keyset::validate_key_count(&ks, 3).is_ok());
+    tink_tests::expect_err(keyset::validate_key_count(&ks, 2), "exceeding maximum of 2");
+}
+
+#[test]
+fn test_read_keyset_key_count_limit() {
+    let key_data = tink_tests::new_key_data(
+        "some type url",
+        &[0; 16],
+        tink_proto::key_data::KeyMaterialType::AsymmetricPublic,
+    );
+    let max = keyset::DEFAULT_MAX_KEYSET_KEYS;
+    let keys: Vec<_> = (1..=max as u32 + 1)
+        .map(|key_id| {
+            tink_tests::new_key(
+                &key_data,
+                tink_proto::KeyStatusType::Enabled,
+                key_id,
+                tink_proto::OutputPrefixType::Tink,
+            )
+        })
+        .collect();
+    let read = |keys: &[tink_proto::keyset::Key]| {
+        let ks = tink_tests::new_keyset(1, keys.to_vec());
+        let mut buf = Vec::new();
+        keyset::BinaryWriter::new(&mut buf).write(&ks).unwrap();
+        keyset::Handle::read_with_no_secrets(&mut keyset::BinaryReader::new(&buf[..]))
+    };
+
+    let kh = read(&keys[..max]).unwrap();
+    assert_eq!(kh.key_count(), max);
+    tink_tests::expect_err(read(&keys), "exceeding maximum of 10000");
+
+    // The streaming reader stops as soon as the limit is passed.
+    let mut buf = Vec::new();
+    keyset::BinaryWriter::new(&mut buf)
+        .write(&tink_tests::new_keyset(1, keys[..3].to_vec()))
+        .unwrap();
+    let limits = keyset::ReadLimits {
+        max_keys: 2,
+        ..Default::default()
+    };
+    let mut r = keyset::StreamingBinaryReader::new(&buf[..]).with_limits(limits);
+    tink_tests::expect_err(r.read(), "exceeding maximum of 2");
+    let mut r = keyset::BinaryReader::new(&buf[..]).with_limits(limits);
+    tink_tests::expect_err(
+        keyset::Handle::read_with_no_secrets(&mut r),
+        "exceeding maximum of 2",
+    );
+}
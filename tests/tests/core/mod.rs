@@ -18,3 +18,4 @@ mod keyset;
 mod primitiveset;
 mod registry;
 mod subtle;
+mod utils_test;
@@ -14,7 +14,11 @@
 //
 ////////////////////////////////////////////////////////////////////////////////
 
-use std::sync::Arc;
+use std::{
+    io::{Read, Write},
+    sync::Arc,
+};
+use tink_core::{Mac, TinkError};
 use tink_proto::prost::Message;
 use tink_tests::fakekms;
 
@@ -61,6 +65,136 @@ fn test_register_key_manager_duplicate() {
     );
 }
 
+#[test]
+fn test_key_manager_type_urls() {
+    tink_mac::init();
+    let dummy_key_manager = Arc::new(tink_tests::DummyAeadKeyManager {
+        type_url: "type.googleapis.com/google.crypto.tink.DummyListedKey",
+    });
+    tink_core::registry::register_key_manager(dummy_key_manager).unwrap();
+
+    let type_urls = tink_core::registry::key_manager_type_urls();
+    let mut sorted = type_urls.clone();
+    sorted.sort();
+    assert_eq!(type_urls, sorted);
+    assert!(type_urls
+        .iter()
+        .any(|u| u == "type.googleapis.com/google.crypto.tink.DummyListedKey"));
+    assert!(type_urls.iter().any(|u| u == tink_tests::HMAC_TYPE_URL));
+    assert!(!type_urls.iter().any(|u| u == "some url"));
+}
+
+#[test]
+fn test_primitive_wrapper() {
+    tink_mac::init();
+    tink_streaming_aead::init();
+    tink_tests::expect_err(
+        tink_core::registry::register_primitive_wrapper(Arc::new(tink_mac::MacWrapper)),
+        "already registered",
+    );
+
+    let kh = tink_core::keyset::Handle::new(&tink_mac::hmac_sha256_tag128_key_template()).unwrap();
+    let mac = kh.primitive::<dyn Mac>().unwrap();
+    let tag = mac.compute_mac(b"data").unwrap();
+    // The wrapped MAC includes the output prefix, just as for `tink_mac::new`.
+    assert_eq!(
+        tag,
+        tink_mac::new(&kh).unwrap().compute_mac(b"data").unwrap()
+    );
+    assert!(mac.verify_mac(&tag, b"data").is_ok());
+
+    let kh =
+        tink_core::keyset::Handle::new(&tink_streaming_aead::aes128_gcm_hkdf_4kb_key_template())
+            .unwrap();
+    let streaming = kh.primitive::<dyn tink_core::StreamingAead>().unwrap();
+    let ct = streaming.encrypt_all(b"data", b"aad").unwrap();
+    assert_eq!(
+        tink_streaming_aead::new(&kh)
+            .unwrap()
+            .decrypt_all(&ct, b"aad")
+            .unwrap(),
+        b"data"
+    );
+}
+
+#[test]
+fn test_primitive_wrappers_for_all_types() {
+    tink_daead::init();
+    tink_hybrid::init();
+    tink_prf::init();
+    tink_signature::init();
+
+    let kh = tink_core::keyset::Handle::new(&tink_daead::aes_siv_key_template()).unwrap();
+    let daead = kh.primitive::<dyn tink_core::DeterministicAead>().unwrap();
+    let ct = daead.encrypt_deterministically(b"data", b"aad").unwrap();
+    assert_eq!(
+        tink_daead::new(&kh)
+            .unwrap()
+            .decrypt_deterministically(&ct, b"aad")
+            .unwrap(),
+        b"data"
+    );
+
+    let kh =
+        tink_core::keyset::Handle::new(&tink_hybrid::ecies_hkdf_aes128_gcm_key_template()).unwrap();
+    let enc = kh
+        .public()
+        .unwrap()
+        .primitive::<dyn tink_core::HybridEncrypt>()
+        .unwrap();
+    let dec = kh.primitive::<dyn tink_core::HybridDecrypt>().unwrap();
+    let ct = enc.encrypt(b"data", b"context").unwrap();
+    assert_eq!(dec.decrypt(&ct, b"context").unwrap(), b"data");
+
+    let kh = tink_core::keyset::Handle::new(&tink_prf::hmac_sha256_prf_key_template()).unwrap();
+    let prf = kh.primitive::<dyn tink_core::Prf>().unwrap();
+    assert_eq!(
+        prf.compute_prf(b"data", 16).unwrap(),
+        tink_prf::Set::new(&kh)
+            .unwrap()
+            .compute_primary_prf(b"data", 16)
+            .unwrap()
+    );
+
+    let kh = tink_core::keyset::Handle::new(&tink_signature::ecdsa_p256_key_template()).unwrap();
+    let signer = kh.primitive::<dyn tink_core::Signer>().unwrap();
+    let verifier = kh
+        .public()
+        .unwrap()
+        .primitive::<dyn tink_core::Verifier>()
+        .unwrap();
+    let sig = signer.sign(b"data").unwrap();
+    assert!(verifier.verify(&sig, b"data").is_ok());
+    assert!(verifier.verify(&sig, b"other data").is_err());
+}
+
+#[test]
+fn test_handle_primitive_by_type() {
+    tink_aead::init();
+    tink_mac::init();
+    let aead_kh = tink_core::keyset::Handle::new(&tink_aead::aes128_gcm_key_template()).unwrap();
+    let a = aead_kh.primitive::<dyn tink_core::Aead>().unwrap();
+    let ct = a.encrypt(b"plaintext", b"aad").unwrap();
+    let a2 = tink_aead::new(&aead_kh).unwrap();
+    assert_eq!(a2.decrypt(&ct, b"aad").unwrap(), b"plaintext");
+
+    let mac_kh =
+        tink_core::keyset::Handle::new(&tink_mac::hmac_sha256_tag128_key_template()).unwrap();
+    let mac = mac_kh.primitive::<dyn Mac>().unwrap();
+    let tag = mac.compute_mac(b"data").unwrap();
+    assert!(tink_mac::new(&mac_kh)
+        .unwrap()
+        .verify_mac(&tag, b"data")
+        .is_ok());
+
+    // Asking for the wrong type of primitive fails.
+    tink_tests::expect_err(aead_kh.primitive::<dyn Mac>(), "not a Mac primitive");
+    tink_tests::expect_err(
+        mac_kh.primitive::<dyn tink_core::Aead>(),
+        "not an AEAD primitive",
+    );
+}
+
 #[test]
 fn test_new_key_data() {
     tink_mac::init();
@@ -148,10 +282,16 @@ fn test_primitive() {
     let mut serialized_key = vec![];
     key.encode(&mut serialized_key).unwrap();
     let p = tink_core::registry::primitive(tink_tests::HMAC_TYPE_URL, &serialized_key).unwrap();
-    if let tink_core::Primitive::Mac(_) = p {
-    } else {
-        panic!("Primitive not a Mac");
-    }
+    let mac = match p {
+        tink_core::Primitive::Mac(mac) => mac,
+        _ => panic!("Primitive not a Mac"),
+    };
+    // The primitive is the bare HMAC for the key, with no output prefix.
+    let want = tink_mac::subtle::Hmac::new(tink_proto::HashType::Sha256, &key.key_value, 16)
+        .unwrap()
+        .compute_mac(b"data")
+        .unwrap();
+    assert_eq!(mac.compute_mac(b"data").unwrap(), want);
 
     // unregistered url
     assert!(
@@ -199,9 +339,224 @@ fn dummy_key_generator() -> tink_proto::KeyTemplate {
 #[test]
 fn test_get_template_generator() {
     let dummy_name = "TEST".to_string();
-    tink_core::registry::register_template_generator(&dummy_name, dummy_key_generator);
+    tink_core::registry::register_template_generator(&dummy_name, dummy_key_generator).unwrap();
     let generator = tink_core::registry::get_template_generator(&dummy_name).unwrap();
     assert_eq!(generator().type_url, "TEST");
-    let names = tink_core::registry::template_names();
+    let names = tink_core::registry::get_template_names();
     assert!(names.contains(&dummy_name));
 }
+
+fn other_dummy_key_generator() -> tink_proto::KeyTemplate {
+    tink_proto::KeyTemplate {
+        type_url: "OTHER_TEST".to_string(),
+        value: vec![],
+        output_prefix_type: 0,
+    }
+}
+
+fn same_dummy_key_generator() -> tink_proto::KeyTemplate {
+    dummy_key_generator()
+}
+
+#[test]
+fn test_register_template_generator_conflict() {
+    tink_core::registry::register_template_generator("CONFLICT_TEST", dummy_key_generator).unwrap();
+    // Re-registering an identical template under the same name is fine, as is registering it
+    // under another name.
+    tink_core::registry::register_template_generator("CONFLICT_TEST", same_dummy_key_generator)
+        .unwrap();
+    tink_core::registry::register_template_generator("CONFLICT_TEST_ALIAS", dummy_key_generator)
+        .unwrap();
+    // A different template under the same name is rejected, and the original is retained.
+    let result = tink_core::registry::register_template_generator(
+        "CONFLICT_TEST",
+        other_dummy_key_generator,
+    );
+    tink_tests::expect_err(result, "different template is already registered");
+    let generator = tink_core::registry::get_template_generator("CONFLICT_TEST").unwrap();
+    assert_eq!(generator().type_url, "TEST");
+}
+
+#[test]
+fn test_template_names_sorted() {
+    tink_aead::init();
+    let names = tink_core::registry::get_template_names();
+    assert_eq!(names, tink_core::registry::template_names());
+    let mut sorted = names.clone();
+    sorted.sort();
+    assert_eq!(names, sorted);
+    let aes128 = names.iter().position(|n| n == "AES128_GCM").unwrap();
+    let aes256 = names.iter().position(|n| n == "AES256_GCM").unwrap();
+    assert!(aes128 < aes256);
+}
+
+/// Key manager that returns a fixed kind of primitive, for exercising the [`tink_core::Primitive`]
+/// variants through the registry.
+struct DummyPrimitiveKeyManager {
+    type_url: &'static str,
+    primitive: fn() -> tink_core::Primitive,
+}
+
+impl tink_core::registry::KeyManager for DummyPrimitiveKeyManager {
+    fn primitive(&self, _serialized_key: &[u8]) -> Result<tink_core::Primitive, TinkError> {
+        Ok((self.primitive)())
+    }
+
+    fn new_key(&self, _serialized_key_format: &[u8]) -> Result<Vec<u8>, TinkError> {
+        Err("not implemented".into())
+    }
+
+    fn type_url(&self) -> &'static str {
+        self.type_url
+    }
+
+    fn key_material_type(&self) -> tink_proto::key_data::KeyMaterialType {
+        tink_proto::key_data::KeyMaterialType::Symmetric
+    }
+}
+
+#[test]
+fn test_signer_verifier_primitives() {
+    tink_core::registry::register_key_manager(Arc::new(DummyPrimitiveKeyManager {
+        type_url: "DummySigner",
+        primitive: || tink_core::Primitive::Signer(Box::new(tink_tests::DummySigner::new("test"))),
+    }))
+    .unwrap();
+    tink_core::registry::register_key_manager(Arc::new(DummyPrimitiveKeyManager {
+        type_url: "DummyVerifier",
+        primitive: || {
+            tink_core::Primitive::Verifier(Box::new(tink_tests::DummyVerifier::new("test")))
+        },
+    }))
+    .unwrap();
+
+    let signer = match tink_core::registry::primitive("DummySigner", &[0x01]).unwrap() {
+        tink_core::Primitive::Signer(s) => s,
+        _ => panic!("not a Signer"),
+    };
+    let verifier = match tink_core::registry::primitive("DummyVerifier", &[0x01]).unwrap() {
+        tink_core::Primitive::Verifier(v) => v,
+        _ => panic!("not a Verifier"),
+    };
+    let sig = signer.sign(b"data").unwrap();
+    assert!(verifier.verify(&sig, b"data").is_ok());
+    assert!(verifier.verify(&sig, b"other data").is_err());
+
+    // Primitives can be cloned while boxed.
+    let sig = signer.box_clone().sign(b"data").unwrap();
+    assert!(verifier.box_clone().verify(&sig, b"data").is_ok());
+}
+
+#[test]
+fn test_hybrid_and_deterministic_aead_primitives() {
+    tink_core::registry::register_key_manager(Arc::new(DummyPrimitiveKeyManager {
+        type_url: "DummyHybridEncrypt",
+        primitive: || {
+            tink_core::Primitive::HybridEncrypt(Box::new(tink_tests::DummyHybridEncrypt::new(
+                "test",
+            )))
+        },
+    }))
+    .unwrap();
+    tink_core::registry::register_key_manager(Arc::new(DummyPrimitiveKeyManager {
+        type_url: "DummyHybridDecrypt",
+        primitive: || {
+            tink_core::Primitive::HybridDecrypt(Box::new(tink_tests::DummyHybridDecrypt::new(
+                "test",
+            )))
+        },
+    }))
+    .unwrap();
+    tink_core::registry::register_key_manager(Arc::new(DummyPrimitiveKeyManager {
+        type_url: "DummyDeterministicAead",
+        primitive: || {
+            tink_core::Primitive::DeterministicAead(
+                Box::<tink_tests::DummyDeterministicAead>::default(),
+            )
+        },
+    }))
+    .unwrap();
+
+    let enc = match tink_core::registry::primitive("DummyHybridEncrypt", &[0x01]).unwrap() {
+        tink_core::Primitive::HybridEncrypt(p) => p,
+        _ => panic!("not a HybridEncrypt"),
+    };
+    let dec = match tink_core::registry::primitive("DummyHybridDecrypt", &[0x01]).unwrap() {
+        tink_core::Primitive::HybridDecrypt(p) => p,
+        _ => panic!("not a HybridDecrypt"),
+    };
+    let ct = enc.encrypt(b"plaintext", b"context").unwrap();
+    assert_eq!(dec.decrypt(&ct, b"context").unwrap(), b"plaintext");
+    assert!(dec.decrypt(&ct, b"other context").is_err());
+
+    let daead = match tink_core::registry::primitive("DummyDeterministicAead", &[0x01]).unwrap() {
+        tink_core::Primitive::DeterministicAead(p) => p,
+        _ => panic!("not a DeterministicAead"),
+    };
+    let ct = daead
+        .encrypt_deterministically(b"plaintext", b"aad")
+        .unwrap();
+    assert_eq!(
+        ct,
+        daead
+            .encrypt_deterministically(b"plaintext", b"aad")
+            .unwrap()
+    );
+    assert_eq!(
+        daead
+            .box_clone()
+            .decrypt_deterministically(&ct, b"aad")
+            .unwrap(),
+        b"plaintext"
+    );
+}
+
+#[test]
+fn test_streaming_aead_primitive() {
+    tink_core::registry::register_key_manager(Arc::new(DummyPrimitiveKeyManager {
+        type_url: "DummyStreamingAead",
+        primitive: || tink_core::Primitive::StreamingAead(Box::new(tink_tests::DummyStreamingAead)),
+    }))
+    .unwrap();
+
+    let saead = match tink_core::registry::primitive("DummyStreamingAead", &[0x01]).unwrap() {
+        tink_core::Primitive::StreamingAead(p) => p,
+        _ => panic!("not a StreamingAead"),
+    };
+    let buf = tink_tests::SharedBuf::new();
+    let mut w = saead
+        .new_encrypting_writer(Box::new(buf.clone()), b"aad")
+        .unwrap();
+    w.write_all(b"some plaintext").unwrap();
+    w.close().unwrap();
+    assert!(w.write(b"more").is_err(), "write after close should fail");
+
+    let mut r = saead
+        .box_clone()
+        .new_decrypting_reader(Box::new(std::io::Cursor::new(buf.contents())), b"aad")
+        .unwrap();
+    let mut pt = vec![];
+    r.read_to_end(&mut pt).unwrap();
+    assert_eq!(pt, b"some plaintext");
+}
+
+#[test]
+fn test_prf_primitive() {
+    tink_core::registry::register_key_manager(Arc::new(DummyPrimitiveKeyManager {
+        type_url: "DummyPrf",
+        primitive: || {
+            tink_core::Primitive::Prf(Box::new(tink_tests::DummyPrf {
+                name: "test".to_string(),
+            }))
+        },
+    }))
+    .unwrap();
+
+    let prf = match tink_core::registry::primitive("DummyPrf", &[0x01]).unwrap() {
+        tink_core::Primitive::Prf(p) => p,
+        _ => panic!("not a Prf"),
+    };
+    let out = prf.compute_prf(b"ab", 8).unwrap();
+    assert_eq!(out, b"abtestab");
+    assert_eq!(prf.box_clone().compute_prf(b"ab", 8).unwrap(), out);
+}
@@ -0,0 +1,50 @@
+// Copyright 2020 The Tink-Rust Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+use tink_core::subtle;
+
+#[test]
+fn test_apply_keystream_nist_test_vector() {
+    // NIST SP 800-38A F.5.1 (CTR-AES128.Encrypt) and F.5.5 (CTR-AES256.Encrypt).
+    let iv = hex::decode("f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff").unwrap();
+    let p = hex::decode("6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e5130c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710").unwrap();
+    let testcases = vec![
+        (
+            "2b7e151628aed2a6abf7158809cf4f3c",
+            "874d6191b620e3261bef6864990db6ce9806f66b7970fdff8617187bb9fffdff5ae4df3edbd5d35e5b4f09020db03eab1e031dda2fbe03d1792170a0f3009cee",
+        ),
+        (
+            "603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4",
+            "601ec313775789a5b7a7f504bbf3d228f443e3ca4d62b59aca84e990cacaf5c52b0930daa23de94ce87017ba2d84988ddfc9c58db67aada613c2dd08457941a6",
+        ),
+    ];
+    for (key, c) in testcases {
+        let key = hex::decode(key).unwrap();
+        let c = hex::decode(c).unwrap();
+        let cipher = subtle::AesCtr::new(&key, iv.len()).unwrap();
+
+        let mut data = p.clone();
+        cipher.apply_keystream(&iv, &mut data).unwrap();
+        assert_eq!(hex::encode(&data), hex::encode(&c));
+        cipher.apply_keystream(&iv, &mut data).unwrap();
+        assert_eq!(data, p);
+
+        tink_tests::expect_err(
+            cipher.apply_keystream(&iv[..12], &mut data),
+            "invalid IV size",
+        );
+    }
+}
@@ -0,0 +1,207 @@
+// Copyright 2020 The Tink-Rust Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+use p256::elliptic_curve::sec1::ToEncodedPoint;
+use tink_core::subtle::ec_util;
+use tink_proto::{EcPointFormat, EllipticCurveType};
+
+const POINT_FORMATS: &[EcPointFormat] = &[
+    EcPointFormat::Uncompressed,
+    EcPointFormat::Compressed,
+    EcPointFormat::DoNotUseCrunchyUncompressed,
+];
+
+#[test]
+fn test_get_curve() {
+    struct Case {
+        name: &'static str,
+        curve: EllipticCurveType,
+        field_size: usize,
+    }
+    let cases = vec![
+        Case {
+            name: "NIST_P256",
+            curve: EllipticCurveType::NistP256,
+            field_size: 32,
+        },
+        Case {
+            name: "NIST_P384",
+            curve: EllipticCurveType::NistP384,
+            field_size: 48,
+        },
+        Case {
+            name: "NIST_P521",
+            curve: EllipticCurveType::NistP521,
+            field_size: 66,
+        },
+    ];
+    for tc in cases {
+        let params = ec_util::get_curve(tc.name).unwrap();
+        assert_eq!(params.curve, tc.curve);
+        assert_eq!(params.field_size_in_bytes(), tc.field_size);
+        assert_eq!(params.order.len(), tc.field_size);
+        assert_eq!(params.generator_x.len(), tc.field_size);
+        assert_eq!(params.generator_y.len(), tc.field_size);
+        assert_eq!(
+            ec_util::field_size_in_bytes(tc.curve).unwrap(),
+            tc.field_size
+        );
+    }
+}
+
+#[test]
+fn test_get_curve_p256_matches_generator() {
+    let params = ec_util::get_curve("NIST_P256").unwrap();
+    let g = p256::AffinePoint::GENERATOR.to_encoded_point(/* compress= */ false);
+    assert_eq!(params.generator_x, g.x().unwrap().as_slice());
+    assert_eq!(params.generator_y, g.y().unwrap().as_slice());
+    assert_eq!(
+        hex::encode(params.order),
+        "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551"
+    );
+}
+
+#[test]
+fn test_get_curve_unknown() {
+    tink_tests::expect_err(ec_util::get_curve("NIST_P224"), "unsupported curve");
+    tink_tests::expect_err(
+        ec_util::field_size_in_bytes(EllipticCurveType::Curve25519),
+        "unsupported curve",
+    );
+}
+
+#[test]
+fn test_point_encode_decode_p256() {
+    let sk = p256::SecretKey::random(&mut p256::elliptic_curve::rand_core::OsRng);
+    let point = sk.public_key().to_encoded_point(/* compress= */ false);
+    let x = point.x().unwrap().to_vec();
+    let y = point.y().unwrap().to_vec();
+    for format in POINT_FORMATS {
+        let encoded = ec_util::point_encode(EllipticCurveType::NistP256, *format, &x, &y).unwrap();
+        let want_len = match format {
+            EcPointFormat::Uncompressed => 65,
+            EcPointFormat::Compressed => 33,
+            _ => 64,
+        };
+        assert_eq!(encoded.len(), want_len, "format {:?}", format);
+        let (got_x, got_y) =
+            ec_util::point_decode(EllipticCurveType::NistP256, *format, &encoded).unwrap();
+        assert_eq!(got_x, x, "format {:?}", format);
+        assert_eq!(got_y, y, "format {:?}", format);
+    }
+    // The uncompressed encoding matches the standard SEC 1 encoding.
+    assert_eq!(
+        ec_util::point_encode(
+            EllipticCurveType::NistP256,
+            EcPointFormat::Uncompressed,
+            &x,
+            &y
+        )
+        .unwrap(),
+        point.as_bytes()
+    );
+}
+
+#[test]
+fn test_point_encode_decode_generators() {
+    for name in &["NIST_P256", "NIST_P384", "NIST_P521"] {
+        let params = ec_util::get_curve(name).unwrap();
+        for format in POINT_FORMATS {
+            let encoded = ec_util::point_encode(
+                params.curve,
+                *format,
+                params.generator_x,
+                params.generator_y,
+            )
+            .unwrap();
+            let (x, y) = ec_util::point_decode(params.curve, *format, &encoded).unwrap();
+            assert_eq!(x, params.generator_x, "{} {:?}", name, format);
+            assert_eq!(y, params.generator_y, "{} {:?}", name, format);
+        }
+    }
+}
+
+#[test]
+fn test_point_encode_decode_invalid() {
+    let params = ec_util::get_curve("NIST_P256").unwrap();
+    let mut bad_y = params.generator_y.to_vec();
+    bad_y[31] ^= 0x01;
+    for format in POINT_FORMATS {
+        tink_tests::expect_err(
+            ec_util::point_encode(params.curve, *format, params.generator_x, &bad_y),
+            "invalid point",
+        );
+    }
+
+    let mut encoded = ec_util::point_encode(
+        params.curve,
+        EcPointFormat::Uncompressed,
+        params.generator_x,
+        params.generator_y,
+    )
+    .unwrap();
+    encoded[64] ^= 0x01;
+    tink_tests::expect_err(
+        ec_util::point_decode(params.curve, EcPointFormat::Uncompressed, &encoded),
+        "invalid point",
+    );
+    tink_tests::expect_err(
+        ec_util::point_decode(params.curve, EcPointFormat::Uncompressed, &encoded[1..]),
+        "invalid point size",
+    );
+    tink_tests::expect_err(
+        ec_util::point_decode(params.curve, EcPointFormat::Compressed, &encoded[..33]),
+        "invalid point format",
+    );
+    tink_tests::expect_err(
+        ec_util::point_encode(params.curve, EcPointFormat::Uncompressed, &[0xff; 33], &[]),
+        "coordinate too large",
+    );
+}
+
+#[test]
+fn test_validate_private_scalar() {
+    for curve in [
+        EllipticCurveType::NistP256,
+        EllipticCurveType::NistP384,
+        EllipticCurveType::NistP521,
+    ] {
+        let order = ec_util::curve_params(curve).unwrap().order.to_vec();
+        let mut order_minus_one = order.clone();
+        *order_minus_one.last_mut().unwrap() -= 1; // no borrow: all orders are odd
+
+        assert!(ec_util::validate_private_scalar(curve, &[1]).is_ok());
+        assert!(ec_util::validate_private_scalar(curve, &[0, 0, 1]).is_ok());
+        assert!(ec_util::validate_private_scalar(curve, &order_minus_one).is_ok());
+
+        tink_tests::expect_err(ec_util::validate_private_scalar(curve, &[]), "zero");
+        tink_tests::expect_err(ec_util::validate_private_scalar(curve, &[0; 32]), "zero");
+        tink_tests::expect_err(
+            ec_util::validate_private_scalar(curve, &order),
+            "out of range",
+        );
+        let mut too_long = vec![1];
+        too_long.extend_from_slice(&order_minus_one);
+        tink_tests::expect_err(
+            ec_util::validate_private_scalar(curve, &too_long),
+            "out of range",
+        );
+    }
+    tink_tests::expect_err(
+        ec_util::validate_private_scalar(EllipticCurveType::Curve25519, &[1]),
+        "unsupported curve",
+    );
+}
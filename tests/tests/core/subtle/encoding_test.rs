@@ -0,0 +1,41 @@
+// Copyright 2020 The Tink-Rust Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+use tink_core::subtle::{
+    encoding::{base64_decode, base64_encode},
+    random::get_random_bytes,
+};
+
+#[test]
+fn test_base64_round_trip() {
+    for size in 0..70 {
+        let data = get_random_bytes(size);
+        let encoded = base64_encode(&data);
+        assert!(!encoded.contains('='), "unexpected padding in {}", encoded);
+        assert!(!encoded.contains('+') && !encoded.contains('/'));
+        assert_eq!(base64_decode(&encoded).unwrap(), data);
+    }
+}
+
+#[test]
+fn test_base64_decode_padding() {
+    assert_eq!(base64_encode(&[0xfb, 0xff]), "-_8");
+    assert_eq!(base64_decode("-_8").unwrap(), vec![0xfb, 0xff]);
+    assert_eq!(base64_decode("-_8=").unwrap(), vec![0xfb, 0xff]);
+    assert_eq!(base64_decode("").unwrap(), Vec::<u8>::new());
+    tink_tests::expect_err(base64_decode("+/8="), "invalid base64 data");
+    tink_tests::expect_err(base64_decode("-_8=="), "invalid base64 data");
+}
@@ -17,7 +17,10 @@
 use tink_core::subtle;
 use tink_proto::HashType;
 
+mod aes_ctr_test;
 mod cryptofmt_test;
+mod ec_util_test;
+mod encoding_test;
 mod hkdf_hmac_test;
 mod random_test;
 
@@ -64,3 +67,38 @@ fn test_compute_hash() {
         "unexpected result for invalid hash types"
     );
 }
+
+#[test]
+fn test_compute_digest() {
+    // Test vectors for the message "abc" from FIPS 180-2.
+    let data = b"abc";
+    let tests = vec![
+        (HashType::Sha1, "a9993e364706816aba3e25717850c26c9cd0d89d"),
+        (
+            HashType::Sha224,
+            "23097d223405d8228642a477bda255b32aadbce4bda0b3f7e36c9da7",
+        ),
+        (
+            HashType::Sha256,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+        ),
+        (
+            HashType::Sha384,
+            "cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed8086072ba1e7cc2358baeca134c825a7",
+        ),
+        (
+            HashType::Sha512,
+            "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f",
+        ),
+    ];
+    for (hash, want) in tests {
+        let got = subtle::compute_digest(hash, data).unwrap();
+        assert_eq!(want, hex::encode(&got), "mismatch for {hash:?}");
+        assert_eq!(got.len(), subtle::get_hash_digest_size(hash).unwrap());
+    }
+
+    tink_tests::expect_err(
+        subtle::compute_digest(HashType::UnknownHash, data),
+        "invalid hash algorithm",
+    );
+}
@@ -24,9 +24,86 @@ fn test_get_random_bytes() {
     }
 }
 
+#[test]
+fn test_get_random_bytes_large() {
+    let size = 8 * 1024 * 1024;
+    let buf = random::get_random_bytes(size);
+    assert_eq!(size, buf.len());
+    tink_tests::z_test_uniform_string(&buf).expect("large random buffer is not uniform");
+    // Chunks must not repeat each other.
+    let chunk = 64 * 1024;
+    assert_ne!(&buf[..chunk], &buf[chunk..2 * chunk]);
+    assert_ne!(&buf[..chunk], &buf[size - chunk..]);
+}
+
 #[test]
 fn test_random_uint() {
     let v1 = random::get_random_uint32();
     let v2 = random::get_random_uint32();
     assert_ne!(v1, v2, "Just unlucky?");
 }
+
+#[test]
+fn test_get_random_key_material_range() {
+    let order = tink_core::subtle::ec_util::get_curve("NIST_P256")
+        .unwrap()
+        .order;
+    for _ in 0..1000 {
+        let k = random::get_random_key_material(order).unwrap();
+        assert_eq!(k.len(), order.len());
+        assert!(k.iter().any(|b| *b != 0), "zero scalar");
+        assert!(k.as_slice() < order, "scalar >= n");
+    }
+
+    // With a small order every value in [1, n-1] should turn up, and nothing else.
+    let order = [0x00, 0x00, 0x0b];
+    let mut seen = [0usize; 11];
+    for _ in 0..2000 {
+        let k = random::get_random_key_material(&order).unwrap();
+        assert_eq!(&k[..2], &[0, 0]);
+        assert!(k[2] >= 1 && k[2] < 11, "value {} out of range", k[2]);
+        seen[k[2] as usize] += 1;
+    }
+    assert_eq!(seen[0], 0);
+    assert!(
+        seen[1..].iter().all(|c| *c > 0),
+        "missing values: {:?}",
+        seen
+    );
+}
+
+#[test]
+fn test_get_random_key_material_uniformity() {
+    // z-test on the mean of the least significant byte, which should be uniform on [0, 255].
+    let order = tink_core::subtle::ec_util::get_curve("NIST_P256")
+        .unwrap()
+        .order;
+    let n = 10000;
+    let sum: u64 = (0..n)
+        .map(|_| {
+            *random::get_random_key_material(order)
+                .unwrap()
+                .last()
+                .unwrap() as u64
+        })
+        .sum();
+    let mean = sum as f64 / n as f64;
+    let expected_mean = 127.5;
+    let std_dev = ((256.0 * 256.0 - 1.0) / 12.0f64).sqrt() / (n as f64).sqrt();
+    let z = (mean - expected_mean) / std_dev;
+    assert!(z.abs() < 5.0, "z-score {} too large (mean {})", z, mean);
+}
+
+#[test]
+fn test_get_random_key_material_invalid_order() {
+    for order in &[&[][..], &[0x00, 0x00][..], &[0x00, 0x01][..]] {
+        tink_tests::expect_err(
+            random::get_random_key_material(order),
+            "invalid group order",
+        );
+    }
+    assert_eq!(
+        random::get_random_key_material(&[0x02]).unwrap(),
+        vec![0x01]
+    );
+}
@@ -0,0 +1,69 @@
+// Copyright 2020 The Tink-Rust Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+use tink_core::{utils::wrap_err, ErrorKind, ResultExt, TinkError};
+
+#[test]
+fn test_tink_error_serialize() {
+    let inner = TinkError::new("HmacKeyManager: invalid key").with_kind(ErrorKind::InvalidKey);
+    let err = wrap_err("registry::primitive: failed", inner);
+
+    let json = serde_json::to_value(&err).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "message": "registry::primitive: failed: HmacKeyManager: invalid key",
+            "kind": "InvalidKey",
+        })
+    );
+    let text = serde_json::to_string(&err).unwrap();
+    assert!(!text.contains('['), "unexpected array in {}", text);
+}
+
+#[test]
+fn test_tink_error_context() {
+    let err = TinkError::new("invalid key")
+        .with_kind(ErrorKind::InvalidKey)
+        .context("HmacKeyManager")
+        .context("registry::primitive");
+    assert_eq!(
+        err.to_string(),
+        "registry::primitive: HmacKeyManager: invalid key"
+    );
+    assert_eq!(err.kind(), ErrorKind::InvalidKey);
+    // Equivalent to nested `wrap_err` calls.
+    let wrapped = wrap_err(
+        "registry::primitive",
+        wrap_err("HmacKeyManager", TinkError::new("invalid key")),
+    );
+    assert_eq!(err.to_string(), wrapped.to_string());
+
+    let result: Result<(), TinkError> = Err(TinkError::new("decryption failed"));
+    let err = result
+        .context("first")
+        .context("second")
+        .expect_err("context should preserve the error");
+    assert_eq!(err.to_string(), "second: first: decryption failed");
+
+    // Non-Tink errors can be annotated too.
+    let result: Result<u8, std::num::ParseIntError> = "x".parse::<u8>();
+    let err = result.context("parse failed").unwrap_err();
+    assert!(err.to_string().starts_with("parse failed: "));
+    assert_eq!(err.kind(), ErrorKind::Other);
+
+    let ok: Result<u8, TinkError> = Ok(1);
+    assert_eq!(ok.context("unused").unwrap(), 1);
+}
@@ -36,6 +36,90 @@ fn test_aes_siv_encrypt_decrypt() {
     assert_eq!(pt, msg, "Mismatched plaintexts");
 }
 
+#[test]
+fn test_aes_siv_known_vectors() {
+    struct TestVector {
+        key: &'static str,
+        aad: &'static str,
+        plaintext: &'static str,
+        synthetic_iv: &'static str,
+        ciphertext: &'static str,
+    }
+    let test_vectors = [
+        // RFC 5297 A.1, with the AES-256 key (S2V half, then CTR half) from the miscreant test
+        // vectors.
+        TestVector {
+            key: "fffefdfcfbfaf9f8f7f6f5f4f3f2f1f06f6e6d6c6b6a69686766656463626160f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff000102030405060708090a0b0c0d0e0f",
+            aad: "101112131415161718191a1b1c1d1e1f2021222324252627",
+            plaintext: "112233445566778899aabbccddee",
+            synthetic_iv: "f125274c598065cfc26b0e7157502908",
+            ciphertext: "8b035217e380cac8919ee800c126",
+        },
+        TestVector {
+            key: "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f00112233445566778899aabbccddeefff0f1f2f3f4f5f6f7f8f9fafbfcfdfeff",
+            aad: "",
+            plaintext: "",
+            synthetic_iv: "6ff5b8ef53fc365606cd3ea047374885",
+            ciphertext: "",
+        },
+    ];
+    for (i, tv) in test_vectors.iter().enumerate() {
+        let a = tink_daead::subtle::AesSiv::new(&hex::decode(tv.key).unwrap()).unwrap();
+        let aad = hex::decode(tv.aad).unwrap();
+        let pt = hex::decode(tv.plaintext).unwrap();
+        let ct = a.encrypt_deterministically(&pt, &aad).unwrap();
+        assert_eq!(hex::encode(&ct[..16]), tv.synthetic_iv, "vector {}", i);
+        assert_eq!(hex::encode(&ct[16..]), tv.ciphertext, "vector {}", i);
+        assert_eq!(a.decrypt_deterministically(&ct, &aad).unwrap(), pt);
+    }
+}
+
+#[test]
+fn test_aes_siv_multiple_additional_data() {
+    // RFC 5297 A.2, with the AES-256 key from the miscreant test vectors.
+    let key = hex::decode("7f7e7d7c7b7a797877767574737271706f6e6d6c6b6a69686766656463626160404142434445464748494a4b4c4d4e4f505152535455565758595a5b5b5d5e5f").unwrap();
+    let ad1 = hex::decode(
+        "00112233445566778899aabbccddeeffdeaddadadeaddadaffeeddccbbaa99887766554433221100",
+    )
+    .unwrap();
+    let ad2 = hex::decode("102030405060708090a0").unwrap();
+    let nonce = hex::decode("09f911029d74e35bd84156c5635688c0").unwrap();
+    let pt = hex::decode("7468697320697320736f6d6520706c61696e7465787420746f20656e6372797074207573696e67205349562d414553").unwrap();
+    let a = tink_daead::subtle::AesSiv::new(&key).unwrap();
+
+    let ct = a
+        .encrypt_deterministically_multi(&pt, &[&ad1, &ad2, &nonce])
+        .unwrap();
+    assert_eq!(hex::encode(&ct), "85b8167310038db7dc4692c0281ca35868181b2762f3c24f2efa5fb80cb143516ce6c434b898a6fd8eb98a418842f51f66fc67de43ac185a66dd72475bbb08");
+    assert_eq!(
+        a.decrypt_deterministically_multi(&ct, &[&ad1, &ad2, &nonce])
+            .unwrap(),
+        pt
+    );
+
+    let ct = a
+        .encrypt_deterministically_multi(&pt, &[&ad1, &ad2])
+        .unwrap();
+    assert_eq!(hex::encode(&ct), "119d9f363ff226408c06a8c788a6c85dbbc4d1ea36de5577d088617ef753a4697974a6736b73519fb6c1aff1e8f83329556b3e626b737d8373017fb0406a0c");
+    assert_eq!(
+        a.decrypt_deterministically_multi(&ct, &[&ad1, &ad2])
+            .unwrap(),
+        pt
+    );
+    // The components are authenticated separately, not as their concatenation.
+    let joined = [ad1.clone(), ad2.clone()].concat();
+    assert!(a.decrypt_deterministically(&ct, &joined).is_err());
+    assert!(a
+        .decrypt_deterministically_multi(&ct, &[&ad2, &ad1])
+        .is_err());
+
+    // A single component matches the single-AAD methods.
+    assert_eq!(
+        a.encrypt_deterministically_multi(&pt, &[&ad1]).unwrap(),
+        a.encrypt_deterministically(&pt, &ad1).unwrap()
+    );
+}
+
 #[test]
 fn test_aes_siv_empty_plaintext() {
     let key_str =
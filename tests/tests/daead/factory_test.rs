@@ -14,7 +14,7 @@
 //
 ////////////////////////////////////////////////////////////////////////////////
 
-use tink_core::{subtle::random::get_random_bytes, TinkError};
+use tink_core::{subtle::random::get_random_bytes, DeterministicAead, TinkError};
 
 #[test]
 fn test_factory_multiple_keys() {
@@ -88,13 +88,13 @@ fn test_factory_raw_key_as_primary() {
 
 // Return an `Err` if decryption fails, panic if something else goes wrong.
 #[allow(clippy::borrowed_box)]
-fn validate_daead_factory_cipher<T: ?Sized>(
+fn validate_daead_factory_cipher<T>(
     encrypt_cipher: &Box<T>,
     decrypt_cipher: &Box<T>,
     expected_prefix: &[u8],
 ) -> Result<(), TinkError>
 where
-    T: tink_core::DeterministicAead,
+    T: tink_core::DeterministicAead + ?Sized,
 {
     let prefix_size = expected_prefix.len();
     // regular plaintext.
@@ -149,3 +149,40 @@ fn test_factory_with_valid_primitive_set_type() {
         "calling new() with good tink_core::keyset::Handle failed"
     );
 }
+
+#[test]
+fn test_factory_multiple_additional_data() {
+    tink_daead::init();
+    let kh = tink_core::keyset::Handle::new(&tink_daead::aes_siv_key_template()).unwrap();
+    let d = tink_daead::new(&kh).unwrap();
+    let pt = b"plaintext";
+    let ct = d
+        .encrypt_deterministically_multi(pt, &[b"ad1", b"ad2"])
+        .unwrap();
+    assert_eq!(
+        d.decrypt_deterministically_multi(&ct, &[b"ad1", b"ad2"])
+            .unwrap(),
+        pt
+    );
+    assert!(d
+        .decrypt_deterministically_multi(&ct, &[b"ad1", b"ad3"])
+        .is_err());
+    assert_eq!(
+        d.encrypt_deterministically_multi(pt, &[b"ad"]).unwrap(),
+        d.encrypt_deterministically(pt, b"ad").unwrap()
+    );
+
+    // Primitives without multi-component support only accept a single component.
+    let dummy = tink_tests::DummyDeterministicAead::default();
+    let ct = dummy.encrypt_deterministically_multi(pt, &[b"ad"]).unwrap();
+    assert_eq!(
+        dummy
+            .decrypt_deterministically_multi(&ct, &[b"ad"])
+            .unwrap(),
+        pt
+    );
+    let err = dummy
+        .encrypt_deterministically_multi(pt, &[b"ad1", b"ad2"])
+        .unwrap_err();
+    assert_eq!(err.kind(), tink_core::ErrorKind::UnsupportedType);
+}
@@ -170,3 +170,95 @@ fn test_ec_aes_siv_encrypt() {
         );
     */
 }
+
+#[test]
+fn test_ec_hkdf_salt() {
+    tink_hybrid::init();
+    let pvt = tink_hybrid::subtle::generate_ecdh_key_pair(EllipticCurveType::NistP256).unwrap();
+    let r_dem =
+        tink_hybrid::EciesAeadHkdfDemHelper::new(&tink_aead::aes128_gcm_key_template()).unwrap();
+    let new_pair = |salt: &[u8]| {
+        let e = tink_hybrid::subtle::EciesAeadHkdfHybridEncrypt::new(
+            &pvt.public_key(),
+            salt,
+            HashType::Sha256,
+            EcPointFormat::Uncompressed,
+            r_dem.clone(),
+        )
+        .unwrap();
+        let d = tink_hybrid::subtle::EciesAeadHkdfHybridDecrypt::new(
+            pvt.clone(),
+            salt,
+            HashType::Sha256,
+            EcPointFormat::Uncompressed,
+            r_dem.clone(),
+        )
+        .unwrap();
+        (e, d)
+    };
+    let pt = get_random_bytes(20);
+    let context = b"context info";
+
+    let (e_empty, d_empty) = new_pair(&[]);
+    let ct_empty = e_empty.encrypt(&pt, context).unwrap();
+    assert_eq!(d_empty.decrypt(&ct_empty, context).unwrap(), pt);
+
+    let salt = get_random_bytes(32);
+    let (e_salt, d_salt) = new_pair(&salt);
+    let ct_salt = e_salt.encrypt(&pt, context).unwrap();
+    assert_eq!(d_salt.decrypt(&ct_salt, context).unwrap(), pt);
+
+    // Changing the salt for the same key breaks decryption.
+    assert!(d_salt.decrypt(&ct_empty, context).is_err());
+    assert!(d_empty.decrypt(&ct_salt, context).is_err());
+    let mut other_salt = salt.clone();
+    other_salt[0] ^= 1;
+    let (_, d_other) = new_pair(&other_salt);
+    assert!(d_other.decrypt(&ct_salt, context).is_err());
+
+    // Overlong salts are rejected.
+    let max = tink_hybrid::subtle::MAX_HKDF_SALT_SIZE;
+    let _ = new_pair(&vec![0; max]);
+    let long_salt = vec![0; max + 1];
+    tink_tests::expect_err(
+        tink_hybrid::subtle::EciesAeadHkdfHybridEncrypt::new(
+            &pvt.public_key(),
+            &long_salt,
+            HashType::Sha256,
+            EcPointFormat::Uncompressed,
+            r_dem.clone(),
+        ),
+        "HKDF salt too long",
+    );
+    tink_tests::expect_err(
+        tink_hybrid::subtle::EciesAeadHkdfHybridDecrypt::new(
+            pvt.clone(),
+            &long_salt,
+            HashType::Sha256,
+            EcPointFormat::Uncompressed,
+            r_dem.clone(),
+        ),
+        "HKDF salt too long",
+    );
+    let template = tink_hybrid::ecies_hkdf_aes128_gcm_key_template();
+    let mut format = <tink_proto::EciesAeadHkdfKeyFormat as tink_proto::prost::Message>::decode(
+        template.value.as_ref(),
+    )
+    .unwrap();
+    format
+        .params
+        .as_mut()
+        .unwrap()
+        .kem_params
+        .as_mut()
+        .unwrap()
+        .hkdf_salt = long_salt;
+    let template = tink_proto::KeyTemplate {
+        value: tink_tests::proto_encode(&format),
+        ..template
+    };
+    tink_tests::expect_err(
+        tink_core::keyset::Handle::new(&template),
+        "HKDF salt too long",
+    );
+}
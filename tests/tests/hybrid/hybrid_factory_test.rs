@@ -112,3 +112,23 @@ fn test_factory_with_valid_primitive_set_type() {
     let result = tink_hybrid::new_decrypt(&good_kh);
     assert!(result.is_ok(), "new_decrypt() failed: {:?}", result.err());
 }
+
+#[test]
+fn test_factory_context_info_binding() {
+    tink_hybrid::init();
+    for template in &[
+        tink_hybrid::ecies_hkdf_aes128_gcm_key_template(),
+        tink_hybrid::ecies_hkdf_aes128_ctr_hmac_sha256_key_template(),
+    ] {
+        let kh_priv = tink_core::keyset::Handle::new(template).unwrap();
+        let kh_pub = kh_priv.public().unwrap();
+        let enc = tink_hybrid::new_encrypt(&kh_pub).unwrap();
+        let dec = tink_hybrid::new_decrypt(&kh_priv).unwrap();
+
+        let pt = b"some plaintext";
+        let ct = enc.encrypt(pt, b"user42").unwrap();
+        assert!(dec.decrypt(&ct, b"user43").is_err());
+        assert!(dec.decrypt(&ct, b"").is_err());
+        assert_eq!(dec.decrypt(&ct, b"user42").unwrap(), pt);
+    }
+}
@@ -26,8 +26,15 @@ fn test_key_templates() {
             "ECIES_P256_HKDF_HMAC_SHA256_AES128_CTR_HMAC_SHA256",
             tink_hybrid::ecies_hkdf_aes128_ctr_hmac_sha256_key_template(),
         ),
+        (
+            "ECIES_P256_COMPRESSED_HKDF_HMAC_SHA256_AES128_GCM",
+            tink_hybrid::ecies_p256_compressed_hkdf_aes128_gcm_key_template(),
+        ),
     ];
     for (name, template) in test_cases {
+        let want = tink_tests::key_template_proto("hybrid", name).unwrap();
+        assert_eq!(want, template);
+
         let private_handle = tink_core::keyset::Handle::new(&template).unwrap();
         let public_handle = private_handle.public().unwrap();
         let enc = tink_hybrid::new_encrypt(&public_handle).unwrap();
@@ -732,6 +732,48 @@ fn test_point_decode_pads() {
     );
 }
 
+#[test]
+fn test_point_decode_crunchy_uncompressed() {
+    // Reference P-256 point as emitted by older Java Tink, i.e. x || y without the 0x04 prefix.
+    let want_x =
+        hex::decode("b0cfc7bc02fc980d858077552947ffb449b10df8949dee4e56fe21e016dcb25a").unwrap();
+    let want_y =
+        hex::decode("1886ccdca5487a6772f9401888203f90587cc00a730e2b83d5c6f89b3b568df7").unwrap();
+    let mut crunchy = want_x.clone();
+    crunchy.extend_from_slice(&want_y);
+
+    let pub_key = subtle::point_decode(
+        EllipticCurveType::NistP256,
+        EcPointFormat::DoNotUseCrunchyUncompressed,
+        &crunchy,
+    )
+    .unwrap();
+    let (x, y) = pub_key.x_y_bytes().unwrap();
+    assert_eq!(x, want_x);
+    assert_eq!(y, want_y);
+
+    // Re-encoding gives back the same crunchy bytes.
+    let encoded = subtle::point_encode(
+        EllipticCurveType::NistP256,
+        EcPointFormat::DoNotUseCrunchyUncompressed,
+        &pub_key,
+    )
+    .unwrap();
+    assert_eq!(encoded, crunchy);
+
+    // The standard uncompressed encoding (with 0x04 prefix) is not accepted as crunchy.
+    let mut uncompressed = vec![0x04];
+    uncompressed.extend_from_slice(&crunchy);
+    expect_err(
+        subtle::point_decode(
+            EllipticCurveType::NistP256,
+            EcPointFormat::DoNotUseCrunchyUncompressed,
+            &uncompressed,
+        ),
+        "invalid point size",
+    );
+}
+
 fn check_flag(flags: &[String], check: &[&str]) -> bool {
     for f in flags {
         for c in check {
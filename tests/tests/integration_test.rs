@@ -15,6 +15,7 @@
 ////////////////////////////////////////////////////////////////////////////////
 
 use tink_core::{subtle::random::get_random_bytes, Aead, Mac, Signer, Verifier};
+use tink_proto::prost::Message;
 
 #[test]
 fn test_dummy_aead() {
@@ -30,6 +31,55 @@ fn test_dummy_aead() {
     assert_eq!(data, decrypt);
 }
 
+#[test]
+fn test_aead_round_trip_with_aad() {
+    let dummy = tink_tests::DummyAead {
+        name: "dummy".to_string(),
+    };
+    tink_tests::aead_round_trip_with_aad(&dummy).unwrap();
+
+    /// AEAD that ignores the additional data.
+    #[derive(Clone)]
+    struct AadIgnoringAead;
+    impl Aead for AadIgnoringAead {
+        fn encrypt(&self, pt: &[u8], _aad: &[u8]) -> Result<Vec<u8>, tink_core::TinkError> {
+            Ok(pt.to_vec())
+        }
+        fn decrypt(&self, ct: &[u8], _aad: &[u8]) -> Result<Vec<u8>, tink_core::TinkError> {
+            Ok(ct.to_vec())
+        }
+    }
+    tink_tests::expect_err(
+        tink_tests::aead_round_trip_with_aad(&AadIgnoringAead),
+        "decryption with different AAD unexpectedly succeeded",
+    );
+}
+
+#[test]
+fn test_key_template_proto_round_trip() {
+    tink_mac::init();
+    let mut template = tink_mac::hmac_sha256_tag128_key_template();
+    tink_tests::key_template_proto_round_trip(
+        &template,
+        |f: &tink_proto::HmacKeyFormat| f.version,
+        |k: &tink_proto::HmacKey| k.version,
+    )
+    .unwrap();
+
+    // A template whose format claims a different version is caught.
+    let mut format = tink_proto::HmacKeyFormat::decode(template.value.as_ref()).unwrap();
+    format.version = 1;
+    template.value = tink_tests::proto_encode(&format);
+    tink_tests::expect_err(
+        tink_tests::key_template_proto_round_trip(
+            &template,
+            |f: &tink_proto::HmacKeyFormat| f.version,
+            |k: &tink_proto::HmacKey| k.version,
+        ),
+        "key has version 0, template has version 1",
+    );
+}
+
 #[test]
 fn test_dummy_signer_verifier() {
     let signer = tink_tests::DummySigner::new("");
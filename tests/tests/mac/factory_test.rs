@@ -14,6 +14,10 @@
 //
 ////////////////////////////////////////////////////////////////////////////////
 
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
 use tink_core::{utils::wrap_err, TinkError};
 
 #[test]
@@ -83,6 +87,192 @@ fn test_factory_raw_key() {
         .expect("invalid primitive");
 }
 
+#[test]
+fn test_factory_mixed_raw_and_tink_keys() {
+    tink_mac::init();
+    let tag_size = 16;
+    let keyset = tink_tests::new_test_hmac_keyset(tag_size, tink_proto::OutputPrefixType::Raw);
+    let raw_key = keyset.key[1].clone();
+    let tink_key = keyset.key[3].clone();
+    assert_eq!(
+        tink_key.output_prefix_type,
+        tink_proto::OutputPrefixType::Tink as i32
+    );
+    let keyset_handle = tink_core::keyset::insecure::new_handle(keyset).unwrap();
+    let p = tink_mac::new(&keyset_handle).unwrap();
+
+    // The RAW primary produces an unprefixed tag.
+    let data = b"some data";
+    let tag = p.compute_mac(data).unwrap();
+    assert_eq!(tag.len(), tag_size as usize);
+    assert!(p.verify_mac(&tag, data).is_ok());
+
+    // Tags from the other RAW key and from the TINK key both verify against the whole keyset.
+    for key in [raw_key, tink_key] {
+        let expected_prefix = tink_core::cryptofmt::output_prefix(&key).unwrap();
+        let single = tink_tests::new_keyset(key.key_id, vec![key]);
+        let single_handle = tink_core::keyset::insecure::new_handle(single).unwrap();
+        let p2 = tink_mac::new(&single_handle).unwrap();
+        let tag = p2.compute_mac(data).unwrap();
+        assert_eq!(&tag[..expected_prefix.len()], &expected_prefix[..]);
+        assert!(p.verify_mac(&tag, data).is_ok());
+        assert!(p.verify_mac(&tag, b"other data").is_err());
+    }
+}
+
+#[test]
+fn test_factory_verify_exact_tag_length() {
+    tink_mac::init();
+    let tag_size = 16;
+    let data = b"some data";
+    for prefix_type in [
+        tink_proto::OutputPrefixType::Tink,
+        tink_proto::OutputPrefixType::Legacy,
+        tink_proto::OutputPrefixType::Raw,
+    ] {
+        let key = tink_tests::new_key(
+            &tink_tests::new_hmac_key_data(tink_proto::HashType::Sha256, tag_size),
+            tink_proto::KeyStatusType::Enabled,
+            42,
+            prefix_type,
+        );
+        let kh =
+            tink_core::keyset::insecure::new_handle(tink_tests::new_keyset(42, vec![key])).unwrap();
+        let p = tink_mac::new(&kh).unwrap();
+        let tag = p.compute_mac(data).unwrap();
+        let prefix_len = tag.len() - tag_size as usize;
+        assert_eq!(
+            prefix_len,
+            if prefix_type == tink_proto::OutputPrefixType::Raw {
+                0
+            } else {
+                tink_core::cryptofmt::NON_RAW_PREFIX_SIZE
+            }
+        );
+
+        // A tag of exactly the key's tag size (after the prefix) is accepted.
+        assert!(p.verify_mac(&tag, data).is_ok(), "{:?}", prefix_type);
+
+        // The candidate tag is not truncated to the key's tag size, so trailing bytes cause
+        // rejection rather than being ignored.
+        let mut long_tag = tag.clone();
+        long_tag.push(0);
+        assert!(p.verify_mac(&long_tag, data).is_err(), "{:?}", prefix_type);
+
+        // Nor is a truncated tag accepted.
+        assert!(
+            p.verify_mac(&tag[..tag.len() - 1], data).is_err(),
+            "{:?}",
+            prefix_type
+        );
+    }
+}
+
+/// MAC that appends its name to the data, and counts verification attempts.
+#[derive(Clone)]
+struct CountingMac {
+    name: String,
+    verify_calls: Arc<AtomicUsize>,
+}
+
+impl tink_core::Mac for CountingMac {
+    fn compute_mac(&self, data: &[u8]) -> Result<Vec<u8>, TinkError> {
+        Ok([data, self.name.as_bytes()].concat())
+    }
+
+    fn verify_mac(&self, mac: &[u8], data: &[u8]) -> Result<(), TinkError> {
+        self.verify_calls.fetch_add(1, Ordering::SeqCst);
+        if mac == self.compute_mac(data)? {
+            Ok(())
+        } else {
+            Err("CountingMac: invalid mac".into())
+        }
+    }
+}
+
+#[test]
+fn test_factory_verify_primary_first() {
+    use tink_core::registry::PrimitiveWrapper;
+    let verify_calls = Arc::new(AtomicUsize::new(0));
+    let mut keys = Vec::new();
+    let mut ps = tink_core::primitiveset::PrimitiveSet::new();
+    for key_id in 1..=50 {
+        let prefix_type = if key_id % 2 == 0 {
+            tink_proto::OutputPrefixType::Raw
+        } else {
+            tink_proto::OutputPrefixType::Tink
+        };
+        let key = tink_tests::new_key(
+            &tink_tests::new_hmac_key_data(tink_proto::HashType::Sha256, 16),
+            tink_proto::KeyStatusType::Enabled,
+            key_id,
+            prefix_type,
+        );
+        let mac = CountingMac {
+            name: format!("mac-{key_id}"),
+            verify_calls: verify_calls.clone(),
+        };
+        let entry = ps
+            .add(tink_core::Primitive::Mac(Box::new(mac)), &key)
+            .unwrap();
+        // Make the last RAW key the primary.
+        if key_id == 50 {
+            ps.primary = Some(entry);
+        }
+        keys.push(key);
+    }
+    let info = tink_core::keyset::insecure::new_handle(tink_tests::new_keyset(50, keys))
+        .unwrap()
+        .keyset_info();
+    let p = match tink_mac::MacWrapper.wrap(ps, &info).unwrap() {
+        tink_core::Primitive::Mac(p) => p,
+        _ => panic!("not a MAC primitive"),
+    };
+
+    let data = b"some data";
+    let tag = p.compute_mac(data).unwrap();
+    assert_eq!(tag, b"some datamac-50");
+    assert!(p.verify_mac(&tag, data).is_ok());
+    assert_eq!(verify_calls.swap(0, Ordering::SeqCst), 1);
+
+    // Tags from other keys still verify, after trying the primary.
+    assert!(p.verify_mac(b"some datamac-2", data).is_ok());
+    assert_eq!(verify_calls.swap(0, Ordering::SeqCst), 2);
+
+    // An invalid tag tries every RAW key exactly once.
+    assert!(p.verify_mac(b"some datamac-0", data).is_err());
+    assert_eq!(verify_calls.swap(0, Ordering::SeqCst), 25);
+}
+
+#[test]
+fn test_factory_verify_mac_batch() {
+    tink_mac::init();
+    for prefix_type in &[
+        tink_proto::OutputPrefixType::Tink,
+        tink_proto::OutputPrefixType::Raw,
+    ] {
+        let keyset = tink_tests::new_test_hmac_keyset(16, *prefix_type);
+        let keyset_handle = tink_core::keyset::insecure::new_handle(keyset).unwrap();
+        let p = tink_mac::new(&keyset_handle).unwrap();
+
+        let tag1 = p.compute_mac(b"first").unwrap();
+        let tag2 = p.compute_mac(b"second").unwrap();
+        let mut bad_tag = tag2.clone();
+        let last = bad_tag.len() - 1;
+        bad_tag[last] ^= 0x01;
+
+        let results = p.verify_mac_batch(&[
+            (&tag1, b"first"),
+            (&tag1, b"second"),
+            (&tag2, b"second"),
+            (&bad_tag, b"second"),
+            (&[], b"first"),
+        ]);
+        let valid: Vec<bool> = results.iter().map(|r| r.is_ok()).collect();
+        assert_eq!(valid, vec![true, false, true, false, false]);
+    }
+}
+
 #[allow(clippy::borrowed_box)]
 fn verify_mac_primitive(
     compute_primitive: &Box<dyn tink_core::Mac>,
@@ -150,3 +340,23 @@ fn test_factory_with_valid_primitive_set_type() {
 
     tink_mac::new(&good_kh).expect("calling new() with good keyset::Handle failed");
 }
+
+#[test]
+fn test_factory_stats() {
+    tink_mac::init();
+    let kh = tink_core::keyset::Handle::new(&tink_mac::hmac_sha256_tag128_key_template()).unwrap();
+    let stats = Arc::new(tink_core::monitoring::Stats::default());
+    let p = tink_mac::new_with_stats(&kh, stats.clone()).unwrap();
+
+    let data = b"some data";
+    let tag = p.compute_mac(data).unwrap();
+    assert!(p.verify_mac(&tag, data).is_ok());
+    assert!(p.verify_mac(&tag, b"other data").is_err());
+    let results = p.verify_mac_batch(&[(&tag, data), (&tag, b"more data")]);
+    assert!(results[0].is_ok());
+    assert!(results[1].is_err());
+
+    assert_eq!(stats.encryptions.load(Ordering::Relaxed), 1);
+    assert_eq!(stats.decryptions.load(Ordering::Relaxed), 4);
+    assert_eq!(stats.failures.load(Ordering::Relaxed), 2);
+}
@@ -15,7 +15,7 @@
 ////////////////////////////////////////////////////////////////////////////////
 
 use std::collections::HashSet;
-use tink_core::{utils::wrap_err, Mac, TinkError};
+use tink_core::{utils::wrap_err, ErrorKind, Mac, TinkError};
 use tink_proto::{prost::Message, HashType};
 use tink_tests::proto_encode;
 
@@ -53,6 +53,39 @@ fn test_get_primitive_with_invalid_input() {
     );
 }
 
+#[test]
+fn test_get_primitive_error_kinds() {
+    tink_mac::init();
+    let km = tink_core::registry::get_key_manager(tink_tests::HMAC_TYPE_URL)
+        .expect("HMAC key manager not found");
+
+    let mut bad_version_key = tink_tests::new_hmac_key(HashType::Sha256, 32);
+    bad_version_key.version = 1;
+    let err = km.primitive(&proto_encode(&bad_version_key)).err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidKey, "{}", err);
+
+    let err = km.primitive(&[0xff; 8]).err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::DecodeError, "{}", err);
+
+    let err = tink_core::registry::get_key_manager("some unknown type url")
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), ErrorKind::UnsupportedType, "{}", err);
+
+    let p = match km
+        .primitive(&proto_encode(&tink_tests::new_hmac_key(
+            HashType::Sha256,
+            32,
+        )))
+        .unwrap()
+    {
+        tink_core::Primitive::Mac(p) => p,
+        _ => panic!("failed to build Mac primitive"),
+    };
+    let err = p.verify_mac(&[0; 32], b"data").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::CryptoFailure, "{}", err);
+}
+
 #[test]
 fn test_new_key_multiple_times() {
     tink_mac::init();
@@ -87,6 +120,66 @@ fn test_new_key_basic() {
     }
 }
 
+#[test]
+fn test_new_key_from_reader() {
+    let format = tink_tests::new_hmac_key_format(HashType::Sha256, 32);
+    let serialized_format = proto_encode(&format);
+    let key_bytes: Vec<u8> = (0..format.key_size as u8).collect();
+    let mut reader = std::io::Cursor::new(key_bytes.clone());
+
+    let serialized_key = tink_mac::new_hmac_key_from_reader(&serialized_format, &mut reader)
+        .expect("new_hmac_key_from_reader failed");
+    let key = tink_proto::HmacKey::decode(serialized_key.as_ref()).unwrap();
+    assert_eq!(key.key_value, key_bytes);
+    assert!(validate_hmac_key(&format, &key).is_ok());
+
+    // The reader is now exhausted.
+    let result = tink_mac::new_hmac_key_from_reader(&serialized_format, &mut reader);
+    tink_tests::expect_err(result, "cannot read key material");
+    // Invalid formats are rejected as for `new_key`.
+    let result = tink_mac::new_hmac_key_from_reader(&[], &mut std::io::Cursor::new(key_bytes));
+    tink_tests::expect_err(result, "invalid key format");
+}
+
+#[test]
+fn test_import_key() {
+    tink_mac::init();
+    let km = tink_core::registry::get_key_manager(tink_tests::HMAC_TYPE_URL)
+        .expect("HMAC key manager not found");
+    let params = tink_proto::HmacParams {
+        hash: HashType::Sha256 as i32,
+        tag_size: 32,
+    };
+    let key_bytes: Vec<u8> = (0..32).collect();
+
+    let serialized_key = tink_mac::import_hmac_key(&params, &key_bytes).expect("import failed");
+    let key = tink_proto::HmacKey::decode(serialized_key.as_ref()).unwrap();
+    assert_eq!(key.key_value, key_bytes);
+    let p = match km.primitive(&serialized_key).unwrap() {
+        tink_core::Primitive::Mac(p) => p,
+        _ => panic!("failed to build Mac primitive"),
+    };
+    let tag = p.compute_mac(b"data").unwrap();
+    let want = tink_mac::subtle::Hmac::new(HashType::Sha256, &key_bytes, 32)
+        .unwrap()
+        .compute_mac(b"data")
+        .unwrap();
+    assert_eq!(tag, want);
+
+    tink_tests::expect_err(
+        tink_mac::import_hmac_key(&params, &key_bytes[..15]),
+        "key too short",
+    );
+    let bad_params = tink_proto::HmacParams {
+        hash: HashType::Sha256 as i32,
+        tag_size: 33,
+    };
+    tink_tests::expect_err(
+        tink_mac::import_hmac_key(&bad_params, &key_bytes),
+        "invalid imported key",
+    );
+}
+
 #[test]
 fn test_new_key_with_invalid_input() {
     tink_mac::init();
@@ -317,3 +410,17 @@ fn validate_hmac_primitive(
 
     Ok(())
 }
+
+#[test]
+fn test_supported_versions() {
+    tink_mac::init();
+    let km = tink_core::registry::get_key_manager(tink_tests::HMAC_TYPE_URL)
+        .expect("HMAC key manager not found");
+    assert_eq!(km.supported_versions(), 0..=0);
+
+    let mut key = tink_tests::new_hmac_key(HashType::Sha256, 32);
+    assert!(km.primitive(&proto_encode(&key)).is_ok());
+    key.version = 1;
+    assert!(!km.supported_versions().contains(&key.version));
+    tink_tests::expect_err(km.primitive(&proto_encode(&key)), "invalid version");
+}
@@ -61,6 +61,72 @@ fn test_key_templates() {
     }
 }
 
+#[test]
+fn test_parameterized_key_templates() {
+    tink_mac::init();
+    assert_eq!(
+        tink_mac::hmac_key_template(tink_proto::HashType::Sha256, 16),
+        tink_mac::hmac_sha256_tag128_key_template()
+    );
+    assert_eq!(
+        tink_mac::hmac_key_template(tink_proto::HashType::Sha512, 64),
+        tink_mac::hmac_sha512_tag512_key_template()
+    );
+    assert_eq!(
+        tink_mac::aes_cmac_key_template(16),
+        tink_mac::aes_cmac_tag128_key_template()
+    );
+
+    let test_cases = vec![
+        (
+            tink_mac::hmac_key_template(tink_proto::HashType::Sha1, 10),
+            10,
+        ),
+        (
+            tink_mac::hmac_key_template(tink_proto::HashType::Sha384, 24),
+            24,
+        ),
+        (tink_mac::aes_cmac_key_template(12), 12),
+    ];
+    for (template, tag_size) in test_cases {
+        assert_eq!(
+            template.output_prefix_type,
+            tink_proto::OutputPrefixType::Tink as i32
+        );
+        let handle = tink_core::keyset::Handle::new(&template).unwrap();
+        let primitive = tink_mac::new(&handle).unwrap();
+        let tag = primitive.compute_mac(b"some data").unwrap();
+        assert_eq!(tag.len(), tink_core::cryptofmt::TINK_PREFIX_SIZE + tag_size);
+        assert!(primitive.verify_mac(&tag, b"some data").is_ok());
+    }
+
+    // Invalid parameters are rejected at key generation.
+    assert!(tink_core::keyset::Handle::new(&tink_mac::hmac_key_template(
+        tink_proto::HashType::Sha256,
+        1
+    ))
+    .is_err());
+    assert!(tink_core::keyset::Handle::new(&tink_mac::aes_cmac_key_template(17)).is_err());
+}
+
+#[test]
+fn test_hmac_key_template_proto_round_trip() {
+    tink_mac::init();
+    for template in &[
+        tink_mac::hmac_sha256_tag128_key_template(),
+        tink_mac::hmac_sha256_tag256_key_template(),
+        tink_mac::hmac_sha512_tag256_key_template(),
+        tink_mac::hmac_sha512_tag512_key_template(),
+    ] {
+        tink_tests::key_template_proto_round_trip(
+            template,
+            |f: &tink_proto::HmacKeyFormat| f.version,
+            |k: &tink_proto::HmacKey| k.version,
+        )
+        .unwrap();
+    }
+}
+
 #[test]
 fn test_templates() {
     tink_mac::init();
@@ -80,6 +146,27 @@ fn test_templates() {
     check_cmac_template(template, 32, 16).expect("incorrect AESCMACTag128KeyTemplate");
 }
 
+#[test]
+fn test_hmac_template_tag_lengths() {
+    tink_mac::init();
+    let test_cases = vec![
+        (tink_mac::hmac_sha256_tag128_key_template(), 16),
+        (tink_mac::hmac_sha256_tag256_key_template(), 32),
+        (tink_mac::hmac_sha512_tag256_key_template(), 32),
+        (tink_mac::hmac_sha512_tag512_key_template(), 64),
+    ];
+    for (template, tag_size) in test_cases {
+        let format = tink_proto::HmacKeyFormat::decode(template.value.as_ref()).unwrap();
+        assert_eq!(format.params.unwrap().tag_size as usize, tag_size);
+
+        let handle = tink_core::keyset::Handle::new(&template).unwrap();
+        let primitive = tink_mac::new(&handle).unwrap();
+        let tag = primitive.compute_mac(b"some data").unwrap();
+        // Templates use the TINK output prefix, which precedes the tag itself.
+        assert_eq!(tag.len(), tink_core::cryptofmt::TINK_PREFIX_SIZE + tag_size);
+    }
+}
+
 fn check_hmac_template(
     template: tink_proto::KeyTemplate,
     key_size: usize,
@@ -172,3 +172,32 @@ fn test_hmac_truncation() {
         }
     }
 }
+
+#[test]
+fn test_hmac_incremental() {
+    let data = get_random_bytes(1000);
+    for hash_alg in [HashType::Sha1, HashType::Sha256, HashType::Sha512] {
+        let cipher = tink_mac::subtle::Hmac::new(hash_alg, KEY, 16).unwrap();
+        let expected = cipher.compute_mac(&data).unwrap();
+        for chunk_size in [1, 7, 64, 999, 1000] {
+            let mut ctx = cipher.start();
+            for chunk in data.chunks(chunk_size) {
+                ctx.update(chunk);
+            }
+            assert!(ctx.clone().finalize_verify(&expected).is_ok());
+            assert_eq!(
+                ctx.finalize().unwrap(),
+                expected,
+                "chunk size {}",
+                chunk_size
+            );
+        }
+
+        // Contexts are independent of each other and of one-shot computations.
+        let mut ctx = cipher.start();
+        ctx.update(&data[..500]);
+        assert_eq!(cipher.compute_mac(DATA).unwrap().len(), 16);
+        ctx.update(b"tampered");
+        tink_tests::expect_err(ctx.finalize_verify(&expected), "Invalid MAC");
+    }
+}
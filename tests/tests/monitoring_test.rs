@@ -0,0 +1,161 @@
+// Copyright 2020 The Tink-Rust Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+// Registering a monitoring client affects every primitive created in the same process, so this
+// lives in its own test binary.
+
+use lazy_static::lazy_static;
+use std::sync::{Arc, Mutex, Once};
+use tink_core::{monitoring, KeyId, TinkError};
+
+#[derive(Clone, Debug, PartialEq)]
+enum Event {
+    Log {
+        api: &'static str,
+        key_id: KeyId,
+        num_bytes: usize,
+    },
+    Failure {
+        api: &'static str,
+    },
+    Warning {
+        api: &'static str,
+        message: String,
+    },
+}
+
+struct MockLogger {
+    api: &'static str,
+    events: Arc<Mutex<Vec<Event>>>,
+}
+
+impl monitoring::Logger for MockLogger {
+    fn log(&self, key_id: KeyId, num_bytes: usize) {
+        self.events.lock().unwrap().push(Event::Log {
+            api: self.api,
+            key_id,
+            num_bytes,
+        });
+    }
+    fn log_failure(&self) {
+        self.events
+            .lock()
+            .unwrap()
+            .push(Event::Failure { api: self.api });
+    }
+    fn log_warning(&self, message: &str) {
+        self.events.lock().unwrap().push(Event::Warning {
+            api: self.api,
+            message: message.to_string(),
+        });
+    }
+}
+
+/// Client that records events in a separate list for each primitive, so that tests running in
+/// parallel do not see each other's events.
+struct MockClient;
+
+impl monitoring::Client for MockClient {
+    fn new_logger(
+        &self,
+        context: &monitoring::Context,
+    ) -> Result<Box<dyn monitoring::Logger>, TinkError> {
+        assert!(!context.keyset_info.key_info.is_empty());
+        let events = match context.primitive {
+            "aead" => AEAD_EVENTS.clone(),
+            "keyset" => KEYSET_EVENTS.clone(),
+            p => panic!("unexpected primitive {}", p),
+        };
+        Ok(Box::new(MockLogger {
+            api: context.api_function,
+            events,
+        }))
+    }
+}
+
+lazy_static! {
+    static ref AEAD_EVENTS: Arc<Mutex<Vec<Event>>> = Arc::new(Mutex::new(Vec::new()));
+    static ref KEYSET_EVENTS: Arc<Mutex<Vec<Event>>> = Arc::new(Mutex::new(Vec::new()));
+}
+
+static REGISTER: Once = Once::new();
+
+fn register_mock_client() {
+    REGISTER.call_once(|| tink_core::registry::register_monitoring_client(MockClient));
+}
+
+#[test]
+fn test_aead_decrypt_logs_key_id() {
+    tink_aead::init();
+    register_mock_client();
+    let events = AEAD_EVENTS.clone();
+
+    let mut ksm = tink_core::keyset::Manager::new();
+    let old_id = ksm.rotate(&tink_aead::aes128_gcm_key_template()).unwrap();
+    let old_aead = tink_aead::new(&ksm.handle().unwrap()).unwrap();
+    let old_ct = old_aead.encrypt(b"old", b"aad").unwrap();
+
+    let new_id = ksm.rotate(&tink_aead::aes256_gcm_key_template()).unwrap();
+    let aead = tink_aead::new(&ksm.handle().unwrap()).unwrap();
+    let new_ct = aead.encrypt(b"new", b"aad").unwrap();
+    events.lock().unwrap().clear();
+
+    assert_eq!(aead.decrypt(&old_ct, b"aad").unwrap(), b"old");
+    assert_eq!(aead.decrypt(&new_ct, b"aad").unwrap(), b"new");
+    assert!(aead.decrypt(&new_ct, b"wrong aad").is_err());
+
+    assert_eq!(
+        *events.lock().unwrap(),
+        vec![
+            Event::Log {
+                api: "decrypt",
+                key_id: old_id,
+                num_bytes: old_ct.len(),
+            },
+            Event::Log {
+                api: "decrypt",
+                key_id: new_id,
+                num_bytes: new_ct.len(),
+            },
+            Event::Failure { api: "decrypt" },
+        ]
+    );
+}
+
+#[test]
+fn test_builder_warns_on_replaced_primary() {
+    tink_aead::init();
+    register_mock_client();
+    let kt = tink_aead::aes128_gcm_key_template();
+
+    let mut builder = tink_core::keyset::HandleBuilder::new();
+    let first_id = builder.add_generated_entry(&kt, true).unwrap();
+    builder.add_generated_entry(&kt, false).unwrap();
+    assert!(KEYSET_EVENTS.lock().unwrap().is_empty());
+
+    let second_id = builder.add_generated_entry(&kt, true).unwrap();
+    let h = builder.build().unwrap();
+    assert_eq!(h.keyset_info().primary_key_id, second_id);
+    assert_eq!(
+        *KEYSET_EVENTS.lock().unwrap(),
+        vec![Event::Warning {
+            api: "build",
+            message: format!(
+                "keyset::HandleBuilder: primary key {first_id} replaced by {second_id}"
+            ),
+        }]
+    );
+}
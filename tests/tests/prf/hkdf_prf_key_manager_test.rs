@@ -205,6 +205,7 @@ fn gen_invalid_hkdf_keys() -> Vec<Vec<u8>> {
     let sha1_key = tink_tests::new_hkdf_prf_key(HashType::Sha1, &[]);
     let unknown_hash_key = tink_tests::new_hkdf_prf_key(HashType::UnknownHash, &[]);
     let non_key = tink_tests::new_hkdf_prf_params(HashType::Sha256, &[]);
+    let long_salt_key = tink_tests::new_hkdf_prf_key(HashType::Sha256, &[0x42; 1025]);
 
     vec![
         proto_encode(&non_key),
@@ -212,6 +213,7 @@ fn gen_invalid_hkdf_keys() -> Vec<Vec<u8>> {
         proto_encode(&short_key),
         proto_encode(&sha1_key),
         proto_encode(&unknown_hash_key),
+        proto_encode(&long_salt_key),
     ]
 }
 
@@ -249,6 +251,7 @@ fn gen_valid_hkdf_keys() -> Vec<tink_proto::HkdfPrfKey> {
         tink_tests::new_hkdf_prf_key(HashType::Sha512, &[]),
         tink_tests::new_hkdf_prf_key(HashType::Sha256, &[0x01, 0x03, 0x42]),
         tink_tests::new_hkdf_prf_key(HashType::Sha512, &[0x01, 0x03, 0x42]),
+        tink_tests::new_hkdf_prf_key(HashType::Sha256, &[0x42; 1024]),
     ]
 }
 
@@ -53,6 +53,75 @@ fn test_get_primitive_hmac_with_invalid_input() {
     );
 }
 
+#[test]
+fn test_get_primitive_hmac_deterministic() {
+    tink_prf::init();
+    let km = tink_core::registry::get_key_manager(tink_tests::HMAC_PRF_TYPE_URL)
+        .expect("HMAC PRF key manager not found");
+    let data = tink_core::subtle::random::get_random_bytes(20);
+    for test_key in gen_valid_hmac_prf_keys() {
+        let serialized_key = proto_encode(&test_key);
+        let prf1: Box<dyn Prf> = km.primitive(&serialized_key).unwrap().into();
+        let prf2: Box<dyn Prf> = km.primitive(&serialized_key).unwrap().into();
+
+        let out1 = prf1.compute_prf(&data, 16).unwrap();
+        let out2 = prf1.compute_prf(&data, 16).unwrap();
+        let out3 = prf2.compute_prf(&data, 16).unwrap();
+        assert_eq!(out1, out2, "repeated PRF computation differs");
+        assert_eq!(
+            out1, out3,
+            "PRF computation differs across primitives for the same key"
+        );
+    }
+}
+
+#[test]
+fn test_get_primitive_hmac_short_key() {
+    tink_prf::init();
+    let km = tink_core::registry::get_key_manager(tink_tests::HMAC_PRF_TYPE_URL)
+        .expect("HMAC PRF key manager not found");
+
+    let mut key = tink_tests::new_hmac_prf_key(HashType::Sha256);
+    key.key_value = vec![0x42; 15];
+    tink_tests::expect_err(km.primitive(&proto_encode(&key)), "key too short");
+
+    key.key_value = vec![0x42; 16];
+    assert!(
+        km.primitive(&proto_encode(&key)).is_ok(),
+        "16-byte key should be accepted"
+    );
+}
+
+#[test]
+fn test_new_key_hmac_hash_types() {
+    tink_prf::init();
+    let km = tink_core::registry::get_key_manager(tink_tests::HMAC_PRF_TYPE_URL)
+        .expect("HMAC PRF key manager not found");
+    for hash in &[HashType::Sha256, HashType::Sha384, HashType::Sha512] {
+        let serialized_format = proto_encode(&tink_tests::new_hmac_prf_key_format(*hash));
+        let serialized_key = km
+            .new_key(&serialized_format)
+            .unwrap_or_else(|e| panic!("unexpected error for {:?}: {:?}", hash, e));
+        let p = km.primitive(&serialized_key).unwrap();
+        let prf: Box<dyn Prf> = p.into();
+        assert!(prf.compute_prf(b"data", 16).is_ok());
+    }
+
+    // SHA-1 is rejected for new keys, but existing SHA-1 keys still work.
+    let serialized_format = proto_encode(&tink_tests::new_hmac_prf_key_format(HashType::Sha1));
+    tink_tests::expect_err(km.new_key(&serialized_format), "not supported for new keys");
+    tink_tests::expect_err(
+        km.new_key_data(&serialized_format),
+        "not supported for new keys",
+    );
+    let sha1_key = tink_tests::new_hmac_prf_key(HashType::Sha1);
+    assert!(km.primitive(&proto_encode(&sha1_key)).is_ok());
+
+    let mut short_format = tink_tests::new_hmac_prf_key_format(HashType::Sha256);
+    short_format.key_size = 15;
+    tink_tests::expect_err(km.new_key(&proto_encode(&short_format)), "key too short");
+}
+
 #[test]
 fn test_new_key_hmac_multiple_times() {
     tink_prf::init();
@@ -226,12 +295,15 @@ fn gen_invalid_hmac_prf_key_formats() -> Vec<Vec<u8>> {
         proto_encode(&tink_tests::new_hmac_prf_key_format(
             tink_proto::HashType::UnknownHash,
         )),
+        // hash not permitted for new keys
+        proto_encode(&tink_tests::new_hmac_prf_key_format(
+            tink_proto::HashType::Sha1,
+        )),
     ]
 }
 
 fn gen_valid_hmac_prf_key_formats() -> Vec<tink_proto::HmacPrfKeyFormat> {
     vec![
-        tink_tests::new_hmac_prf_key_format(tink_proto::HashType::Sha1),
         tink_tests::new_hmac_prf_key_format(tink_proto::HashType::Sha256),
         tink_tests::new_hmac_prf_key_format(tink_proto::HashType::Sha384),
         tink_tests::new_hmac_prf_key_format(tink_proto::HashType::Sha512),
@@ -124,6 +124,57 @@ fn test_factory_basic() {
     }
 }
 
+#[test]
+fn test_factory_from_key_data() {
+    // Initialization is idempotent.
+    tink_prf::init();
+    tink_prf::init();
+    for type_url in &[
+        tink_tests::HMAC_PRF_TYPE_URL,
+        tink_tests::HKDF_PRF_TYPE_URL,
+        tink_tests::AES_CMAC_PRF_TYPE_URL,
+    ] {
+        assert!(tink_core::registry::get_key_manager(type_url).is_ok());
+    }
+
+    let key_datas = [
+        tink_tests::new_hmac_prf_key_data(tink_proto::HashType::Sha256),
+        tink_tests::new_hkdf_prf_key_data(tink_proto::HashType::Sha256, b"salt"),
+        tink_tests::new_aes_cmac_prf_key_data(),
+    ];
+    let keys: Vec<_> = key_datas
+        .iter()
+        .zip(1..)
+        .map(|(kd, id)| {
+            tink_tests::new_key(
+                kd,
+                tink_proto::KeyStatusType::Enabled,
+                id,
+                tink_proto::OutputPrefixType::Raw,
+            )
+        })
+        .collect();
+    let keyset = tink_tests::new_keyset(2, keys);
+    let handle = tink_core::keyset::insecure::new_handle(keyset).unwrap();
+    let prf_set = tink_prf::Set::new(&handle).unwrap();
+    assert_eq!(prf_set.primary_id, 2);
+    assert_eq!(prf_set.prfs.len(), 3);
+
+    // Output is a deterministic function of the key and input, including across `Set`s built from
+    // the same keyset.
+    let prf_set2 = tink_prf::Set::new(&handle).unwrap();
+    for (id, prf) in &prf_set.prfs {
+        let out = prf.compute_prf(b"input", 16).unwrap();
+        assert_eq!(out, prf.compute_prf(b"input", 16).unwrap());
+        assert_eq!(out, prf_set2.prfs[id].compute_prf(b"input", 16).unwrap());
+        assert_ne!(out, prf.compute_prf(b"other input", 16).unwrap());
+    }
+    assert_eq!(
+        prf_set.compute_primary_prf(b"input", 32).unwrap(),
+        prf_set.prfs[&2].compute_prf(b"input", 32).unwrap()
+    );
+}
+
 #[test]
 fn test_non_raw_keys() {
     tink_prf::init();
@@ -188,3 +239,34 @@ fn run_z_tests(results: Vec<Vec<u8>>) {
         }
     }
 }
+
+#[test]
+fn test_factory_output_length_bounds() {
+    tink_prf::init();
+    let testdata = [
+        (tink_prf::aes_cmac_prf_key_template(), 16),
+        (tink_prf::hmac_sha256_prf_key_template(), 32),
+        (tink_prf::hmac_sha512_prf_key_template(), 64),
+        (tink_prf::hkdf_sha256_prf_key_template(), 8160),
+    ];
+    for (template, max_length) in testdata.iter() {
+        let handle = tink_core::keyset::Handle::new(template).expect("Could not create handle");
+        let prf_set = tink_prf::Set::new(&handle).expect("Could not create tink_prf::Set");
+
+        let output = prf_set
+            .compute_primary_prf(b"The input", 0)
+            .expect("zero-length PRF output should succeed");
+        assert!(output.is_empty(), "{}", template.type_url);
+        let output = prf_set
+            .compute_primary_prf(b"The input", *max_length)
+            .expect("max-length PRF output should succeed");
+        assert_eq!(output.len(), *max_length, "{}", template.type_url);
+        assert!(
+            prf_set
+                .compute_primary_prf(b"The input", max_length + 1)
+                .is_err(),
+            "{}",
+            template.type_url
+        );
+    }
+}
@@ -43,6 +43,7 @@ fn test_vectors_rfc4493() {
     }
 }
 
+#[allow(dead_code)]
 #[derive(Debug, Deserialize)]
 pub struct TestData {
     #[serde(flatten)]
@@ -51,6 +52,7 @@ pub struct TestData {
     pub test_groups: Vec<TestGroup>,
 }
 
+#[allow(dead_code)]
 #[derive(Debug, Deserialize)]
 pub struct TestGroup {
     #[serde(flatten)]
@@ -205,3 +207,10 @@ fn test_aes_cmac_prf_output_length() {
         );
     }
 }
+
+#[test]
+fn test_aes_cmac_prf_incremental_unsupported() {
+    let prf = AesCmacPrf::new(&[0; 32]).unwrap();
+    let err = prf.start_incremental().err().unwrap();
+    assert_eq!(err.kind(), tink_core::ErrorKind::UnsupportedType);
+}
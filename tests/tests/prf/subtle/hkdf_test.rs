@@ -17,7 +17,7 @@
 use maplit::hashmap;
 use serde::Deserialize;
 use tink_core::Prf;
-use tink_prf::subtle::{validate_hkdf_prf_params, HkdfPrf};
+use tink_prf::subtle::{validate_hkdf_prf_params, HkdfPrf, MAX_HKDF_SALT_SIZE_IN_BYTES};
 use tink_proto::HashType;
 
 struct Rfc5869Test {
@@ -353,4 +353,54 @@ fn test_validate_hkdf_prf_params() {
         validate_hkdf_prf_params(HashType::Sha1, 32, &[]).is_err(),
         "Weak hash function not detected for HKDF PRF params"
     );
+    assert!(
+        validate_hkdf_prf_params(HashType::Sha256, 32, &[0; MAX_HKDF_SALT_SIZE_IN_BYTES]).is_ok(),
+        "Unexpected error for maximum length salt in HKDF PRF params"
+    );
+    tink_tests::expect_err(
+        validate_hkdf_prf_params(HashType::Sha256, 32, &[0; MAX_HKDF_SALT_SIZE_IN_BYTES + 1]),
+        "salt too long",
+    );
+}
+
+#[test]
+fn test_hkdf_prf_with_info() {
+    let prf = HkdfPrf::new(HashType::Sha256, &[0x01; 32], &[]).unwrap();
+    let input = b"input";
+
+    let out1 = prf.compute_prf_with_info(input, b"info-1", 32).unwrap();
+    let out2 = prf.compute_prf_with_info(input, b"info-2", 32).unwrap();
+    assert_ne!(out1, out2, "different info should give different output");
+    assert_eq!(
+        out1,
+        prf.compute_prf_with_info(input, b"info-1", 32).unwrap()
+    );
+    assert_ne!(
+        prf.compute_prf_with_info(input, &[], 32).unwrap(),
+        prf.compute_prf(input, 32).unwrap()
+    );
+    // Moving bytes between input and info changes the output.
+    assert_ne!(
+        prf.compute_prf_with_info(b"ab", b"c", 32).unwrap(),
+        prf.compute_prf_with_info(b"a", b"bc", 32).unwrap()
+    );
+
+    // The HKDF info is the length-prefixed input followed by the info.
+    let mut encoded = (input.len() as u64).to_be_bytes().to_vec();
+    encoded.extend_from_slice(input);
+    encoded.extend_from_slice(b"info-1");
+    assert_eq!(out1, prf.compute_prf(&encoded, 32).unwrap());
+}
+
+#[test]
+fn test_hkdf_prf_incremental() {
+    let key = tink_core::subtle::random::get_random_bytes(32);
+    let input = tink_core::subtle::random::get_random_bytes(1000);
+    let prf = HkdfPrf::new(HashType::Sha256, &key, b"salt").unwrap();
+    let expected = prf.compute_prf(&input, 100).unwrap();
+    let mut ctx = prf.start_incremental().unwrap();
+    for chunk in input.chunks(77) {
+        ctx.update(chunk);
+    }
+    assert_eq!(ctx.finalize(100).unwrap(), expected);
 }
@@ -281,3 +281,37 @@ fn test_validate_hmac_prf_params() {
         "Unknown hash function not detected for HMAC PRF params"
     );
 }
+
+#[test]
+fn test_hmac_prf_incremental() {
+    let key = tink_core::subtle::random::get_random_bytes(32);
+    let input = tink_core::subtle::random::get_random_bytes(1000);
+    for (hash, length) in [
+        (HashType::Sha1, 20),
+        (HashType::Sha256, 32),
+        (HashType::Sha512, 64),
+    ] {
+        let prf = HmacPrf::new(hash, &key).unwrap();
+        let expected = prf.compute_prf(&input, length).unwrap();
+        for chunk_size in [1, 13, 128, 1000] {
+            let mut ctx = prf.start_incremental().unwrap();
+            for chunk in input.chunks(chunk_size) {
+                ctx.update(chunk);
+            }
+            assert_eq!(
+                ctx.finalize(length).unwrap(),
+                expected,
+                "hash {:?}, chunk size {}",
+                hash,
+                chunk_size
+            );
+        }
+        // Shorter output is a prefix of the full output.
+        let mut ctx = prf.start_incremental().unwrap();
+        ctx.update(&input);
+        assert_eq!(ctx.finalize(16).unwrap(), &expected[..16]);
+
+        let ctx = prf.start_incremental().unwrap();
+        assert!(ctx.finalize(length + 1).is_err());
+    }
+}
@@ -0,0 +1,53 @@
+// Copyright 2020 The Tink-Rust Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+// Clearing the global registry affects every test in the same process, so this lives in its own
+// test binary.
+
+use std::sync::Arc;
+
+#[test]
+fn test_registry_clear() {
+    let km = Arc::new(tink_tests::DummyAeadKeyManager::default());
+    let type_url = tink_core::registry::KeyManager::type_url(km.as_ref());
+    tink_core::registry::register_key_manager(km).unwrap();
+    tink_core::registry::register_template_generator("DUMMY", || tink_proto::KeyTemplate {
+        type_url: "dummy".to_string(),
+        value: vec![],
+        output_prefix_type: tink_proto::OutputPrefixType::Tink as i32,
+    })
+    .unwrap();
+    tink_core::registry::register_kms_client(tink_tests::DummyKmsClient {});
+    tink_core::registry::register_primitive_wrapper(Arc::new(tink_mac::MacWrapper)).unwrap();
+
+    assert!(tink_core::registry::get_key_manager(type_url).is_ok());
+    assert!(tink_core::registry::get_template_generator("DUMMY").is_some());
+    assert!(tink_core::registry::get_kms_client("dummy").is_ok());
+    assert!(tink_core::registry::get_primitive_wrapper("mac").is_ok());
+
+    tink_core::registry::clear();
+
+    assert!(tink_core::registry::get_key_manager(type_url).is_err());
+    assert!(tink_core::registry::get_template_generator("DUMMY").is_none());
+    assert!(tink_core::registry::template_names().is_empty());
+    assert!(tink_core::registry::get_kms_client("dummy").is_err());
+    assert!(tink_core::registry::get_primitive_wrapper("mac").is_err());
+
+    // The same key manager can be registered again after clearing.
+    tink_core::registry::register_key_manager(Arc::new(tink_tests::DummyAeadKeyManager::default()))
+        .unwrap();
+    assert!(tink_core::registry::get_key_manager(type_url).is_ok());
+}
@@ -106,7 +106,7 @@ fn test_ecdsa_sign_new_key_with_invalid_input() {
     let km = tink_core::registry::get_key_manager(tink_tests::ECDSA_SIGNER_TYPE_URL)
         .expect("cannot obtain EcdsaSigner key manager");
     // invalid hash and curve type
-    let test_params = vec![
+    let test_params = [
         EcdsaParams {
             hash_type: HashType::UnknownHash as i32,
             curve: EllipticCurveType::NistP256 as i32,
@@ -702,3 +702,46 @@ fn test_primitive_with_invalid_key() {
         tink_tests::expect_err(result, err_msg);
     }
 }
+
+#[test]
+fn test_primitive_private_scalar_range() {
+    tink_signature::init();
+    let km = tink_core::registry::get_key_manager(tink_tests::ECDSA_SIGNER_TYPE_URL).unwrap();
+    let key_with_scalar = |key_value: Vec<u8>| EcdsaPrivateKey {
+        version: tink_signature::ECDSA_SIGNER_KEY_VERSION,
+        public_key: Some(EcdsaPublicKey {
+            version: tink_signature::ECDSA_VERIFIER_KEY_VERSION,
+            params: Some(EcdsaParams {
+                hash_type: HashType::Sha256 as i32,
+                curve: EllipticCurveType::NistP256 as i32,
+                encoding: EcdsaSignatureEncoding::Der as i32,
+            }),
+            x: hex::decode("7ea7cc506e46cfb2bbdb1503b0fb5f4edbf6e9830459b64a4064455045a7a58c")
+                .unwrap(),
+            y: hex::decode("fe38bbb204c8afab3691af996eeb78aa60b8c24ea6dbe13fb6df788786fb2230")
+                .unwrap(),
+        }),
+        key_value,
+    };
+
+    let valid = key_with_scalar(
+        hex::decode("2fa00a02762046c8797d5cc62cd1ba41ecf11f0996e3c5169ca8c891af8055c3").unwrap(),
+    );
+    assert!(km.primitive(&tink_tests::proto_encode(&valid)).is_ok());
+
+    let zero = key_with_scalar(vec![0; 32]);
+    tink_tests::expect_err(
+        km.primitive(&tink_tests::proto_encode(&zero)),
+        "private scalar is zero",
+    );
+
+    let order = tink_core::subtle::ec_util::curve_params(EllipticCurveType::NistP256)
+        .unwrap()
+        .order
+        .to_vec();
+    let out_of_range = key_with_scalar(order);
+    tink_tests::expect_err(
+        km.primitive(&tink_tests::proto_encode(&out_of_range)),
+        "out of range",
+    );
+}
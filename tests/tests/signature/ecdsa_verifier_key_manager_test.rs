@@ -74,6 +74,25 @@ fn test_ecdsa_verify_get_primitive_with_invalid_input() {
     );
 }
 
+#[test]
+fn test_ecdsa_verify_get_primitive_rejects_private_key() {
+    tink_signature::init();
+    let km = tink_core::registry::get_key_manager(tink_tests::ECDSA_VERIFIER_TYPE_URL)
+        .expect("cannot obtain EcdsaVerifier key manager");
+    for test_param in gen_valid_ecdsa_params() {
+        let priv_key =
+            tink_tests::new_random_ecdsa_private_key(test_param.hash_type, test_param.curve);
+        tink_tests::expect_err(
+            km.primitive(&tink_tests::proto_encode(&priv_key)),
+            "got an EcdsaPrivateKey, expected an EcdsaPublicKey",
+        );
+
+        // The embedded public key is accepted.
+        let pub_key = priv_key.public_key.unwrap();
+        assert!(km.primitive(&tink_tests::proto_encode(&pub_key)).is_ok());
+    }
+}
+
 #[test]
 fn test_new_key_fails() {
     tink_signature::init();
@@ -161,6 +161,80 @@ fn test_signer_verify_multiple_keys() {
     );
 }
 
+#[test]
+fn test_verify_raw_signature_with_tink_keys() {
+    tink_signature::init();
+    let mut km = tink_core::keyset::Manager::new();
+    let tink_id = km
+        .add(&tink_signature::ed25519_key_template(), false)
+        .unwrap();
+    km.add(&tink_signature::ecdsa_p256_key_template(), false)
+        .unwrap();
+    // Sign under a RAW key as primary.
+    km.add(&tink_signature::ed25519_key_without_prefix_template(), true)
+        .unwrap();
+    let raw_kh = km.handle().unwrap();
+    let data = get_random_bytes(200);
+    let raw_sig = tink_signature::sign(&raw_kh, &data).unwrap();
+    assert_eq!(raw_sig.len(), 64, "RAW signature should carry no prefix");
+
+    // Verify through a handle whose primary is a TINK key.
+    km.set_primary(tink_id).unwrap();
+    let pub_kh = km.handle().unwrap().public().unwrap();
+    assert!(tink_signature::verify(&pub_kh, &raw_sig, &data).is_ok());
+    let verifier = tink_signature::new_verifier(&pub_kh).unwrap();
+    assert!(verifier.verify(&raw_sig, &data).is_ok());
+
+    // A truncated (or otherwise modified) RAW signature fails.
+    tink_tests::expect_err(
+        verifier.verify(&raw_sig[..raw_sig.len() - 1], &data),
+        "invalid signature",
+    );
+    tink_tests::expect_err(verifier.verify(&raw_sig[..3], &data), "invalid signature");
+    tink_tests::expect_err(verifier.verify(&[], &data), "invalid signature");
+    assert!(verifier.verify(&raw_sig, b"other data").is_err());
+}
+
+#[test]
+fn test_verifier_reuse_many_signatures() {
+    tink_signature::init();
+    let mut km = tink_core::keyset::Manager::new();
+    km.rotate(&tink_signature::ecdsa_p256_key_template())
+        .unwrap();
+    km.rotate(&tink_signature::ed25519_key_template()).unwrap();
+    km.add(&tink_signature::ecdsa_p256_raw_key_template(), false)
+        .unwrap();
+    let priv_kh = km.handle().unwrap();
+    let key_ids: Vec<_> = priv_kh
+        .keyset_info()
+        .key_info
+        .iter()
+        .map(|k| k.key_id)
+        .collect();
+
+    // Sign with each key in turn as primary.
+    let mut signatures = Vec::new();
+    for (i, key_id) in key_ids.iter().enumerate() {
+        km.set_primary(*key_id).unwrap();
+        let signer = tink_signature::new_signer(&km.handle().unwrap()).unwrap();
+        for j in 0..20 {
+            let data = format!("message {i}-{j}").into_bytes();
+            let sig = signer.sign(&data).unwrap();
+            signatures.push((data, sig));
+        }
+    }
+
+    // A single verifier, with its public keys parsed once, verifies all of them.
+    let verifier = tink_signature::new_verifier(&priv_kh.public().unwrap()).unwrap();
+    for (data, sig) in &signatures {
+        assert!(verifier.verify(sig, data).is_ok());
+    }
+    for (i, (data, _)) in signatures.iter().enumerate() {
+        let other_sig = &signatures[(i + 1) % signatures.len()].1;
+        assert!(verifier.verify(other_sig, data).is_err());
+    }
+}
+
 fn new_ecdsa_keyset_keypair(
     hash_type: tink_proto::HashType,
     curve: tink_proto::EllipticCurveType,
@@ -247,3 +321,37 @@ fn test_factory_with_valid_primitive_set_type() {
         "calling new_verifier() with good keyset::handle failed"
     );
 }
+
+#[test]
+fn test_one_shot_sign_verify() {
+    tink_signature::init();
+    let priv_kh =
+        tink_core::keyset::Handle::new(&tink_signature::ecdsa_p256_key_template()).unwrap();
+    let pub_kh = priv_kh.public().unwrap();
+    let data = get_random_bytes(1211);
+
+    let sig = tink_signature::sign(&priv_kh, &data).expect("signing failed");
+    assert!(
+        tink_signature::verify(&pub_kh, &sig, &data).is_ok(),
+        "verification failed"
+    );
+    // Signatures from the one-shot helper are interchangeable with those from the primitive.
+    let verifier = tink_signature::new_verifier(&pub_kh).unwrap();
+    assert!(verifier.verify(&sig, &data).is_ok());
+    let sig2 = tink_signature::new_signer(&priv_kh)
+        .unwrap()
+        .sign(&data)
+        .unwrap();
+    assert!(tink_signature::verify(&pub_kh, &sig2, &data).is_ok());
+
+    // Modified data or a different key should fail verification.
+    assert!(tink_signature::verify(&pub_kh, &sig, b"other data").is_err());
+    let other_kh = tink_core::keyset::Handle::new(&tink_signature::ecdsa_p256_key_template())
+        .unwrap()
+        .public()
+        .unwrap();
+    assert!(tink_signature::verify(&other_kh, &sig, &data).is_err());
+
+    // A public keyset cannot be used for signing.
+    assert!(tink_signature::sign(&pub_kh, &data).is_err());
+}
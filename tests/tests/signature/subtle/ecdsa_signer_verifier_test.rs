@@ -98,6 +98,45 @@ fn test_sign_verify() {
     }
 }
 
+#[test]
+fn test_sign_deterministic_nonces() {
+    let mut csprng = p256::elliptic_curve::rand_core::OsRng {};
+    let data = get_random_bytes(20);
+    let hash = HashType::Sha256;
+    let curve = EllipticCurveType::NistP256;
+    for encoding in [
+        EcdsaSignatureEncoding::Der,
+        EcdsaSignatureEncoding::IeeeP1363,
+    ] {
+        let secret_key = p256::ecdsa::SigningKey::random(&mut csprng);
+        let pub_key = EcdsaPublicKey::NistP256(p256::ecdsa::VerifyingKey::from(&secret_key));
+        let signer = subtle::EcdsaSigner::new_from_private_key(
+            hash,
+            curve,
+            encoding,
+            EcdsaPrivateKey::NistP256(secret_key),
+        )
+        .unwrap();
+        let verifier =
+            subtle::EcdsaVerifier::new_from_public_key(hash, curve, encoding, pub_key).unwrap();
+
+        // Random mode (the default) gives a different signature each time.
+        let sig1 = signer.sign(&data).unwrap();
+        let sig2 = signer.sign(&data).unwrap();
+        assert_ne!(sig1, sig2);
+
+        let signer = signer.with_deterministic_nonces(true);
+        let sig1 = signer.sign(&data).unwrap();
+        let sig2 = signer.sign(&data).unwrap();
+        assert_eq!(sig1, sig2);
+        assert!(verifier.verify(&sig1, &data).is_ok());
+        assert_ne!(signer.sign(b"other data").unwrap(), sig1);
+
+        let signer = signer.with_deterministic_nonces(false);
+        assert_ne!(signer.sign(&data).unwrap(), sig1);
+    }
+}
+
 #[test]
 fn test_ecdsa_invalid_signer_params() {
     let mut csprng = p256::elliptic_curve::rand_core::OsRng {};
@@ -0,0 +1,185 @@
+// Copyright 2020 The Tink-Rust Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+use tink_proto::{
+    RsaSsaPkcs1PrivateKey, RsaSsaPkcs1PublicKey, RsaSsaPssPrivateKey, RsaSsaPssPublicKey,
+};
+use tink_signature::subtle::{
+    validate_rsa_ssa_pkcs1_private_key, validate_rsa_ssa_pss_private_key,
+};
+use tink_tests::expect_err;
+
+// 2048-bit RSA key generated with `openssl genrsa`.
+const RSA_N: &str = concat!(
+    "d425bf024da5950980c2a6d7cd74c86d3cc3934410d2a5c0b5a63b421ea7537d",
+    "4a127eb1ad6131e99ed363cf7b53d6c49276a07ebf5e386d6b5351a41fbca63d",
+    "04e7c199894942b29e68479ee1714ee6ab6011e177be9511a90a5b2ed1f35259",
+    "0a815c814082d837ae3e7ef67c9eb7f52d951ac2cc41775154ad2239d2b5738e",
+    "e851e0abe4c7470e295fc80825786a63a7c012bf8572614ce7bd590848e04e67",
+    "314312210295cd9b5d4ce69a2e40664563db85e8caac4d47f7ddad7074e97ea0",
+    "3b26f6a404c36c774cb09fdf93165a78ab07a94cd73f849e4f52eec53a40e65e",
+    "8b7d2c79c72e5f9bb36a4e516c8ee54abc3d504a016655f10995b1fd03b580fb",
+);
+
+const RSA_E: &str = "010001";
+
+const RSA_D: &str = concat!(
+    "625c1bdae40844f8f2778d6699ee2eed22d2a4a956c96bab552d3ce553940dec",
+    "15974ca1717b126ab846574cffe7a7b85d15de24d5f247e447720a74b7783e75",
+    "251c94d0ec6b889e8b3e5b12898100ed87009459beaca0addefb9bba32028828",
+    "1b6c4de8e971a0f0f3fb2adf7a79165e79235c147c22504167aa9c27460ff602",
+    "afe205622b4112fffd811262577dc1e86b1aa16663cd1c4c1cc7f14880c9b105",
+    "b9f8070e44b252688e42f4584d7e88445b955e775345296f6f589f59e83a42bb",
+    "a5066db7352bf397920c943c95fbca5775ccf13c655120d7adf3f332a09d9242",
+    "a7ee98268f45308631bcb6c0f41b7d41afb2a6d0f5f21a4fac83fbc388a21f41",
+);
+
+const RSA_P: &str = concat!(
+    "fc3e4412f4074fe0b5b8feb1e64c2c4d10314426f1624fafc7704c410ef5dbf2",
+    "380889cada3f6189fbadb5e9893d456f116a8fd7898bff786f71a35e1bb63777",
+    "17bed2b3bfdd76895a93022340fc508b722cc46f41bf3eedda7d69332aa22610",
+    "17c026fa4192c2fb513f627f2f0bb620d4123c44097789f60de13efee7fa26bb",
+);
+
+const RSA_Q: &str = concat!(
+    "d74e9b247ac133ae80b4a4771e44933263f430a8d1f972582756f3fabfc8ca60",
+    "a08814b780bfe0bc2f8269d905e90cebd7470665d3d615f4b29e56eddbd9c649",
+    "9299ab5a83b28a4c8ba4fdf3e85037b925414448fa59559c2dd057e17808d962",
+    "cf464fc04ea1d5e14acf573a1384a5aa413c3b46579da4f40127923b8da10ac1",
+);
+
+const RSA_DP: &str = concat!(
+    "76b0fdeb4534a39b8326f781d272096e81ae0cf99e1fba8b65f72b8a7975aaf7",
+    "40f29394063a48b55ade14793d80bd495412a678af5a56edded2c422a63b89c1",
+    "da448a74d655694f9c750c4e7667c3a6ba24e291cf88241e5bef232f56625232",
+    "c45a407d0599ff71ae7b7ae789c5a2ffb38a991d12af53159ad6608425b809bd",
+);
+
+const RSA_DQ: &str = concat!(
+    "0a41b25e5082b7a58e780c85a8297821a4655394173c9cd7da37e8337fc35ef0",
+    "af750c1799c88a3a2cc137ada0fc732321043f27c6a9a22e1ffc2f03f1bf3414",
+    "f928dce2d8f73e635b1ba7056181c0c5cf2286d03b2cda49305d86723537a6b3",
+    "705057284016b18ba3f6b09f11a2f60960463044af93d0f1d6c43dd11c741981",
+);
+
+const RSA_CRT: &str = concat!(
+    "429a4cf8e7b0d982183f5fbda06b1af02be7c5699bc5c27ce1bceeb8d0fce278",
+    "e813870605a7aa71dbe639b67c4398d85daf3cd1e5bb02c86ce979ba8f8d032e",
+    "be19ff2f11c4b3ae1888ca9c10d9c1caf3320088027bd9046b482d913fefe2a9",
+    "fd24c560e92ab46751f6d9f4eb4949662a7c243f9556de955a01d08cfb902ad5",
+);
+
+fn h(s: &str) -> Vec<u8> {
+    hex::decode(s).unwrap()
+}
+
+fn new_rsa_ssa_pkcs1_private_key() -> RsaSsaPkcs1PrivateKey {
+    RsaSsaPkcs1PrivateKey {
+        version: 0,
+        public_key: Some(RsaSsaPkcs1PublicKey {
+            version: 0,
+            params: None,
+            n: h(RSA_N),
+            e: h(RSA_E),
+        }),
+        d: h(RSA_D),
+        p: h(RSA_P),
+        q: h(RSA_Q),
+        dp: h(RSA_DP),
+        dq: h(RSA_DQ),
+        crt: h(RSA_CRT),
+    }
+}
+
+#[test]
+fn test_validate_rsa_ssa_pkcs1_private_key() {
+    let key = new_rsa_ssa_pkcs1_private_key();
+    assert!(validate_rsa_ssa_pkcs1_private_key(&key).is_ok());
+
+    let mut corrupt_q = key.clone();
+    let last = corrupt_q.q.len() - 1;
+    corrupt_q.q[last] ^= 0x02;
+    expect_err(
+        validate_rsa_ssa_pkcs1_private_key(&corrupt_q),
+        "inconsistent with modulus",
+    );
+
+    // Swapping the prime factors leaves p * q == n but invalidates the CRT values.
+    let mut swapped = key.clone();
+    std::mem::swap(&mut swapped.p, &mut swapped.q);
+    expect_err(
+        validate_rsa_ssa_pkcs1_private_key(&swapped),
+        "CRT exponent inconsistent",
+    );
+
+    let mut corrupt_d = key.clone();
+    corrupt_d.d[0] ^= 0x01;
+    expect_err(
+        validate_rsa_ssa_pkcs1_private_key(&corrupt_d),
+        "private exponent inconsistent",
+    );
+
+    let mut corrupt_dq = key.clone();
+    corrupt_dq.dq[0] ^= 0x01;
+    expect_err(
+        validate_rsa_ssa_pkcs1_private_key(&corrupt_dq),
+        "CRT exponent inconsistent",
+    );
+
+    let mut corrupt_crt = key.clone();
+    corrupt_crt.crt[0] ^= 0x01;
+    expect_err(
+        validate_rsa_ssa_pkcs1_private_key(&corrupt_crt),
+        "CRT coefficient inconsistent",
+    );
+
+    let mut no_public = key;
+    no_public.public_key = None;
+    expect_err(
+        validate_rsa_ssa_pkcs1_private_key(&no_public),
+        "no public key",
+    );
+}
+
+#[test]
+fn test_validate_rsa_ssa_pss_private_key() {
+    let pkcs1 = new_rsa_ssa_pkcs1_private_key();
+    let public_key = pkcs1.public_key.unwrap();
+    let key = RsaSsaPssPrivateKey {
+        version: 0,
+        public_key: Some(RsaSsaPssPublicKey {
+            version: 0,
+            params: None,
+            n: public_key.n,
+            e: public_key.e,
+        }),
+        d: pkcs1.d,
+        p: pkcs1.p,
+        q: pkcs1.q,
+        dp: pkcs1.dp,
+        dq: pkcs1.dq,
+        crt: pkcs1.crt,
+    };
+    assert!(validate_rsa_ssa_pss_private_key(&key).is_ok());
+
+    let mut corrupt_q = key;
+    let last = corrupt_q.q.len() - 1;
+    corrupt_q.q[last] ^= 0x02;
+    expect_err(
+        validate_rsa_ssa_pss_private_key(&corrupt_q),
+        "inconsistent with modulus",
+    );
+}
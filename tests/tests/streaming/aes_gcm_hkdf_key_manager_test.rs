@@ -46,6 +46,37 @@ fn test_aes_gcm_hkdf_get_primitive_basic() {
     }
 }
 
+#[test]
+fn test_aes_gcm_hkdf_get_primitive_sha512() {
+    tink_streaming_aead::init();
+    let key_manager = tink_core::registry::get_key_manager(tink_tests::AES_GCM_HKDF_TYPE_URL)
+        .expect("cannot obtain AES-GCM-HKDF key manager");
+    let key = tink_tests::new_aes_gcm_hkdf_key(
+        tink_tests::AES_GCM_HKDF_KEY_VERSION,
+        32,
+        32,
+        HashType::Sha512 as i32,
+        4096,
+    );
+    let p = match key_manager.primitive(&proto_encode(&key)) {
+        Ok(tink_core::Primitive::StreamingAead(p)) => p,
+        _ => panic!("cannot get SHA-512 AES-GCM-HKDF primitive"),
+    };
+    encrypt_decrypt(p.box_clone(), p.box_clone(), 10000, 32).unwrap();
+
+    let key = tink_tests::new_aes_gcm_hkdf_key(
+        tink_tests::AES_GCM_HKDF_KEY_VERSION,
+        32,
+        32,
+        HashType::Sha1 as i32,
+        4096,
+    );
+    tink_tests::expect_err(
+        key_manager.primitive(&proto_encode(&key)),
+        "unsupported HKDF hash type",
+    );
+}
+
 #[test]
 fn test_aes_gcm_hkdf_get_primitive_with_invalid_input() {
     tink_streaming_aead::init();
@@ -274,6 +305,14 @@ fn gen_invalid_aes_gcm_hkdf_keys() -> Vec<Vec<u8>> {
             9999,
             4096,
         )),
+        // unsupported hash
+        proto_encode(&tink_tests::new_aes_gcm_hkdf_key(
+            tink_tests::AES_GCM_KEY_VERSION,
+            16,
+            16,
+            HashType::Sha1 as i32,
+            4096,
+        )),
     ]
 }
 
@@ -0,0 +1,222 @@
+// Copyright 2020 The Tink-Rust Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tink_core::{subtle::random::get_random_bytes, StreamingAead, TinkError};
+use tink_proto::HashType;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt, ReadBuf};
+
+/// [`AsyncRead`] implementation that returns data in small chunks, and is only ready on
+/// alternate polls.
+struct ChunkedReader {
+    data: Vec<u8>,
+    pos: usize,
+    chunk_size: usize,
+    ready: bool,
+}
+
+impl ChunkedReader {
+    fn new(data: Vec<u8>, chunk_size: usize) -> Self {
+        Self {
+            data,
+            pos: 0,
+            chunk_size,
+            ready: false,
+        }
+    }
+}
+
+impl AsyncRead for ChunkedReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        if !self.ready {
+            self.ready = true;
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        self.ready = false;
+        let n = std::cmp::min(
+            std::cmp::min(self.chunk_size, buf.remaining()),
+            self.data.len() - self.pos,
+        );
+        buf.put_slice(&self.data[self.pos..self.pos + n]);
+        self.pos += n;
+        Poll::Ready(Ok(()))
+    }
+}
+
+async fn async_encrypt(a: &dyn StreamingAead, pt: &[u8], aad: &[u8]) -> Vec<u8> {
+    let mut w = tink_streaming_aead::new_async_encrypting_writer(a, Vec::new(), aad).unwrap();
+    // Write in uneven pieces that do not line up with segment boundaries.
+    for piece in pt.chunks(3001) {
+        w.write_all(piece).await.unwrap();
+    }
+    w.shutdown().await.unwrap();
+    w.into_inner()
+}
+
+async fn async_decrypt(
+    a: &dyn StreamingAead,
+    ct: &[u8],
+    aad: &[u8],
+    chunk_size: usize,
+) -> std::io::Result<Vec<u8>> {
+    let mut r = tink_streaming_aead::new_async_decrypting_reader(
+        a,
+        ChunkedReader::new(ct.to_vec(), chunk_size),
+        aad,
+    )
+    .unwrap();
+    let mut pt = Vec::new();
+    r.read_to_end(&mut pt).await?;
+    Ok(pt)
+}
+
+#[tokio::test]
+async fn test_async_round_trip() {
+    tink_streaming_aead::init();
+    let pt = get_random_bytes(5 * 1024 * 1024 + 17);
+    let aad = b"aad";
+    for template in &[
+        tink_streaming_aead::aes128_gcm_hkdf_4kb_key_template(),
+        tink_streaming_aead::aes256_ctr_hmac_sha256_segment_1mb_key_template(),
+    ] {
+        let kh = tink_core::keyset::Handle::new(template).unwrap();
+        let a = tink_streaming_aead::new(&kh).unwrap();
+        assert!(a.max_read_ahead().is_some());
+
+        let ct = async_encrypt(a.as_ref(), &pt, aad).await;
+        // Same framing as the synchronous API.
+        assert_eq!(a.decrypt_all(&ct, aad).unwrap(), pt);
+
+        for chunk_size in &[1000, 65536] {
+            assert_eq!(
+                async_decrypt(a.as_ref(), &ct, aad, *chunk_size)
+                    .await
+                    .unwrap(),
+                pt
+            );
+        }
+        let sync_ct = a.encrypt_all(&pt, aad).unwrap();
+        assert_eq!(
+            async_decrypt(a.as_ref(), &sync_ct, aad, 4096)
+                .await
+                .unwrap(),
+            pt
+        );
+
+        let mut corrupted = ct.clone();
+        let last = corrupted.len() - 1;
+        corrupted[last] ^= 0x01;
+        assert!(async_decrypt(a.as_ref(), &corrupted, aad, 4096)
+            .await
+            .is_err());
+        assert!(async_decrypt(a.as_ref(), &ct, b"other aad", 4096)
+            .await
+            .is_err());
+    }
+}
+
+#[tokio::test]
+async fn test_async_round_trip_single_bytes() {
+    tink_streaming_aead::init();
+    let pt = get_random_bytes(20_000);
+    let kh =
+        tink_core::keyset::Handle::new(&tink_streaming_aead::aes128_gcm_hkdf_4kb_key_template())
+            .unwrap();
+    let a = tink_streaming_aead::new(&kh).unwrap();
+    let ct = async_encrypt(a.as_ref(), &pt, b"aad").await;
+    assert_eq!(async_decrypt(a.as_ref(), &ct, b"aad", 1).await.unwrap(), pt);
+}
+
+#[tokio::test]
+async fn test_async_without_read_ahead_bound() {
+    let a = tink_tests::DummyStreamingAead;
+    assert_eq!(a.max_read_ahead(), None);
+    let result = tink_streaming_aead::new_async_decrypting_reader(
+        &a,
+        ChunkedReader::new(vec![], 1000),
+        b"aad",
+    );
+    tink_tests::expect_err(result, "no bound on ciphertext read ahead");
+}
+
+/// Wrapper around a [`StreamingAead`] that under-reports how much ciphertext its readers need.
+#[derive(Clone)]
+struct LowReadAhead(tink_streaming_aead::subtle::AesGcmHkdf);
+
+impl StreamingAead for LowReadAhead {
+    fn new_encrypting_writer(
+        &self,
+        w: Box<dyn std::io::Write>,
+        aad: &[u8],
+    ) -> Result<Box<dyn tink_core::EncryptingWrite>, TinkError> {
+        self.0.new_encrypting_writer(w, aad)
+    }
+    fn new_decrypting_reader(
+        &self,
+        r: Box<dyn std::io::Read>,
+        aad: &[u8],
+    ) -> Result<Box<dyn std::io::Read>, TinkError> {
+        self.0.new_decrypting_reader(r, aad)
+    }
+    fn max_read_ahead(&self) -> Option<usize> {
+        Some(1)
+    }
+}
+
+#[tokio::test]
+async fn test_async_round_trip_low_read_ahead_bound() {
+    let a = LowReadAhead(
+        tink_streaming_aead::subtle::AesGcmHkdf::new(
+            &get_random_bytes(32),
+            HashType::Sha256,
+            16,
+            4096,
+            0,
+        )
+        .unwrap(),
+    );
+    let pt = get_random_bytes(50_000);
+    let ct = async_encrypt(&a, &pt, b"aad").await;
+    for chunk_size in &[1, 1000] {
+        assert_eq!(
+            async_decrypt(&a, &ct, b"aad", *chunk_size).await.unwrap(),
+            pt
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_async_empty_plaintext() {
+    tink_streaming_aead::init();
+    let kh =
+        tink_core::keyset::Handle::new(&tink_streaming_aead::aes128_gcm_hkdf_4kb_key_template())
+            .unwrap();
+    let a = tink_streaming_aead::new(&kh).unwrap();
+    let ct = async_encrypt(a.as_ref(), &[], b"aad").await;
+    assert!(!ct.is_empty());
+    assert!(async_decrypt(a.as_ref(), &ct, b"aad", 10)
+        .await
+        .unwrap()
+        .is_empty());
+}
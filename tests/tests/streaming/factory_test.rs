@@ -14,7 +14,9 @@
 //
 ////////////////////////////////////////////////////////////////////////////////
 
-use tink_core::{utils::wrap_err, TinkError};
+use std::io::{Read, Write};
+use tink_core::{subtle::random::get_random_bytes, utils::wrap_err, TinkError};
+use tink_tests::SharedBuf;
 
 use super::common::encrypt_decrypt;
 
@@ -151,3 +153,71 @@ fn test_key_rotation() {
     assert!(validate_factory_cipher(a3.box_clone(), a3.box_clone()).is_ok());
     assert!(validate_factory_cipher(a3.box_clone(), a4.box_clone()).is_ok());
 }
+
+#[test]
+fn test_factory_encrypt_all_decrypt_all() {
+    tink_streaming_aead::init();
+    for kt in [
+        tink_streaming_aead::aes128_gcm_hkdf_4kb_key_template(),
+        tink_streaming_aead::aes128_ctr_hmac_sha256_segment_4kb_key_template(),
+    ] {
+        let kh = tink_core::keyset::Handle::new(&kt).unwrap();
+        let a = tink_streaming_aead::new(&kh).expect("tink_streaming_aead::new failed");
+        let aad = b"aad";
+
+        for pt_size in [0, 1, 4000, 4096, 4097, 16384] {
+            let pt = get_random_bytes(pt_size);
+
+            // Output of `encrypt_all` can be decrypted via the streaming reader...
+            let ct = a.encrypt_all(&pt, aad).expect("encrypt_all failed");
+            let mut r = a
+                .new_decrypting_reader(Box::new(std::io::Cursor::new(ct.clone())), aad)
+                .unwrap();
+            let mut got = vec![];
+            r.read_to_end(&mut got)
+                .expect("streaming decryption failed");
+            assert_eq!(got, pt, "plaintext size {pt_size}");
+            assert_eq!(a.decrypt_all(&ct, aad).unwrap(), pt);
+
+            // ...and the output of the streaming writer can be decrypted via `decrypt_all`.
+            let buf = SharedBuf::new();
+            let mut w = a.new_encrypting_writer(Box::new(buf.clone()), aad).unwrap();
+            w.write_all(&pt).unwrap();
+            w.close().unwrap();
+            let streamed_ct = buf.contents();
+            assert_eq!(streamed_ct.len(), ct.len());
+            assert_eq!(a.decrypt_all(&streamed_ct, aad).unwrap(), pt);
+
+            tink_tests::expect_err(a.decrypt_all(&ct, b"wrong aad"), "no matching key");
+            tink_tests::expect_err(a.decrypt_all(&ct[..ct.len() - 1], aad), "no matching key");
+        }
+    }
+
+    // The default trait implementations go via the writer and reader.
+    let d = tink_tests::DummyStreamingAead;
+    let ct = tink_core::StreamingAead::encrypt_all(&d, b"data", b"aad").unwrap();
+    assert_eq!(ct, b"data");
+    let pt = tink_core::StreamingAead::decrypt_all(&d, &ct, b"aad").unwrap();
+    assert_eq!(pt, b"data");
+}
+
+#[test]
+fn test_factory_size_prediction() {
+    tink_streaming_aead::init();
+    let keyset_handle =
+        tink_core::keyset::insecure::new_handle(tink_tests::new_test_aes_gcm_hkdf_keyset())
+            .unwrap();
+    let a = tink_streaming_aead::new(&keyset_handle).unwrap();
+    for pt_len in [0, 1, 4095, 4096, 4097, 16384] {
+        let ct = a.encrypt_all(&vec![0; pt_len], b"aad").unwrap();
+        assert_eq!(
+            a.ciphertext_size(pt_len as u64),
+            Some(ct.len() as u64),
+            "plaintext length {}",
+            pt_len
+        );
+    }
+    // The key that produced a ciphertext is not known up front, so its plaintext size is not
+    // either.
+    tink_tests::expect_err(a.plaintext_size(100), "not available");
+}
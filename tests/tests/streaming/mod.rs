@@ -18,6 +18,7 @@ pub mod common;
 
 mod aes_ctr_hmac_key_manager_test;
 mod aes_gcm_hkdf_key_manager_test;
+mod async_io_test;
 mod factory_test;
 mod integration_test;
 mod key_templates_test;
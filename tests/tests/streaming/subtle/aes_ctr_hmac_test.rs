@@ -14,6 +14,7 @@
 //
 ////////////////////////////////////////////////////////////////////////////////
 
+use tink_core::StreamingAead;
 use tink_streaming_aead::subtle;
 
 #[test]
@@ -223,6 +224,18 @@ fn test_aes_ctr_hmac_encrypt_decrypt() {
         .unwrap_or_else(|e| panic!("{}: cannot create cipher: {:?}", tc.name, e));
         let (pt, ct) = super::encrypt(&cipher, super::AAD, tc.plaintext_size)
             .unwrap_or_else(|e| panic!("{}: failure during encryption: {:?}", tc.name, e));
+        assert_eq!(
+            ct.len() as u64,
+            cipher.ciphertext_size(pt.len() as u64).unwrap(),
+            "{}",
+            tc.name
+        );
+        assert_eq!(
+            cipher.plaintext_size(ct.len() as u64).unwrap(),
+            pt.len() as u64,
+            "{}",
+            tc.name
+        );
         assert!(
             super::decrypt(&cipher, super::AAD, &pt, &ct, tc.chunk_size).is_ok(),
             "{}: failure during decryption",
@@ -335,3 +348,29 @@ fn test_aes_ctr_hmac_modified_ciphertext() {
         );
     }
 }
+
+#[test]
+fn test_aes_ctr_hmac_wrong_aad_fails_on_first_segment() {
+    let segment_size = 256;
+    let cipher = subtle::AesCtrHmac::new(
+        super::IKM,
+        tink_proto::HashType::Sha256,
+        16,
+        tink_proto::HashType::Sha256,
+        16,
+        segment_size,
+        0,
+    )
+    .expect("Cannot create a cipher");
+    let (_pt, ct) = super::encrypt(&cipher, super::AAD, 4 * segment_size).unwrap();
+
+    let mut aad2 = super::AAD.to_vec();
+    aad2[0] ^= 0x01;
+    let mut r = cipher
+        .new_decrypting_reader(Box::new(std::io::Cursor::new(ct)), &aad2)
+        .expect("cannot create decrypting reader");
+    // The associated data is bound into the derived keys, so the very first segment must fail
+    // to decrypt rather than yielding any plaintext.
+    let mut chunk = vec![0; segment_size];
+    assert!(r.read(&mut chunk).is_err(), "expected error on first read");
+}
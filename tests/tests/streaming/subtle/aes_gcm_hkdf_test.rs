@@ -14,6 +14,7 @@
 //
 ////////////////////////////////////////////////////////////////////////////////
 
+use tink_core::StreamingAead;
 use tink_proto::HashType;
 use tink_streaming_aead::subtle;
 
@@ -200,11 +201,75 @@ fn test_aes_gcm_hkdf_encrypt_decrypt() {
         .unwrap_or_else(|e| panic!("{}: Cannot create a cipher: {:?}", tc.name, e));
 
         let (pt, ct) = super::encrypt(&cipher, super::AAD, tc.plaintext_size).unwrap();
+        assert_eq!(
+            ct.len() as u64,
+            cipher.ciphertext_size(pt.len() as u64).unwrap(),
+            "{}",
+            tc.name
+        );
+        assert_eq!(
+            cipher.plaintext_size(ct.len() as u64).unwrap(),
+            pt.len() as u64,
+            "{}",
+            tc.name
+        );
 
         assert!(super::decrypt(&cipher, super::AAD, &pt, &ct, tc.chunk_size).is_ok());
     }
 }
 
+#[test]
+fn test_aes_gcm_hkdf_size_prediction() {
+    let segment_size = 256;
+    let cipher =
+        subtle::AesGcmHkdf::new(super::IKM, HashType::Sha256, 16, segment_size, 0).unwrap();
+    let header_len = cipher.header_length();
+    let tag_len = 16;
+    let first_pt_len = segment_size - header_len - tag_len;
+    let pt_segment_len = segment_size - tag_len;
+
+    // Sizes around segment boundaries, where an extra segment (and tag) starts.
+    for pt_len in [
+        0,
+        1,
+        first_pt_len,
+        first_pt_len + 1,
+        first_pt_len + pt_segment_len,
+        first_pt_len + pt_segment_len + 1,
+        10 * pt_segment_len,
+    ] {
+        let ct = cipher.encrypt_all(&vec![0; pt_len], super::AAD).unwrap();
+        assert_eq!(
+            ct.len() as u64,
+            cipher.ciphertext_size(pt_len as u64).unwrap(),
+            "plaintext length {}",
+            pt_len
+        );
+        assert_eq!(
+            cipher.plaintext_size(ct.len() as u64).unwrap(),
+            pt_len as u64
+        );
+    }
+    assert_eq!(
+        cipher.ciphertext_size(first_pt_len as u64 + 1).unwrap(),
+        (segment_size + tag_len + 1) as u64
+    );
+
+    // Lengths that no ciphertext can have.
+    for ct_len in [
+        0,
+        header_len,
+        header_len + tag_len - 1,
+        segment_size + tag_len - 1,
+    ] {
+        assert!(
+            cipher.plaintext_size(ct_len as u64).is_err(),
+            "ciphertext length {}",
+            ct_len
+        );
+    }
+}
+
 #[test]
 fn test_aes_gcm_hkdf_invalid_params() {
     struct TestCase {
@@ -248,6 +313,14 @@ fn test_aes_gcm_hkdf_invalid_params() {
             segment_size: 2,
             first_segment_offset: 0,
         },
+        TestCase {
+            err_msg: "unsupported HKDF hash",
+            hash: HashType::Sha1,
+            key: vec![0; 32],
+            key_size_in_bytes: 16,
+            segment_size: 256,
+            first_segment_offset: 0,
+        },
     ];
     for tc in test_cases {
         let result = subtle::AesGcmHkdf::new(
@@ -362,3 +435,21 @@ fn test_aes_gcm_hkdf_modified_ciphertext() {
         );
     }
 }
+
+#[test]
+fn test_aes_gcm_hkdf_wrong_aad_fails_on_first_segment() {
+    let segment_size = 256;
+    let cipher = subtle::AesGcmHkdf::new(super::IKM, HashType::Sha256, 16, segment_size, 0)
+        .expect("Cannot create a cipher");
+    let (_pt, ct) = super::encrypt(&cipher, super::AAD, 4 * segment_size).unwrap();
+
+    let mut aad2 = super::AAD.to_vec();
+    aad2[0] ^= 0x01;
+    let mut r = cipher
+        .new_decrypting_reader(Box::new(std::io::Cursor::new(ct)), &aad2)
+        .expect("cannot create decrypting reader");
+    // The associated data is bound into the derived key, so the very first segment must fail
+    // to decrypt rather than yielding any plaintext.
+    let mut chunk = vec![0; segment_size];
+    assert!(r.read(&mut chunk).is_err(), "expected error on first read");
+}
@@ -118,6 +118,60 @@ struct TestParams {
     first_ciphertext_segment_offset: usize,
 }
 
+#[test]
+fn test_nonce_based_encrypt_all_matches_writer() {
+    let params = TestParams {
+        nonce_size: 10,
+        plaintext_segment_size: 20,
+        first_ciphertext_segment_offset: 8,
+    };
+    // The first segment holds 12 bytes of plaintext, subsequent segments 20 bytes.
+    for plaintext_size in [0, 1, 11, 12, 13, 31, 32, 33, 100] {
+        let result = test_encrypt(plaintext_size, 5, &params).unwrap();
+
+        let mut ciphertext = vec![];
+        noncebased::encrypt_all(
+            &TestEncrypter {},
+            params.nonce_size,
+            &result.nonce_prefix,
+            params.plaintext_segment_size,
+            params.first_ciphertext_segment_offset,
+            &result.plaintext,
+            &mut ciphertext,
+        )
+        .unwrap();
+        assert_eq!(
+            hex::encode(&ciphertext),
+            hex::encode(&result.ciphertext),
+            "plaintext size {plaintext_size}"
+        );
+
+        let plaintext = noncebased::decrypt_all(
+            &TestDecrypter {},
+            params.nonce_size,
+            &result.nonce_prefix,
+            params.plaintext_segment_size + params.nonce_size,
+            params.first_ciphertext_segment_offset,
+            &result.ciphertext,
+        )
+        .unwrap();
+        assert_eq!(
+            plaintext, result.plaintext,
+            "plaintext size {plaintext_size}"
+        );
+    }
+
+    let result = noncebased::decrypt_all(
+        &TestDecrypter {},
+        params.nonce_size,
+        &[0; 5],
+        params.plaintext_segment_size + params.nonce_size,
+        params.first_ciphertext_segment_offset,
+        &[],
+    );
+    tink_tests::expect_err(result, "too short");
+}
+
 #[test]
 fn test_nonce_based_invalid_parameters() {
     struct TestCase {